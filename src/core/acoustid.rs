@@ -0,0 +1,109 @@
+//! core/acoustid.rs
+//! Acoustic fingerprinting (Chromaprint) + AcoustID lookup.
+//!
+//! - Fingerprints are computed from decoded PCM (same Symphonia path as playback),
+//!   over the first two minutes, matching what `fpcalc` submits by default.
+//! - AcoustID maps a fingerprint + duration to MusicBrainz recording ids; the
+//!   GUI then feeds the best id into `musicbrainz::lookup_by_recording_id`.
+//! - Lookups need an application API key (free, from acoustid.org).
+
+use std::path::Path;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rodio::Source;
+use rusty_chromaprint::{Configuration, FingerprintCompressor, Fingerprinter};
+use serde_json::Value;
+
+use super::playback::open_source_at_ms;
+
+const LOOKUP_URL: &str = "https://api.acoustid.org/v2/lookup";
+
+/// Seconds of audio fingerprinted (fpcalc's default `-length`).
+const MAX_SECONDS: usize = 120;
+
+/// Results below this score (0.0..=1.0) are treated as "no match".
+const MIN_SCORE: f64 = 0.5;
+
+/// Compute the compressed, base64url-encoded Chromaprint fingerprint of `path`.
+///
+/// Blocking (decodes up to two minutes of audio); run it off the UI thread.
+pub fn fingerprint_file(path: &Path) -> Result<String, String> {
+    let (mut src, _duration) = open_source_at_ms(path, 0)?;
+    let sample_rate = src.sample_rate();
+    let channels = src.channels();
+
+    // preset_test2 is Chromaprint's default algorithm (the one AcoustID indexes).
+    let config = Configuration::preset_test2();
+    let mut printer = Fingerprinter::new(&config);
+    printer
+        .start(sample_rate, u32::from(channels))
+        .map_err(|e| format!("Fingerprinter init failed: {e:?}"))?;
+
+    let max_samples = sample_rate as usize * usize::from(channels) * MAX_SECONDS;
+    let mut chunk: Vec<i16> = Vec::with_capacity(16 * 1024);
+    for s in src.by_ref().take(max_samples) {
+        chunk.push((s.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16);
+        if chunk.len() == chunk.capacity() {
+            printer.consume(&chunk);
+            chunk.clear();
+        }
+    }
+    printer.consume(&chunk);
+    printer.finish();
+
+    let raw = printer.fingerprint();
+    if raw.is_empty() {
+        return Err("Not enough audio to fingerprint".to_string());
+    }
+
+    let compressed = FingerprintCompressor::from(&config).compress(raw);
+    Ok(URL_SAFE_NO_PAD.encode(compressed))
+}
+
+/// Best-scoring MusicBrainz recording id for a fingerprint (`Ok(None)` if unknown).
+pub async fn lookup_recording_id(
+    api_key: &str,
+    fingerprint: &str,
+    duration_s: u64,
+) -> Result<Option<String>, String> {
+    let duration = duration_s.to_string();
+    let form = [
+        ("client", api_key.trim()),
+        ("meta", "recordingids"),
+        ("duration", duration.as_str()),
+        ("fingerprint", fingerprint),
+        ("format", "json"),
+    ];
+
+    // Fingerprints run to several KB: POST keeps them out of the URL.
+    let resp = reqwest::Client::new()
+        .post(LOOKUP_URL)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("AcoustID request failed: {e}"))?;
+
+    let body: Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("AcoustID response was not JSON: {e}"))?;
+
+    if body["status"].as_str() != Some("ok") {
+        let msg = body["error"]["message"].as_str().unwrap_or("unknown error");
+        return Err(format!("AcoustID error: {msg}"));
+    }
+
+    let best = body["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|r| r["score"].as_f64().unwrap_or(0.0) >= MIN_SCORE)
+        .filter_map(|r| {
+            let id = r["recordings"].as_array()?.first()?["id"].as_str()?;
+            Some((r["score"].as_f64().unwrap_or(0.0), id))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+
+    Ok(best.map(|(_, id)| id.to_string()))
+}
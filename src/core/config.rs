@@ -0,0 +1,144 @@
+//! core/config.rs
+//!
+//! User preferences persisted to disk as TOML.
+//!
+//! - Location: `<config dir>/sonora/config.toml` (e.g. `~/.config/sonora/config.toml`)
+//! - Missing file or missing keys => defaults (first launch just works)
+//! - Unparseable file => defaults (we never refuse to start over a bad config)
+//!
+//! This module knows nothing about the GUI: view modes etc. are stored as plain strings
+//! and mapped by the GUI layer.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::scrobbler::ScrobblerConfig;
+
+const APP_DIR: &str = "sonora";
+const FILE_NAME: &str = "config.toml";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Library root folders, in the order the user added them.
+    pub roots: Vec<PathBuf>,
+
+    /// Scan exclusion patterns (glob like `*/podcasts/*`, else substring).
+    pub exclusions: Vec<String>,
+
+    /// Playback volume (0.0..=1.0).
+    pub volume: f32,
+
+    /// Last list mode ("albums" | "tracks").
+    pub view_mode: String,
+
+    /// Inspector "Show more tags" toggle.
+    pub show_extended: bool,
+
+    /// ReplayGain normalization ("off" | "track" | "album").
+    pub replaygain: String,
+
+    /// Crossfade between consecutive tracks in ms (0 = gapless).
+    pub crossfade_ms: u64,
+
+    /// Output device name (`None` = system default).
+    pub output_device: Option<String>,
+
+    /// Last.fm credentials + session (empty = scrobbling off).
+    pub scrobbler: ScrobblerConfig,
+
+    /// AcoustID application key for fingerprint lookups (empty = feature unavailable).
+    pub acoustid_api_key: String,
+
+    /// Command for "Open in external editor" (e.g. `open -a Mp3tag`,
+    /// `"C:\Program Files\Mp3tag\Mp3tag.exe"`); the file paths are appended.
+    pub external_tag_editor: Option<String>,
+
+    /// POPM owner for star ratings (empty = Last.fm username, else "sonora").
+    pub rating_email: String,
+
+    /// ID3 version for tag writes ("2.4" | "2.3").
+    pub tag_version: String,
+
+    /// Copy each file to `<name>.bak` before writing its tags.
+    pub backup_on_write: bool,
+
+    /// Check each MP3's frame structure before writing its tags (refuse corrupt files).
+    pub validate_before_write: bool,
+
+    /// Group albums by (album artist, album, year) instead of (album artist, album).
+    pub album_group_by_year: bool,
+
+    /// Denser track table and album list rows.
+    pub compact_mode: bool,
+
+    /// Track table columns in display order ("title", "artist", ...; empty = all).
+    pub track_columns: Vec<String>,
+
+    /// Track table column widths in px, by the same keys (missing = built-in width).
+    pub column_widths: BTreeMap<String, f32>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            exclusions: Vec::new(),
+            volume: 1.0,
+            view_mode: "tracks".to_string(),
+            show_extended: false,
+            replaygain: "off".to_string(),
+            crossfade_ms: 0,
+            output_device: None,
+            scrobbler: ScrobblerConfig::default(),
+            acoustid_api_key: String::new(),
+            external_tag_editor: None,
+            rating_email: String::new(),
+            tag_version: "2.4".to_string(),
+            backup_on_write: false,
+            validate_before_write: false,
+            album_group_by_year: false,
+            compact_mode: false,
+            track_columns: Vec::new(),
+            column_widths: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Full path of the config file, if the platform has a config dir.
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join(APP_DIR).join(FILE_NAME))
+    }
+
+    /// Load config from disk, falling back to defaults on any problem.
+    pub fn load() -> Config {
+        let Some(path) = Self::path() else {
+            return Config::default();
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(s) => toml::from_str(&s).unwrap_or_else(|_e| {
+                #[cfg(debug_assertions)]
+                eprintln!("[CONFIG] parse failed for {}: {_e}", path.display());
+                Config::default()
+            }),
+            Err(_) => Config::default(),
+        }
+    }
+
+    /// Write config to disk (creating the parent directory if needed).
+    pub fn save(&self) -> Result<(), String> {
+        let path =
+            Self::path().ok_or_else(|| "No config directory on this platform".to_string())?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+        }
+
+        let s = toml::to_string_pretty(self).map_err(|e| format!("Serialize failed: {e}"))?;
+        std::fs::write(&path, s).map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
@@ -0,0 +1,387 @@
+//! core/db.rs
+//!
+//! SQLite-backed library cache.
+//!
+//! Purpose: make rescans incremental.
+//! - Each file is stored once, keyed by its canonical path (`cache_key`), with its
+//!   last-modified time + size.
+//! - Paths are stored as raw bytes, so names that aren't valid UTF-8 never collide.
+//! - If a file's (mtime, size) still match, its cached `TrackRow` is reused (no tag IO).
+//! - New or changed files are re-read and upserted.
+//!
+//! Identity:
+//! - `tracks.id` is an `INTEGER PRIMARY KEY` and *is* the `TrackId`.
+//! - Upserts keep the id for an existing path, so ids are stable across rescans.
+//!
+//! Storage:
+//! - `TrackRow` is stored as JSON (`row_json`). The metadata struct changes often;
+//!   a serialized blob avoids a schema migration for every new field.
+//! - If a cached blob no longer deserializes, it is treated as a cache miss.
+//!
+//! Migrations:
+//! - `PRAGMA user_version` records how many entries of `MIGRATIONS` have run.
+//! - Append new migrations; never edit old ones.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use rusqlite::{Connection, params};
+
+use super::types::{TrackId, TrackRow};
+
+const APP_DIR: &str = "sonora";
+const FILE_NAME: &str = "library.db";
+
+/// Ordered schema migrations. Index + 1 == resulting `user_version`.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE tracks (
+        id         INTEGER PRIMARY KEY,
+        path       TEXT NOT NULL UNIQUE,
+        mtime_ns   INTEGER NOT NULL,
+        size       INTEGER NOT NULL,
+        tag_failed INTEGER NOT NULL,
+        row_json   TEXT NOT NULL
+    );",
+    // Durations now come from a stream probe: force every cached row to be re-read
+    // (ids are kept; only the stamp is invalidated).
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `tag_version` + ID3v1 fallback: cached rows would read back as "untagged".
+    "UPDATE tracks SET mtime_ns = -1;",
+    // Numeric TCON genres ("(13)") are now expanded to names on read.
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `volume_trim_db` (TXXX:SONORA_TRIM_DB): cached rows would read back as "no trim".
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `date_added` (TXXX:SONORA_DATE_ADDED): cached rows would all be stamped "added now".
+    "UPDATE tracks SET mtime_ns = -1;",
+    // Why a file's tags failed to read (scan error log). NULL on older failed rows.
+    "ALTER TABLE tracks ADD COLUMN tag_error TEXT;",
+    // `channels` (stream probe): cached rows would read back with no channel count.
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `is_favorite` (TXXX:SONORA_FAVORITE): cached rows would read back as "not loved".
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `tag_format`: cached rows would read back with no tag format.
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `mb_recording_id` / `mb_release_id`: cached rows would read back without them.
+    "UPDATE tracks SET mtime_ns = -1;",
+    // `path` holds raw bytes (BLOB) instead of lossy text; existing UTF-8 paths carry over.
+    "CREATE TABLE tracks_new (
+        id         INTEGER PRIMARY KEY,
+        path       BLOB NOT NULL UNIQUE,
+        mtime_ns   INTEGER NOT NULL,
+        size       INTEGER NOT NULL,
+        tag_failed INTEGER NOT NULL,
+        row_json   TEXT NOT NULL,
+        tag_error  TEXT
+    );
+    INSERT INTO tracks_new (id, path, mtime_ns, size, tag_failed, row_json, tag_error)
+        SELECT id, CAST(path AS BLOB), mtime_ns, size, tag_failed, row_json, tag_error
+        FROM tracks;
+    DROP TABLE tracks;
+    ALTER TABLE tracks_new RENAME TO tracks;",
+];
+
+/// A cached row as stored in the DB (`row.id` is the DB id).
+pub struct CachedTrack {
+    pub mtime_ns: i64,
+    pub size: i64,
+    /// `Some(reason)` when the tags could not be read.
+    pub tag_error: Option<String>,
+    pub row: TrackRow,
+}
+
+/// File fingerprint used for cache validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub mtime_ns: i64,
+    pub size: i64,
+}
+
+impl FileStamp {
+    /// Read (mtime, size) for a file. `None` if metadata is unavailable.
+    pub fn of(path: &Path) -> Option<FileStamp> {
+        let md = std::fs::metadata(path).ok()?;
+        let mtime_ns = md
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos() as i64;
+        Some(FileStamp {
+            mtime_ns,
+            size: md.len() as i64,
+        })
+    }
+}
+
+/// The path a file is cached under: canonical (symlinks, `.` and `..` resolved), so one
+/// file is one row however it was reached. A path that no longer exists (the old side
+/// of a rename) canonicalizes its folder instead; failing that, it is used as-is.
+pub fn cache_key(path: &Path) -> PathBuf {
+    if let Ok(p) = std::fs::canonicalize(path) {
+        return p;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => std::fs::canonicalize(dir)
+            .map(|d| d.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Stored form of a path: its exact bytes on Unix.
+#[cfg(unix)]
+fn path_to_blob(path: &Path) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(unix)]
+fn path_from_blob(bytes: Vec<u8>) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+// Elsewhere paths are (near enough always) valid Unicode; store them as UTF-8.
+#[cfg(not(unix))]
+fn path_to_blob(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+#[cfg(not(unix))]
+fn path_from_blob(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+pub struct LibraryDb {
+    conn: Connection,
+}
+
+impl LibraryDb {
+    /// Default location: `<data dir>/sonora/library.db`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|d| d.join(APP_DIR).join(FILE_NAME))
+    }
+
+    /// Open (or create) the cache at its default location.
+    pub fn open_default() -> Result<LibraryDb, String> {
+        let path =
+            Self::default_path().ok_or_else(|| "No data directory on this platform".to_string())?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+        }
+        Self::open(&path)
+    }
+
+    /// Open (or create) the cache at `path` and bring the schema up to date.
+    pub fn open(path: &Path) -> Result<LibraryDb, String> {
+        let conn = Connection::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut db = LibraryDb { conn };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        let version: i64 = self
+            .conn
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .map_err(|e| format!("Read schema version failed: {e}"))?;
+
+        let start = version.max(0) as usize;
+        if start > MIGRATIONS.len() {
+            return Err(format!(
+                "Library cache schema v{version} is newer than this build supports (v{})",
+                MIGRATIONS.len()
+            ));
+        }
+
+        for (i, sql) in MIGRATIONS.iter().enumerate().skip(start) {
+            let tx = self
+                .conn
+                .transaction()
+                .map_err(|e| format!("Migration {} failed: {e}", i + 1))?;
+            tx.execute_batch(sql)
+                .map_err(|e| format!("Migration {} failed: {e}", i + 1))?;
+            // PRAGMA does not accept bound parameters.
+            tx.execute_batch(&format!("PRAGMA user_version = {}", i + 1))
+                .map_err(|e| format!("Migration {} failed: {e}", i + 1))?;
+            tx.commit()
+                .map_err(|e| format!("Migration {} failed: {e}", i + 1))?;
+        }
+
+        Ok(())
+    }
+
+    /// Load the whole cache keyed by `cache_key` path (one query instead of one per file).
+    pub fn load_all(&self) -> Result<HashMap<PathBuf, CachedTrack>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, path, mtime_ns, size, tag_failed, tag_error, row_json FROM tracks")
+            .map_err(|e| format!("Cache query failed: {e}"))?;
+
+        let rows = stmt
+            .query_map([], |r| {
+                Ok((
+                    r.get::<_, i64>(0)?,
+                    r.get::<_, Vec<u8>>(1)?,
+                    r.get::<_, i64>(2)?,
+                    r.get::<_, i64>(3)?,
+                    r.get::<_, bool>(4)?,
+                    r.get::<_, Option<String>>(5)?,
+                    r.get::<_, String>(6)?,
+                ))
+            })
+            .map_err(|e| format!("Cache query failed: {e}"))?;
+
+        let mut out = HashMap::new();
+        for r in rows {
+            let (id, path, mtime_ns, size, tag_failed, tag_error, json) =
+                r.map_err(|e| format!("Cache read failed: {e}"))?;
+            let path = path_from_blob(path);
+            // Rows cached before `tag_error` existed only know that reading failed.
+            let tag_error = tag_error.or_else(|| tag_failed.then(|| "Tag read failed".to_string()));
+
+            // Stale/incompatible blob => treat as a miss (it will be re-read + overwritten).
+            let Ok(mut row) = serde_json::from_str::<TrackRow>(&json) else {
+                continue;
+            };
+            row.id = Some(id);
+            row.path = path.clone();
+
+            out.insert(
+                path,
+                CachedTrack {
+                    mtime_ns,
+                    size,
+                    tag_error,
+                    row,
+                },
+            );
+        }
+
+        Ok(out)
+    }
+
+    /// Insert or update rows in one transaction and return their ids (same order as input).
+    ///
+    /// Existing paths (by `cache_key`) keep their id.
+    pub fn upsert_many(
+        &mut self,
+        rows: &[(TrackRow, FileStamp, Option<String>)],
+    ) -> Result<Vec<TrackId>, String> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| format!("Cache write failed: {e}"))?;
+
+        let mut ids = Vec::with_capacity(rows.len());
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT INTO tracks (path, mtime_ns, size, tag_failed, tag_error, row_json)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                     ON CONFLICT(path) DO UPDATE SET
+                        mtime_ns = excluded.mtime_ns,
+                        size = excluded.size,
+                        tag_failed = excluded.tag_failed,
+                        tag_error = excluded.tag_error,
+                        row_json = excluded.row_json
+                     RETURNING id",
+                )
+                .map_err(|e| format!("Cache write failed: {e}"))?;
+
+            for (row, stamp, tag_error) in rows {
+                let json =
+                    serde_json::to_string(row).map_err(|e| format!("Serialize failed: {e}"))?;
+                let id: i64 = stmt
+                    .query_row(
+                        params![
+                            path_to_blob(&cache_key(&row.path)),
+                            stamp.mtime_ns,
+                            stamp.size,
+                            tag_error.is_some(),
+                            tag_error,
+                            json
+                        ],
+                        |r| r.get(0),
+                    )
+                    .map_err(|e| format!("Cache write failed for {}: {e}", row.path.display()))?;
+                ids.push(id);
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Cache write failed: {e}"))?;
+        Ok(ids)
+    }
+
+    /// Re-key a cached row after its file moved from `old_path` to `row.path` (id is kept).
+    pub fn rename_path(&mut self, old_path: &Path, row: &TrackRow) -> Result<(), String> {
+        let json = serde_json::to_string(row).map_err(|e| format!("Serialize failed: {e}"))?;
+        self.conn
+            .execute(
+                "UPDATE tracks SET path = ?1, row_json = ?2 WHERE path = ?3",
+                params![
+                    path_to_blob(&cache_key(&row.path)),
+                    json,
+                    path_to_blob(&cache_key(old_path))
+                ],
+            )
+            .map_err(|e| format!("Cache update failed for {}: {e}", row.path.display()))?;
+        Ok(())
+    }
+
+    /// Forget rows by id (files trashed or removed by the user). Returns the number removed.
+    pub fn delete_ids(&mut self, ids: &[TrackId]) -> Result<usize, String> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| format!("Cache delete failed: {e}"))?;
+
+        let mut removed = 0;
+        for id in ids {
+            removed += tx
+                .execute("DELETE FROM tracks WHERE id = ?1", params![id])
+                .map_err(|e| format!("Cache delete failed: {e}"))?;
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Cache delete failed: {e}"))?;
+        Ok(removed)
+    }
+
+    /// Delete rows whose path is not under any of `roots`. Returns the number removed.
+    pub fn prune_outside_roots(&mut self, roots: &[PathBuf]) -> Result<usize, String> {
+        let roots: Vec<PathBuf> = roots.iter().map(|r| cache_key(r)).collect();
+        let paths: Vec<(i64, Vec<u8>)> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT id, path FROM tracks")
+                .map_err(|e| format!("Cache query failed: {e}"))?;
+            stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+                .map_err(|e| format!("Cache query failed: {e}"))?
+                .filter_map(Result::ok)
+                .collect()
+        };
+
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| format!("Cache prune failed: {e}"))?;
+
+        let mut removed = 0;
+        for (id, path) in paths {
+            let path = path_from_blob(path);
+            if roots.iter().any(|root| path.starts_with(root)) {
+                continue;
+            }
+            removed += tx
+                .execute("DELETE FROM tracks WHERE id = ?1", params![id])
+                .map_err(|e| format!("Cache prune failed: {e}"))?;
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Cache prune failed: {e}"))?;
+        Ok(removed)
+    }
+}
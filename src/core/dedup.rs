@@ -0,0 +1,73 @@
+//! core/dedup.rs
+//! Duplicate detection.
+//!
+//! Two tracks are duplicates when:
+//! - their normalized (title, artist, album) match (lowercase, trimmed, whitespace collapsed)
+//! - and their durations are within `DURATION_TOLERANCE_MS` (chained: A~B and B~C groups A, B, C)
+//!
+//! Untitled tracks are never grouped (they would all "match" each other).
+//! Tracks without a duration only group with other tracks without one.
+
+use std::collections::HashMap;
+
+use super::types::TrackRow;
+
+const DURATION_TOLERANCE_MS: u32 = 2000;
+
+/// Groups (indices into `tracks`) of likely duplicates; every group has 2+ entries.
+///
+/// Groups are ordered by their first index; indices inside a group are ascending.
+pub fn find_duplicates(tracks: &[TrackRow]) -> Vec<Vec<usize>> {
+    let mut by_key: HashMap<(String, String, String), Vec<usize>> = HashMap::new();
+
+    for (i, t) in tracks.iter().enumerate() {
+        let title = normalize(t.title.as_deref());
+        if title.is_empty() {
+            continue;
+        }
+        let key = (
+            title,
+            normalize(t.artist.as_deref()),
+            normalize(t.album.as_deref()),
+        );
+        by_key.entry(key).or_default().push(i);
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+
+    for idxs in by_key.into_values().filter(|v| v.len() > 1) {
+        let (mut timed, untimed): (Vec<usize>, Vec<usize>) = idxs
+            .into_iter()
+            .partition(|&i| tracks[i].duration_ms.is_some());
+
+        // Sort by duration, then split wherever the gap to the previous track is too big.
+        timed.sort_by_key(|&i| tracks[i].duration_ms);
+        let mut current: Vec<usize> = Vec::new();
+        let mut prev_ms: Option<u32> = None;
+        for i in timed {
+            let ms = tracks[i].duration_ms.unwrap_or(0);
+            if prev_ms.is_some_and(|p| ms - p > DURATION_TOLERANCE_MS) {
+                groups.push(std::mem::take(&mut current));
+            }
+            current.push(i);
+            prev_ms = Some(ms);
+        }
+        groups.push(current);
+        groups.push(untimed);
+    }
+
+    groups.retain(|g| g.len() > 1);
+    for g in groups.iter_mut() {
+        g.sort_unstable();
+    }
+    groups.sort_by_key(|g| g[0]);
+    groups
+}
+
+fn normalize(s: Option<&str>) -> String {
+    s.unwrap_or("")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
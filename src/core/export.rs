@@ -0,0 +1,104 @@
+//! core/export.rs
+//! Export library metadata to external formats.
+//!
+//! - CSV: one row per track, fixed column set, `None` => empty cell.
+//! - JSON (`sonora --scan`): array of flat objects, `None` => `null`.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::types::TrackRow;
+
+/// CSV header, in column order.
+const CSV_HEADER: [&str; 17] = [
+    "path",
+    "title",
+    "artist",
+    "album",
+    "album_artist",
+    "composer",
+    "track_no",
+    "track_total",
+    "disc_no",
+    "disc_total",
+    "year",
+    "genre",
+    "bpm",
+    "duration_ms",
+    "rating",
+    "play_count",
+    "isrc",
+];
+
+/// Write `rows` as CSV (header first) to `path`, replacing any existing file.
+pub fn write_tracks_csv(rows: &[TrackRow], path: &Path) -> Result<(), String> {
+    let mut w = csv::Writer::from_path(path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    w.write_record(CSV_HEADER)
+        .map_err(|e| format!("CSV write failed: {e}"))?;
+
+    fn opt<T: ToString>(v: &Option<T>) -> String {
+        v.as_ref().map(ToString::to_string).unwrap_or_default()
+    }
+
+    for t in rows {
+        let record: [String; 17] = [
+            t.path.display().to_string(),
+            opt(&t.title),
+            opt(&t.artist),
+            opt(&t.album),
+            opt(&t.album_artist),
+            opt(&t.composer),
+            opt(&t.track_no),
+            opt(&t.track_total),
+            opt(&t.disc_no),
+            opt(&t.disc_total),
+            opt(&t.year),
+            opt(&t.genre),
+            opt(&t.bpm),
+            opt(&t.duration_ms),
+            opt(&t.rating),
+            opt(&t.play_count),
+            opt(&t.isrc),
+        ];
+        w.write_record(&record)
+            .map_err(|e| format!("CSV write failed: {e}"))?;
+    }
+
+    w.flush().map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// The scriptable subset of a `TrackRow` (what `--scan` prints).
+#[derive(Serialize)]
+pub struct TrackRowJson<'a> {
+    pub path: String,
+    pub title: Option<&'a str>,
+    pub artist: Option<&'a str>,
+    pub album: Option<&'a str>,
+    pub track_no: Option<u32>,
+    pub year: Option<i32>,
+    pub genre: Option<&'a str>,
+    pub duration_ms: Option<u32>,
+}
+
+impl<'a> From<&'a TrackRow> for TrackRowJson<'a> {
+    fn from(t: &'a TrackRow) -> Self {
+        TrackRowJson {
+            path: t.path.display().to_string(),
+            title: t.title.as_deref(),
+            artist: t.artist.as_deref(),
+            album: t.album.as_deref(),
+            track_no: t.track_no,
+            year: t.year,
+            genre: t.genre.as_deref(),
+            duration_ms: t.duration_ms,
+        }
+    }
+}
+
+/// Serialize `rows` as a pretty-printed JSON array of `TrackRowJson`.
+pub fn tracks_json(rows: &[TrackRow]) -> Result<String, String> {
+    let flat: Vec<TrackRowJson<'_>> = rows.iter().map(TrackRowJson::from).collect();
+    serde_json::to_string_pretty(&flat).map_err(|e| format!("Serialize failed: {e}"))
+}
@@ -0,0 +1,368 @@
+//! core/fileops.rs
+//! Move/rename audio files based on their tags.
+//!
+//! - `rename_from_template`: new name, same folder.
+//! - `rename_batch`: all-or-nothing renames (rolls back on the first failure).
+//! - `organize_files`: move/copy into `dest_root/<template>` (template may contain `/`).
+//!
+//! Templates:
+//! - `{field}` expands to a `TrackRow` value, e.g. `{artist} - {title}`.
+//! - Numeric fields accept a zero-pad width: `{track_no:02}` => `07`.
+//! - Values are sanitized for file names (`/`, `:`, `?` ... become `_`).
+//! - A referenced field that is empty is an error (no "Unknown" placeholders on disk).
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::types::TrackRow;
+
+/// Characters that are invalid (or awkward) in file names on at least one platform.
+const UNSAFE_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Rename `row.path` (same folder, same extension) to the expanded `template`.
+///
+/// - Returns the destination path; equal to `row.path` when the name already matches.
+/// - Refuses to overwrite an existing file.
+/// - With `dry_run`, performs the same checks but leaves the disk untouched.
+pub fn rename_from_template(
+    row: &TrackRow,
+    template: &str,
+    dry_run: bool,
+) -> Result<PathBuf, String> {
+    let stem = expand_template(row, template)?;
+    if stem.contains(['/', '\\']) {
+        return Err("Rename templates name a file, not a folder".to_string());
+    }
+
+    let mut name = stem;
+    if let Some(ext) = row.path.extension().and_then(|e| e.to_str()) {
+        name.push('.');
+        name.push_str(ext);
+    }
+
+    let dest = match row.path.parent() {
+        Some(dir) => dir.join(&name),
+        None => PathBuf::from(&name),
+    };
+
+    if dest == row.path {
+        return Ok(dest);
+    }
+
+    if dest.exists() {
+        return Err(format!("{} already exists", dest.display()));
+    }
+
+    if !dry_run {
+        std::fs::rename(&row.path, &dest)
+            .map_err(|e| format!("Rename {} failed: {e}", row.path.display()))?;
+    }
+
+    Ok(dest)
+}
+
+/// `(from, to)` path pairs, in the order they run.
+pub type RenameOps = Vec<(PathBuf, PathBuf)>;
+
+/// Rename every `(from, to)` pair, or none of them.
+///
+/// - Validates first: sources exist, destinations are free, unique, and in writable folders.
+/// - Renames run in order; on a failure the completed ones are reversed (newest first).
+/// - `Err` carries the renames completed before the failure; rollback problems are appended
+///   to the message, so callers should check the disk before trusting those paths.
+/// - Plain `rename` only: both paths must be on the same filesystem.
+pub fn rename_batch(operations: RenameOps) -> Result<RenameOps, (RenameOps, String)> {
+    validate_renames(&operations).map_err(|e| (Vec::new(), e))?;
+
+    let mut completed: RenameOps = Vec::with_capacity(operations.len());
+    for (from, to) in operations {
+        if let Err(e) = std::fs::rename(&from, &to) {
+            let mut msg = format!("Rename {} failed: {e}", from.display());
+            for (done_from, done_to) in completed.iter().rev() {
+                if let Err(e) = std::fs::rename(done_to, done_from) {
+                    msg.push_str(&format!("; couldn't restore {}: {e}", done_from.display()));
+                }
+            }
+            return Err((completed, msg));
+        }
+        completed.push((from, to));
+    }
+
+    Ok(completed)
+}
+
+/// Pre-flight checks for `rename_batch` (nothing on disk changes here).
+fn validate_renames(operations: &[(PathBuf, PathBuf)]) -> Result<(), String> {
+    let mut targets: HashSet<&Path> = HashSet::new();
+
+    for (from, to) in operations {
+        if !from.is_file() {
+            return Err(format!("{} no longer exists", from.display()));
+        }
+        if to.exists() {
+            return Err(format!("{} already exists", to.display()));
+        }
+        if !targets.insert(to) {
+            return Err(format!("{} is the target of two renames", to.display()));
+        }
+
+        let dir = match to.parent() {
+            Some(d) if !d.as_os_str().is_empty() => d,
+            _ => Path::new("."),
+        };
+        let meta = std::fs::metadata(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        if !meta.is_dir() {
+            return Err(format!("{} is not a folder", dir.display()));
+        }
+        if meta.permissions().readonly() {
+            return Err(format!("{} is read-only", dir.display()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Outcome of `organize_files` (per-file problems are collected, not fatal).
+#[derive(Debug, Clone, Default)]
+pub struct OrgReport {
+    /// (from, to) for every file moved or copied.
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    /// Files already at their destination.
+    pub unchanged: usize,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Move (or copy) each row's file to `dest_root/<expanded template>.<ext>`.
+///
+/// - `/` in the template creates folders (created on demand); `/` inside values does not.
+/// - Destinations that already exist, or that two rows share, get a " (2)", " (3)"... suffix.
+/// - Moves fall back to copy + delete across filesystems.
+/// - `Err` only if `dest_root` itself is unusable.
+pub fn organize_files(
+    rows: &[TrackRow],
+    dest_root: &Path,
+    template: &str,
+    copy: bool,
+) -> Result<OrgReport, String> {
+    std::fs::create_dir_all(dest_root).map_err(|e| format!("{}: {e}", dest_root.display()))?;
+
+    let mut report = OrgReport::default();
+    let mut taken: HashSet<PathBuf> = HashSet::new();
+
+    for row in rows {
+        let rel = match expand_template(row, template) {
+            Ok(rel) => rel,
+            Err(e) => {
+                report.failed.push((row.path.clone(), e));
+                continue;
+            }
+        };
+
+        // Drop empty / dot segments so a template can't climb out of `dest_root`.
+        let rel: PathBuf = rel
+            .split(['/', '\\'])
+            .map(str::trim)
+            .filter(|seg| !seg.is_empty() && *seg != "." && *seg != "..")
+            .collect();
+        if rel.as_os_str().is_empty() {
+            report.failed.push((
+                row.path.clone(),
+                "Template expanded to an empty path".into(),
+            ));
+            continue;
+        }
+
+        let ext = row.path.extension().and_then(|e| e.to_str());
+        let wanted = with_extension(&dest_root.join(rel), ext);
+
+        if wanted == row.path {
+            taken.insert(wanted);
+            report.unchanged += 1;
+            continue;
+        }
+
+        let dest = unique_destination(&wanted, ext, &taken);
+        taken.insert(dest.clone());
+
+        match transfer(&row.path, &dest, copy) {
+            Ok(()) => report.moved.push((row.path.clone(), dest)),
+            Err(e) => report.failed.push((row.path.clone(), e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Staging area for "deleted" files: `<data dir>/sonora/trash/`.
+pub fn trash_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("sonora").join("trash"))
+}
+
+/// Move `path` into `trash_dir()` (never a permanent delete). Returns where it went.
+///
+/// Name clashes inside the trash get a " (2)", " (3)"... suffix.
+pub fn move_to_trash(path: &Path) -> Result<PathBuf, String> {
+    let dir = trash_dir().ok_or_else(|| "No data directory on this platform".to_string())?;
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("{} has no file name", path.display()))?;
+
+    let ext = path.extension().and_then(|e| e.to_str());
+    let dest = unique_destination(&dir.join(name), ext, &HashSet::new());
+    transfer(path, &dest, false)?;
+    Ok(dest)
+}
+
+/// `base` plus `.ext` (appended, so dots inside titles survive).
+fn with_extension(base: &Path, ext: Option<&str>) -> PathBuf {
+    let mut s = base.as_os_str().to_owned();
+    if let Some(ext) = ext {
+        s.push(".");
+        s.push(ext);
+    }
+    PathBuf::from(s)
+}
+
+/// First of `wanted`, `stem (2).ext`, `stem (3).ext`... not on disk and not already planned.
+fn unique_destination(wanted: &Path, ext: Option<&str>, taken: &HashSet<PathBuf>) -> PathBuf {
+    let free = |p: &Path| !p.exists() && !taken.contains(p);
+    if free(wanted) {
+        return wanted.to_path_buf();
+    }
+
+    let base = match ext {
+        Some(ext) => {
+            let s = wanted.to_string_lossy();
+            PathBuf::from(s.strip_suffix(&format!(".{ext}")).unwrap_or(&s).to_string())
+        }
+        None => wanted.to_path_buf(),
+    };
+
+    (2..)
+        .map(|n| {
+            let mut s = base.as_os_str().to_owned();
+            s.push(format!(" ({n})"));
+            with_extension(Path::new(&s), ext)
+        })
+        .find(|p| free(p))
+        .expect("unbounded suffix search")
+}
+
+fn transfer(from: &Path, to: &Path, copy: bool) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+
+    if copy {
+        std::fs::copy(from, to).map_err(|e| format!("Copy to {} failed: {e}", to.display()))?;
+        return Ok(());
+    }
+
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    // Different filesystem (or similar): copy, then remove the original.
+    std::fs::copy(from, to).map_err(|e| format!("Move to {} failed: {e}", to.display()))?;
+    std::fs::remove_file(from).map_err(|e| {
+        let _ = std::fs::remove_file(to);
+        format!("Move from {} failed: {e}", from.display())
+    })
+}
+
+/// Expand `{field}` / `{field:0N}` placeholders using `row`.
+pub(crate) fn expand_template(row: &TrackRow, template: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len() + 32);
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let close = after
+            .find('}')
+            .ok_or_else(|| format!("Unclosed '{{' in template: {template}"))?;
+
+        let spec = &after[..close];
+        let (key, width) = match spec.split_once(':') {
+            Some((key, fmt)) => (key.trim(), Some(parse_pad(fmt)?)),
+            None => (spec.trim(), None),
+        };
+
+        let value = field_value(row, key, width)?
+            .ok_or_else(|| format!("No {{{key}}} for {}", row.path.display()))?;
+        out.push_str(&sanitize_component(&value));
+
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+
+    let out = out.trim().to_string();
+    if out.is_empty() {
+        return Err("Template expanded to an empty name".to_string());
+    }
+    Ok(out)
+}
+
+/// `02` => width 2 (only zero-padding is supported).
+fn parse_pad(fmt: &str) -> Result<usize, String> {
+    let fmt = fmt.trim();
+    match fmt.strip_prefix('0').map(str::parse::<usize>) {
+        Some(Ok(w)) => Ok(w),
+        _ => Err(format!("Unsupported format ':{fmt}' (use e.g. :02)")),
+    }
+}
+
+/// Look up a template variable. `Ok(None)` = known field, but empty for this row.
+fn field_value(row: &TrackRow, key: &str, width: Option<usize>) -> Result<Option<String>, String> {
+    fn text(v: &Option<String>) -> Option<String> {
+        v.as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    }
+
+    let number: Option<i64> = match key {
+        "track_no" => row.track_no.map(i64::from),
+        "track_total" => row.track_total.map(i64::from),
+        "disc_no" => row.disc_no.map(i64::from),
+        "disc_total" => row.disc_total.map(i64::from),
+        "year" => row.year.map(i64::from),
+        "bpm" => row.bpm.map(i64::from),
+        _ => {
+            if width.is_some() {
+                return Err(format!("{{{key}}} is not numeric; it can't be padded"));
+            }
+            return match key {
+                "title" => Ok(text(&row.title)),
+                "artist" => Ok(text(&row.artist)),
+                "album" => Ok(text(&row.album)),
+                "album_artist" => Ok(text(&row.album_artist)),
+                "composer" => Ok(text(&row.composer)),
+                "genre" => Ok(text(&row.genre)),
+                _ => Err(format!("Unknown template variable {{{key}}}")),
+            };
+        }
+    };
+
+    Ok(number.map(|n| match width {
+        Some(w) => format!("{n:0w$}"),
+        None => n.to_string(),
+    }))
+}
+
+/// Make a tag value safe to use as (part of) a single path component.
+pub fn sanitize_component(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| {
+            if UNSAFE_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    // Trailing dots/spaces are stripped by Windows; keep names round-trippable.
+    cleaned.trim_end_matches(['.', ' ']).to_string()
+}
@@ -0,0 +1,73 @@
+//! core/history.rs
+//!
+//! Play history persisted to disk as JSON, next to `config.toml` (`history.json`).
+//!
+//! - Entries are (track id, when it finished playing), oldest first
+//! - Ids are library cache ids, so they stay valid across rescans
+//! - Missing or unparseable file => empty history (like `Config::load`)
+//! - Times are stored as RFC 3339 strings, the same format as `date_added`
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::config::Config;
+use super::types::TrackId;
+
+const FILE_NAME: &str = "history.json";
+
+#[derive(Serialize, Deserialize)]
+struct HistoryEntry {
+    track_id: TrackId,
+    played_at: String,
+}
+
+/// Full path of the history file, if the platform has a config dir.
+pub fn path() -> Option<PathBuf> {
+    Config::path().map(|p| p.with_file_name(FILE_NAME))
+}
+
+/// Load the saved history; entries with an unreadable timestamp are dropped.
+pub fn load() -> Vec<(TrackId, DateTime<Local>)> {
+    let Some(path) = path() else {
+        return Vec::new();
+    };
+    let Ok(s) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    let entries: Vec<HistoryEntry> = serde_json::from_str(&s).unwrap_or_else(|_e| {
+        #[cfg(debug_assertions)]
+        eprintln!("[HISTORY] parse failed for {}: {_e}", path.display());
+        Vec::new()
+    });
+
+    entries
+        .into_iter()
+        .filter_map(|e| {
+            let at = DateTime::parse_from_rfc3339(&e.played_at).ok()?;
+            Some((e.track_id, at.with_timezone(&Local)))
+        })
+        .collect()
+}
+
+/// Write the history to disk (creating the parent directory if needed).
+pub fn save(history: &[(TrackId, DateTime<Local>)]) -> Result<(), String> {
+    let path = path().ok_or_else(|| "No config directory on this platform".to_string())?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+
+    let entries: Vec<HistoryEntry> = history
+        .iter()
+        .map(|(id, at)| HistoryEntry {
+            track_id: *id,
+            played_at: at.to_rfc3339(),
+        })
+        .collect();
+
+    let s = serde_json::to_string(&entries).map_err(|e| format!("Serialize failed: {e}"))?;
+    std::fs::write(&path, s).map_err(|e| format!("{}: {e}", path.display()))
+}
@@ -11,19 +11,68 @@
 
 use std::path::{Path, PathBuf};
 
-/// Recursively scan a directory tree and return all `.mp3` file paths.
+/// Compiled scan exclusion patterns (e.g. `*/podcasts/*`, `audiobooks`).
+///
+/// - Patterns using glob syntax (`*`, `?`, `**`, `[..]`) match the whole path
+/// - Anything else (or a glob that doesn't parse) is a plain substring match
+/// - Paths are matched with `/` separators on every platform
+pub struct Exclusions {
+    globs: Vec<glob::Pattern>,
+    substrings: Vec<String>,
+}
+
+impl Exclusions {
+    pub fn new(patterns: &[String]) -> Exclusions {
+        let mut globs = Vec::new();
+        let mut substrings = Vec::new();
+
+        for p in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let is_glob = p.contains(['*', '?', '[']);
+            match glob::Pattern::new(p) {
+                Ok(pattern) if is_glob => globs.push(pattern),
+                _ => substrings.push(p.to_string()),
+            }
+        }
+
+        Exclusions { globs, substrings }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.globs.is_empty() && self.substrings.is_empty()
+    }
+
+    /// True if `path` matches any pattern.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let s = path.to_string_lossy().replace('\\', "/");
+        self.globs.iter().any(|g| g.matches(&s))
+            || self.substrings.iter().any(|sub| s.contains(sub.as_str()))
+    }
+
+    /// Folder check: `*/podcasts/*` should prune `/music/podcasts` itself.
+    fn is_excluded_dir(&self, dir: &Path) -> bool {
+        self.is_excluded(&dir.join(""))
+    }
+}
+
+/// Recursively scan a directory tree and return all supported audio file paths (`.mp3`, `.ogg`, `.flac`).
 ///
 /// Behavior:
 /// - Root must be a directory (else Err).
+/// - Paths (and whole folders) matching `exclusions` are skipped.
 /// - Non-fatal walk errors are skipped (PermissionDenied, NotFound).
 /// - Symlinked directories are NOT traversed (prevents cycles).
 /// - Symlinked files ARE allowed if they ultimately resolve to a file.
 /// - Output is sorted by full path.
-pub fn scan_mp3s(root: &Path) -> Result<Vec<PathBuf>, String> {
+pub fn scan_audio_files(root: &Path, exclusions: &[String]) -> Result<Vec<PathBuf>, String> {
     if !root.is_dir() {
         return Err(format!("Not a directory: {}", root.display()));
     }
 
+    let exclusions = Exclusions::new(exclusions);
+
     let mut out: Vec<PathBuf> = Vec::new();
     let mut stack: Vec<PathBuf> = vec![root.to_path_buf()];
 
@@ -63,7 +112,13 @@ pub fn scan_mp3s(root: &Path) -> Result<Vec<PathBuf>, String> {
             };
 
             if ft.is_dir() {
-                stack.push(path);
+                if !exclusions.is_excluded_dir(&path) {
+                    stack.push(path);
+                }
+                continue;
+            }
+
+            if exclusions.is_excluded(&path) {
                 continue;
             }
 
@@ -72,7 +127,7 @@ pub fn scan_mp3s(root: &Path) -> Result<Vec<PathBuf>, String> {
             if ft.is_symlink() {
                 match std::fs::metadata(&path) {
                     Ok(md) => {
-                        if md.is_file() && is_mp3(&path) {
+                        if md.is_file() && is_audio_file(&path) {
                             out.push(path);
                         }
                     }
@@ -86,7 +141,7 @@ pub fn scan_mp3s(root: &Path) -> Result<Vec<PathBuf>, String> {
                 continue;
             }
 
-            if ft.is_file() && is_mp3(&path) {
+            if ft.is_file() && is_audio_file(&path) {
                 out.push(path);
             }
         }
@@ -105,9 +160,28 @@ fn is_nonfatal_walk_error(e: &std::io::Error) -> bool {
 }
 
 /// True if the file extension is `.mp3` (case-insensitive).
-fn is_mp3(path: &Path) -> bool {
+pub fn is_mp3(path: &Path) -> bool {
+    has_extension(path, "mp3")
+}
+
+/// True if the file extension is `.ogg` (case-insensitive).
+pub fn is_ogg(path: &Path) -> bool {
+    has_extension(path, "ogg")
+}
+
+/// True if the file extension is `.flac` (case-insensitive).
+pub fn is_flac(path: &Path) -> bool {
+    has_extension(path, "flac")
+}
+
+/// True for every extension scans pick up.
+pub fn is_audio_file(path: &Path) -> bool {
+    is_mp3(path) || is_ogg(path) || is_flac(path)
+}
+
+fn has_extension(path: &Path, wanted: &str) -> bool {
     path.extension()
         .and_then(|s| s.to_str())
-        .map(|ext| ext.eq_ignore_ascii_case("mp3"))
+        .map(|ext| ext.eq_ignore_ascii_case(wanted))
         .unwrap_or(false)
 }
@@ -0,0 +1,163 @@
+//! core/lint.rs
+//! Library health checks (metadata completeness).
+//!
+//! - A field counts as missing when it is `None` or blank text.
+//! - `Cover` means "no embedded artwork" (`artwork_count == 0`).
+//! - Album completeness checks each disc of an album on its own (track numbers restart).
+//! - Missing files are tracks whose recorded path no longer exists (moved/deleted since the scan).
+
+use std::collections::BTreeMap;
+
+use super::types::{AlbumKey, TrackId, TrackRow};
+
+/// Fields a "complete" track is expected to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequiredField {
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    TrackNo,
+    Year,
+    Genre,
+    Cover,
+}
+
+impl RequiredField {
+    pub const ALL: [RequiredField; 8] = [
+        RequiredField::Title,
+        RequiredField::Artist,
+        RequiredField::Album,
+        RequiredField::AlbumArtist,
+        RequiredField::TrackNo,
+        RequiredField::Year,
+        RequiredField::Genre,
+        RequiredField::Cover,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RequiredField::Title => "Title",
+            RequiredField::Artist => "Artist",
+            RequiredField::Album => "Album",
+            RequiredField::AlbumArtist => "Album Artist",
+            RequiredField::TrackNo => "Track #",
+            RequiredField::Year => "Year",
+            RequiredField::Genre => "Genre",
+            RequiredField::Cover => "Cover art",
+        }
+    }
+
+    fn is_missing(self, t: &TrackRow) -> bool {
+        fn blank(v: &Option<String>) -> bool {
+            v.as_deref().is_none_or(|s| s.trim().is_empty())
+        }
+
+        match self {
+            RequiredField::Title => blank(&t.title),
+            RequiredField::Artist => blank(&t.artist),
+            RequiredField::Album => blank(&t.album),
+            RequiredField::AlbumArtist => blank(&t.album_artist),
+            RequiredField::TrackNo => t.track_no.is_none(),
+            RequiredField::Year => t.year.is_none(),
+            RequiredField::Genre => blank(&t.genre),
+            RequiredField::Cover => t.artwork_count == 0,
+        }
+    }
+}
+
+/// (index into `tracks`, missing fields in `required` order) for every incomplete track.
+pub fn find_missing_tags(
+    tracks: &[TrackRow],
+    required: &[RequiredField],
+) -> Vec<(usize, Vec<RequiredField>)> {
+    tracks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| {
+            let missing: Vec<RequiredField> = required
+                .iter()
+                .copied()
+                .filter(|f| f.is_missing(t))
+                .collect();
+            (!missing.is_empty()).then_some((i, missing))
+        })
+        .collect()
+}
+
+/// Ids of tracks whose file is gone from its recorded path (one `exists()` stat per track).
+pub fn check_missing_files(tracks: &[TrackRow]) -> Vec<TrackId> {
+    tracks
+        .iter()
+        .filter(|t| !t.path.exists())
+        .filter_map(|t| t.id)
+        .collect()
+}
+
+/// Track numbering problems within one album (or one disc of a multi-disc album).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlbumLint {
+    pub key: AlbumKey,
+    /// Set when the album spans several discs; `gaps`/`duplicate_nos` are for that disc.
+    pub disc_no: Option<u32>,
+    /// Missing numbers in `1..=track_total` (or `1..=max track #` without a total).
+    pub gaps: Vec<u32>,
+    /// Track numbers used by more than one file.
+    pub duplicate_nos: Vec<u32>,
+}
+
+/// Albums whose track numbers skip or repeat, sorted by album key then disc.
+///
+/// Tracks without a track number are ignored here (`find_missing_tags` reports them).
+pub fn find_incomplete_albums(tracks: &[TrackRow]) -> Vec<AlbumLint> {
+    /// Track numbers seen on one disc, plus the largest track total tagged there.
+    #[derive(Default)]
+    struct Disc {
+        nos: Vec<u32>,
+        total: Option<u32>,
+    }
+
+    let mut discs: BTreeMap<(AlbumKey, Option<u32>), Disc> = BTreeMap::new();
+    for t in tracks {
+        let Some(no) = t.track_no else { continue };
+        let disc = discs
+            .entry((AlbumKey::for_track(t), t.disc_no))
+            .or_default();
+        disc.nos.push(no);
+        disc.total = disc.total.max(t.track_total);
+    }
+
+    for disc in discs.values_mut() {
+        disc.nos.sort_unstable();
+    }
+
+    let mut disc_counts: BTreeMap<&AlbumKey, usize> = BTreeMap::new();
+    for (key, _) in discs.keys() {
+        *disc_counts.entry(key).or_default() += 1;
+    }
+
+    let mut out = Vec::new();
+    for ((key, disc_no), Disc { nos, total }) in &discs {
+        let mut duplicate_nos: Vec<u32> = nos
+            .windows(2)
+            .filter(|w| w[0] == w[1])
+            .map(|w| w[0])
+            .collect();
+        duplicate_nos.dedup();
+
+        let last = total.unwrap_or(0).max(nos.last().copied().unwrap_or(0));
+        let gaps: Vec<u32> = (1..=last)
+            .filter(|n| nos.binary_search(n).is_err())
+            .collect();
+
+        if !gaps.is_empty() || !duplicate_nos.is_empty() {
+            out.push(AlbumLint {
+                key: key.clone(),
+                disc_no: disc_no.filter(|_| disc_counts[key] > 1),
+                gaps,
+                duplicate_nos,
+            });
+        }
+    }
+    out
+}
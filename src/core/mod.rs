@@ -7,33 +7,58 @@
 //!
 //! - Make the scan pipeline explicit and modular:
 //!   (A) discover paths -> Vec<PathBuf>
-//!   (B) read tags -> Vec<TrackRow>
+//!   (B) read tags -> (Vec<TrackRow>, Vec<ScanError>)
 //!
-//! This keeps the GUI dumb, and makes the later SQLite pivot easy:
-//! - "scan" becomes "discover paths -> upsert/load from DB"
-//! - but (A) and (B) remain stable APIs.
+//! This keeps the GUI dumb, and keeps the SQLite cache a thin layer:
+//! - "scan" is "discover paths -> load unchanged rows from DB, read + upsert the rest"
+//! - (A) and (B) remain stable APIs; `read_tracks_cached` wraps (B).
 
+pub mod acoustid;
+pub mod config;
+pub mod db;
+pub mod dedup;
+pub mod export;
+pub mod fileops;
+pub mod history;
 pub mod library;
+pub mod lint;
+pub mod musicbrainz;
 pub mod playback;
+pub mod playlist;
+pub mod scrobbler;
+pub mod stats;
 pub mod tags;
 pub mod types;
+pub mod undo_log;
+pub mod watcher;
+pub mod waveform;
 
 use std::collections::HashSet;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
 
-use types::TrackRow;
+use db::FileStamp;
+use types::{ScanError, TrackRow};
+
+/// Incremental progress for scan stage (B), sent once per file read.
+#[derive(Debug, Clone, Copy)]
+pub struct ScanProgress {
+    pub scanned: usize,
+    pub total: usize,
+}
 
 /// Discover candidate audio files under multiple roots.
 ///
-/// - MP3-only for MVP (library enforces extension rules)
+/// - `.mp3`, `.ogg` and `.flac` only (library enforces extension rules)
 /// - De-dupes across overlapping roots by full path
 /// - Sorts paths once (core owns ordering, GUI shouldn't)
-pub fn scan_paths(roots: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+/// - Skips paths matching any of `exclusions` (glob or substring, see `library::Exclusions`)
+pub fn scan_paths(roots: &[PathBuf], exclusions: &[String]) -> Result<Vec<PathBuf>, String> {
     let mut seen: HashSet<PathBuf> = HashSet::with_capacity(1024);
     let mut out: Vec<PathBuf> = Vec::new();
 
     for root in roots {
-        let paths = library::scan_mp3s(root)?;
+        let paths = library::scan_audio_files(root, exclusions)?;
         for path in paths {
             if seen.insert(path.clone()) {
                 out.push(path);
@@ -48,34 +73,177 @@ pub fn scan_paths(roots: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
 /// Read tags for a set of already-discovered audio paths.
 ///
 /// - Never fails hard per-file: unreadable tags return an "empty-ish" TrackRow
-/// - Returns (rows, tag_failures)
-pub fn read_tracks(paths: Vec<PathBuf>) -> (Vec<TrackRow>, usize) {
-    let mut rows: Vec<TrackRow> = Vec::with_capacity(paths.len());
-    let mut tag_failures: usize = 0;
+/// - If `progress` is given, sends a `ScanProgress` after each file (best-effort)
+/// - Output order matches `paths` (parallel or not)
+/// - Returns (rows, one `ScanError` per file whose tags could not be read)
+pub fn read_tracks(
+    paths: Vec<PathBuf>,
+    progress: Option<&Sender<ScanProgress>>,
+) -> (Vec<TrackRow>, Vec<ScanError>) {
+    let total = paths.len();
+    let results = read_rows(paths, progress, 0, total);
+
+    split_errors(results)
+}
+
+/// Like `read_tracks`, but consults the SQLite library cache first.
+///
+/// - Files whose (mtime, size) match the cache reuse the cached row (no tag IO)
+/// - New/changed files are read from disk and upserted
+/// - Every cached row comes back with `id = Some(db id)` (stable across rescans)
+pub fn read_tracks_cached(
+    paths: Vec<PathBuf>,
+    db: &mut db::LibraryDb,
+    progress: Option<&Sender<ScanProgress>>,
+) -> Result<(Vec<TrackRow>, Vec<ScanError>), String> {
+    let total = paths.len();
+    let mut cache = db.load_all()?;
+
+    // Slot per input path (keeps output order == input order).
+    let mut slots: Vec<Option<(TrackRow, Option<String>)>> = Vec::with_capacity(total);
+    let mut miss_idx: Vec<usize> = Vec::new();
+    let mut miss_paths: Vec<PathBuf> = Vec::new();
+    let mut miss_stamps: Vec<Option<FileStamp>> = Vec::new();
+    let mut miss_dates: Vec<Option<String>> = Vec::new();
+
+    for (i, path) in paths.into_iter().enumerate() {
+        let stamp = FileStamp::of(&path);
+        match (stamp, cache.remove(&db::cache_key(&path))) {
+            (Some(s), Some(mut c)) if c.mtime_ns == s.mtime_ns && c.size == s.size => {
+                // Rows keep the path the scan found, not the canonical key.
+                c.row.path = path;
+                slots.push(Some((c.row, c.tag_error)));
+            }
+            (_, cached) => {
+                slots.push(None);
+                miss_idx.push(i);
+                miss_paths.push(path);
+                miss_stamps.push(stamp);
+                miss_dates.push(cached.and_then(|c| c.row.date_added));
+            }
+        }
+    }
+
+    let hits = total - miss_paths.len();
+    if let Some(tx) = progress {
+        let _ = tx.send(ScanProgress {
+            scanned: hits,
+            total,
+        });
+    }
+
+    let mut fresh = read_rows(miss_paths, progress, hits, total);
+
+    // A date added only the cache knows (the file couldn't be stamped) survives a re-read.
+    for ((row, _error), date) in fresh.iter_mut().zip(miss_dates) {
+        if row.date_added.is_none() {
+            row.date_added = date;
+        }
+    }
+
+    // Upsert fresh rows we can fingerprint; unstamped files stay uncached (id None).
+    let mut store: Vec<(TrackRow, FileStamp, Option<String>)> = Vec::new();
+    let mut store_idx: Vec<usize> = Vec::new();
+    for ((i, stamp), (row, error)) in miss_idx.into_iter().zip(miss_stamps).zip(fresh) {
+        match stamp {
+            Some(stamp) => {
+                store.push((row, stamp, error));
+                store_idx.push(i);
+            }
+            None => slots[i] = Some((row, error)),
+        }
+    }
+
+    let ids = db.upsert_many(&store)?;
+    for ((i, id), (mut row, _stamp, error)) in store_idx.into_iter().zip(ids).zip(store) {
+        row.id = Some(id);
+        slots[i] = Some((row, error));
+    }
+
+    Ok(split_errors(slots.into_iter().flatten().collect()))
+}
+
+/// (row, error) pairs -> rows + a `ScanError` for each failed one (same order).
+fn split_errors(results: Vec<(TrackRow, Option<String>)>) -> (Vec<TrackRow>, Vec<ScanError>) {
+    let mut errors = Vec::new();
+    let rows = results
+        .into_iter()
+        .map(|(row, error)| {
+            if let Some(reason) = error {
+                errors.push(ScanError {
+                    path: row.path.clone(),
+                    reason,
+                });
+            }
+            row
+        })
+        .collect();
+
+    (rows, errors)
+}
+
+/// Read (row, error) per path. Progress counts continue from `done_before`.
+#[cfg(feature = "parallel-scan")]
+fn read_rows(
+    paths: Vec<PathBuf>,
+    progress: Option<&Sender<ScanProgress>>,
+    done_before: usize,
+    total: usize,
+) -> Vec<(TrackRow, Option<String>)> {
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let scanned = AtomicUsize::new(done_before);
+
+    // Indexed parallel iterator: collect() preserves input order.
+    paths
+        .into_par_iter()
+        .map(|path| {
+            let out = tags::read_track_row_checked(path);
+
+            if let Some(tx) = progress {
+                let n = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+                let _ = tx.send(ScanProgress { scanned: n, total });
+            }
+
+            out
+        })
+        .collect()
+}
+
+/// Sequential fallback when the `parallel-scan` feature is disabled.
+#[cfg(not(feature = "parallel-scan"))]
+fn read_rows(
+    paths: Vec<PathBuf>,
+    progress: Option<&Sender<ScanProgress>>,
+    done_before: usize,
+    total: usize,
+) -> Vec<(TrackRow, Option<String>)> {
+    let mut out: Vec<(TrackRow, Option<String>)> = Vec::with_capacity(paths.len());
 
     for path in paths {
-        let (row, failed) = tags::read_track_row(path);
-        if failed {
-            tag_failures += 1;
+        out.push(tags::read_track_row_checked(path));
+
+        if let Some(tx) = progress {
+            let _ = tx.send(ScanProgress {
+                scanned: done_before + out.len(),
+                total,
+            });
         }
-        rows.push(row);
     }
 
-    (rows, tag_failures)
+    out
 }
 
-/// Convenience: old API preserved (GUI can keep calling this for now).
+/// Convenience: discover + read in one call, no cache (used by `sonora --scan`).
 ///
 /// Internally, this is now just:
-/// - scan_paths(roots)
-/// - read_tracks(paths)
-pub fn scan_and_read_roots(roots: &[PathBuf]) -> Result<(Vec<TrackRow>, usize), String> {
-    let paths = scan_paths(roots)?;
-    let (rows, failures) = read_tracks(paths);
-    Ok((rows, failures))
-}
-
-/// Convenience for callers that have a single root.
-pub fn scan_paths_one(root: &Path) -> Result<Vec<PathBuf>, String> {
-    scan_paths(&[root.to_path_buf()])
+/// - scan_paths(roots, no exclusions)
+/// - read_tracks(paths, progress)
+pub fn scan_and_read_roots(
+    roots: &[PathBuf],
+    progress: Option<&Sender<ScanProgress>>,
+) -> Result<(Vec<TrackRow>, Vec<ScanError>), String> {
+    let paths = scan_paths(roots, &[])?;
+    Ok(read_tracks(paths, progress))
 }
@@ -0,0 +1,183 @@
+//! core/musicbrainz.rs
+//! MusicBrainz lookups (read-only web service, JSON).
+//!
+//! - Searches recordings by title + artist and picks the best-scoring match,
+//!   or fetches a known recording id (e.g. from an AcoustID fingerprint match).
+//! - Results only *suggest* values: the GUI puts them in the inspector draft and the
+//!   user decides whether to save.
+//! - MusicBrainz requires a descriptive User-Agent and allows ~1 request/second;
+//!   lookups are user-initiated, one at a time.
+
+use serde_json::Value;
+
+const API_ROOT: &str = "https://musicbrainz.org/ws/2";
+
+/// Search hits below this score (0-100) are treated as "no match".
+const MIN_SCORE: i64 = 80;
+
+/// The metadata we take from a recording + one of its releases.
+#[derive(Debug, Clone, Default)]
+pub struct MbRelease {
+    /// Search score (0-100); absent for direct id lookups.
+    pub score: Option<i64>,
+
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub year: Option<i32>,
+    pub track_no: Option<u32>,
+    pub track_total: Option<u32>,
+    pub disc_no: Option<u32>,
+
+    /// MusicBrainz ids of the matched recording and of the release it was taken from.
+    pub recording_id: Option<String>,
+    pub release_id: Option<String>,
+}
+
+/// Best recording match for `title` by `artist` (`Ok(None)` if nothing scores well enough).
+pub async fn lookup_by_title_artist(
+    title: &str,
+    artist: &str,
+) -> Result<Option<MbRelease>, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("MusicBrainz lookup needs a title".to_string());
+    }
+
+    let mut query = format!("recording:\"{}\"", escape_lucene(title));
+    if !artist.trim().is_empty() {
+        query.push_str(&format!(" AND artist:\"{}\"", escape_lucene(artist.trim())));
+    }
+
+    let Some(body) = get_json(
+        &format!("{API_ROOT}/recording"),
+        &[("query", query.as_str()), ("fmt", "json"), ("limit", "5")],
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let best = body["recordings"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|r| r["score"].as_i64().unwrap_or(0) >= MIN_SCORE)
+        .max_by_key(|r| r["score"].as_i64().unwrap_or(0));
+
+    Ok(best.map(release_from_recording))
+}
+
+/// Fetch one recording by MusicBrainz id (`Ok(None)` if it doesn't exist / was merged away).
+pub async fn lookup_by_recording_id(recording_id: &str) -> Result<Option<MbRelease>, String> {
+    let id = recording_id.trim();
+    if id.is_empty() {
+        return Err("MusicBrainz lookup needs a recording id".to_string());
+    }
+
+    let body = get_json(
+        &format!("{API_ROOT}/recording/{id}"),
+        &[("inc", "artist-credits+releases+media"), ("fmt", "json")],
+    )
+    .await?;
+
+    Ok(body.as_ref().map(release_from_recording))
+}
+
+/// GET + parse JSON. `Ok(None)` on 404 (unknown id).
+async fn get_json(url: &str, query: &[(&str, &str)]) -> Result<Option<Value>, String> {
+    let client = reqwest::Client::builder()
+        .user_agent(concat!(
+            "Sonora/",
+            env!("CARGO_PKG_VERSION"),
+            " ( https://github.com/Hjhawley/Sonora )"
+        ))
+        .build()
+        .map_err(|e| format!("HTTP client init failed: {e}"))?;
+
+    let resp = client
+        .get(url)
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("MusicBrainz request failed: {e}"))?;
+
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(format!("MusicBrainz returned HTTP {}", resp.status()));
+    }
+
+    resp.json::<Value>()
+        .await
+        .map(Some)
+        .map_err(|e| format!("MusicBrainz response was not JSON: {e}"))
+}
+
+/// Flatten one recording JSON object (+ its first release) into an `MbRelease`.
+fn release_from_recording(rec: &Value) -> MbRelease {
+    let release = rec["releases"].as_array().and_then(|r| r.first());
+    let medium = release
+        .and_then(|r| r["media"].as_array())
+        .and_then(|m| m.first());
+
+    MbRelease {
+        score: rec["score"].as_i64(),
+
+        title: str_field(&rec["title"]),
+        artist: artist_credit(&rec["artist-credit"]),
+        album: release.and_then(|r| str_field(&r["title"])),
+        album_artist: release.and_then(|r| artist_credit(&r["artist-credit"])),
+        year: release
+            .and_then(|r| r["date"].as_str())
+            .or_else(|| rec["first-release-date"].as_str())
+            .and_then(|d| d.get(..4))
+            .and_then(|y| y.parse().ok()),
+        // Search results call the track list "track"; direct lookups call it "tracks".
+        track_no: medium
+            .and_then(|m| m["track"].as_array().or_else(|| m["tracks"].as_array()))
+            .and_then(|t| t.first())
+            .and_then(|t| t["number"].as_str())
+            .and_then(|n| n.parse().ok()),
+        track_total: medium
+            .and_then(|m| m["track-count"].as_u64())
+            .map(|n| n as u32),
+        disc_no: medium
+            .and_then(|m| m["position"].as_u64())
+            .map(|n| n as u32),
+
+        recording_id: str_field(&rec["id"]),
+        release_id: release.and_then(|r| str_field(&r["id"])),
+    }
+}
+
+/// Join an artist-credit array the way MusicBrainz displays it ("A feat. B").
+fn artist_credit(v: &Value) -> Option<String> {
+    let credits = v.as_array()?;
+    let joined: String = credits
+        .iter()
+        .map(|c| {
+            format!(
+                "{}{}",
+                c["name"].as_str().unwrap_or_default(),
+                c["joinphrase"].as_str().unwrap_or_default()
+            )
+        })
+        .collect();
+    let joined = joined.trim().to_string();
+    (!joined.is_empty()).then_some(joined)
+}
+
+fn str_field(v: &Value) -> Option<String> {
+    v.as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Escape Lucene query syntax inside a quoted phrase.
+fn escape_lucene(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
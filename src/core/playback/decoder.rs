@@ -20,11 +20,8 @@ use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 use symphonia::core::units::{Time, TimeBase};
 
-/// Construct a new seekable rodio Source from `path`, starting at `start_ms`.
-pub fn open_source_at_ms(
-    path: &Path,
-    start_ms: u64,
-) -> Result<(SymphoniaSource, Option<u64>), String> {
+/// Open `path` and probe its container format (shared by playback and tag-time probing).
+pub(crate) fn probe_format(path: &Path) -> Result<Box<dyn FormatReader>, String> {
     let file = File::open(path).map_err(|e| format!("Open failed: {e}"))?;
     let mss = MediaSourceStream::new(Box::new(file), MediaSourceStreamOptions::default());
 
@@ -42,7 +39,15 @@ pub fn open_source_at_ms(
         )
         .map_err(|e| format!("Format probe failed: {e}"))?;
 
-    let mut format = probed.format;
+    Ok(probed.format)
+}
+
+/// Construct a new seekable rodio Source from `path`, starting at `start_ms`.
+pub fn open_source_at_ms(
+    path: &Path,
+    start_ms: u64,
+) -> Result<(SymphoniaSource, Option<u64>), String> {
+    let mut format = probe_format(path)?;
 
     let track = format
         .default_track()
@@ -134,7 +139,10 @@ pub fn open_source_at_ms(
     Ok((src, duration_ms))
 }
 
-fn duration_from_params(time_base: Option<TimeBase>, n_frames: Option<u64>) -> Option<u64> {
+pub(crate) fn duration_from_params(
+    time_base: Option<TimeBase>,
+    n_frames: Option<u64>,
+) -> Option<u64> {
     let tb = time_base?;
     let frames = n_frames?;
     let t = tb.calc_time(frames);
@@ -287,8 +295,7 @@ impl SymphoniaSource {
                         (sr, ch, out)
                     }
                     other => {
-                        let spec =
-                            SignalSpec::new(other.spec().rate, other.spec().channels.clone());
+                        let spec = SignalSpec::new(other.spec().rate, other.spec().channels);
                         let sr = spec.rate;
                         let ch = spec.channels.count() as u16;
 
@@ -1,16 +1,55 @@
 //! core/playback/engine.rs
+//!
+//! Gapless: when the current track nears its end we emit `NearEnd`; the GUI answers
+//! with `SetNext`, and once fewer than `PREBUFFER_MS` remain the next source is
+//! appended to the *same* sink (no stop/restart, so no gap). When the sink's sound
+//! count drops back to one, the appended track has taken over (`AdvancedToNext`).
+//!
+//! Crossfade: with `crossfade_ms > 0` the next track gets its own sink instead, started
+//! `crossfade_ms` before the end (`CrossfadeStarted`). `tick()` ramps the outgoing sink
+//! down and the new one up, then stops the old sink once the fade is done.
+//!
+//! Output device: `SetOutputDevice` opens a stream on the named device (empty = system
+//! default) and swaps it in; the playing track restarts there at its current position.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 use std::time::Duration;
 
-use rodio::{OutputStream, OutputStreamBuilder, Sink};
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{OutputStream, OutputStreamBuilder, Sink, cpal};
 
 use super::decoder::open_source_at_ms;
-use super::{PlayerCommand, PlayerEvent};
+use super::eq::{EqSettings, EqSource};
+use super::{PlayerCommand, PlayerEvent, RepeatMode, ReplayGainMode, TrackGain};
 
 const TICK_MS: u64 = 200;
 
+/// Tick faster while a crossfade is ramping, so volume steps stay inaudible.
+const FADE_TICK_MS: u64 = 50;
+
+/// Emit `NearEnd` once this much of the current track remains.
+const NEAR_END_MS: u64 = 5_000;
+
+/// Append the next track's source once this much of the current track remains.
+const PREBUFFER_MS: u64 = 2_000;
+
+/// A track appended behind the current one (gapless), not yet audible.
+struct AppendedTrack {
+    path: PathBuf,
+    duration_ms: Option<u64>,
+    gain: TrackGain,
+}
+
+/// The outgoing track of a crossfade, still audible on its own sink.
+struct Fade {
+    sink: Sink,
+    gain: TrackGain,
+    /// Fade length (the configured crossfade, capped for short tracks).
+    length_ms: u64,
+}
+
 pub struct PlaybackEngine {
     // Keep alive for lifetime of engine
     stream: OutputStream,
@@ -25,16 +64,78 @@ pub struct PlaybackEngine {
     // Track current volume so seek/play can apply it to new sinks
     volume: f32,
 
+    // ReplayGain: mode + the current file's gains (kept so seeks re-apply them).
+    replaygain_mode: ReplayGainMode,
+    current_gain: TrackGain,
+
+    // Equalizer bands, shared with every source we hand to a sink.
+    eq: Arc<EqSettings>,
+
     // Prevent duplicate TrackEnded events for the same track.
     ended_emitted: bool,
 
+    // Gapless: what plays after the current track (from SetNext), whether we've asked
+    // the GUI for it yet, and the source already appended to the sink (if any).
+    next_track: Option<(PathBuf, TrackGain)>,
+    near_end_emitted: bool,
+    appended: Option<AppendedTrack>,
+
+    // Crossfade length (0 = gapless instead) and the fade in progress, if any.
+    crossfade_ms: u64,
+    fade: Option<Fade>,
+
+    // Shuffle mode. Track choice lives in the GUI (it owns display order);
+    // the engine holds the flag and confirms changes via ShuffleChanged.
+    shuffle: bool,
+
+    // Repeat mode. Like shuffle, the GUI acts on TrackEnded; we just hold + confirm it.
+    repeat: RepeatMode,
+
     event_tx: Sender<PlayerEvent>,
 }
 
+/// Names of every output device across the available audio hosts (first seen wins).
+pub fn list_audio_devices() -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for device in output_devices() {
+        if let Ok(name) = device.name()
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+    }
+    names
+}
+
+fn output_devices() -> Vec<cpal::Device> {
+    cpal::available_hosts()
+        .into_iter()
+        .filter_map(|id| cpal::host_from_id(id).ok())
+        .filter_map(|host| host.output_devices().ok())
+        .flatten()
+        .collect()
+}
+
+/// Open an output stream on the named device, or the system default for `None`.
+fn open_stream(device: Option<&str>) -> Result<OutputStream, String> {
+    let Some(name) = device else {
+        return OutputStreamBuilder::open_default_stream()
+            .map_err(|e| format!("Audio init failed: {e}"));
+    };
+
+    let device = output_devices()
+        .into_iter()
+        .find(|d| d.name().is_ok_and(|n| n == name))
+        .ok_or_else(|| format!("Audio device not found: {name}"))?;
+
+    OutputStreamBuilder::from_device(device)
+        .and_then(|b| b.open_stream_or_fallback())
+        .map_err(|e| format!("Could not open {name}: {e}"))
+}
+
 impl PlaybackEngine {
     pub fn new(event_tx: Sender<PlayerEvent>) -> Result<Self, String> {
-        let stream = OutputStreamBuilder::open_default_stream()
-            .map_err(|e| format!("Audio init failed: {e}"))?;
+        let stream = open_stream(None)?;
 
         Ok(Self {
             stream,
@@ -43,16 +144,30 @@ impl PlaybackEngine {
             current_duration_ms: None,
             base_position_ms: 0,
             volume: 1.0,
+            replaygain_mode: ReplayGainMode::Off,
+            current_gain: TrackGain::default(),
+            eq: Arc::new(EqSettings::default()),
             ended_emitted: false,
+            next_track: None,
+            near_end_emitted: false,
+            appended: None,
+            crossfade_ms: 0,
+            fade: None,
+            shuffle: false,
+            repeat: RepeatMode::Off,
             event_tx,
         })
     }
 
     pub fn run(&mut self, command_rx: Receiver<PlayerCommand>) {
-        let tick = Duration::from_millis(TICK_MS);
-
         loop {
-            match command_rx.recv_timeout(tick) {
+            let tick_ms = if self.fade.is_some() {
+                FADE_TICK_MS
+            } else {
+                TICK_MS
+            };
+
+            match command_rx.recv_timeout(Duration::from_millis(tick_ms)) {
                 Ok(cmd) => {
                     if self.handle_command(cmd) {
                         break;
@@ -75,9 +190,13 @@ impl PlaybackEngine {
 
     fn handle_command(&mut self, cmd: PlayerCommand) -> bool {
         match cmd {
-            PlayerCommand::PlayFile(path) => {
+            PlayerCommand::PlayFile(path, gain) => {
                 #[cfg(debug_assertions)]
-                eprintln!("[ENGINE] PlayFile {}", path.display());
+                eprintln!("[ENGINE] PlayFile {} gain={:?}", path.display(), gain);
+
+                self.current_gain = gain;
+                // A fresh track gets a fresh "what's next" (the GUI re-sends it on NearEnd).
+                self.next_track = None;
 
                 if let Err(e) = self.play_file_at(path, 0, true) {
                     let _ = self.event_tx.send(PlayerEvent::Error(e));
@@ -89,6 +208,9 @@ impl PlaybackEngine {
 
                 if let Some(sink) = &self.sink {
                     sink.pause();
+                    if let Some(fade) = &self.fade {
+                        fade.sink.pause();
+                    }
                     let _ = self.event_tx.send(PlayerEvent::Paused);
                 }
             }
@@ -98,6 +220,9 @@ impl PlaybackEngine {
 
                 if let Some(sink) = &self.sink {
                     sink.play();
+                    if let Some(fade) = &self.fade {
+                        fade.sink.play();
+                    }
                     let _ = self.event_tx.send(PlayerEvent::Resumed);
                 }
             }
@@ -137,10 +262,58 @@ impl PlaybackEngine {
                 #[cfg(debug_assertions)]
                 eprintln!("[ENGINE] SetVolume {}", self.volume);
 
-                if let Some(sink) = &self.sink {
-                    sink.set_volume(self.volume);
+                self.apply_volume();
+            }
+            PlayerCommand::SetNext(path, gain) => {
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetNext {}", path.display());
+
+                // Too late to change once appended; otherwise tick() picks it up.
+                self.next_track = Some((path, gain));
+            }
+            PlayerCommand::SetEq(bands) => {
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetEq {} bands", bands.len());
+
+                // Live sources pick up the new generation on their next sample.
+                self.eq.set(bands);
+            }
+            PlayerCommand::SetReplayGain(mode) => {
+                self.replaygain_mode = mode;
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetReplayGain {:?}", self.replaygain_mode);
+
+                self.apply_volume();
+            }
+            PlayerCommand::SetCrossfade(ms) => {
+                self.crossfade_ms = ms;
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetCrossfade {}ms", self.crossfade_ms);
+            }
+            PlayerCommand::SetOutputDevice(name) => {
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetOutputDevice {name:?}");
+
+                if let Err(e) = self.switch_output_device(&name) {
+                    let _ = self.event_tx.send(PlayerEvent::Error(e));
                 }
             }
+            PlayerCommand::SetShuffle(on) => {
+                self.shuffle = on;
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetShuffle {}", self.shuffle);
+
+                let _ = self
+                    .event_tx
+                    .send(PlayerEvent::ShuffleChanged(self.shuffle));
+            }
+            PlayerCommand::SetRepeat(mode) => {
+                self.repeat = mode;
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] SetRepeat {:?}", self.repeat);
+
+                let _ = self.event_tx.send(PlayerEvent::RepeatChanged(self.repeat));
+            }
             PlayerCommand::Shutdown => {
                 #[cfg(debug_assertions)]
                 eprintln!("[ENGINE] Shutdown");
@@ -152,6 +325,12 @@ impl PlaybackEngine {
     }
 
     fn tick(&mut self) {
+        // The appended track took over: adopt it before reporting positions.
+        if self.appended.is_some() && self.sink.as_ref().is_some_and(|s| s.len() == 1) {
+            self.advance_to_appended();
+        }
+        self.update_fade();
+
         let Some(sink) = &self.sink else {
             return;
         };
@@ -159,6 +338,32 @@ impl PlaybackEngine {
         let position_ms = self.base_position_ms + sink.get_pos().as_millis() as u64;
         let _ = self.event_tx.send(PlayerEvent::Position { position_ms });
 
+        if let Some(dur) = self.current_duration_ms
+            && !sink.empty()
+        {
+            let remaining_ms = dur.saturating_sub(position_ms);
+
+            // Ask early enough that the answer is in before a crossfade has to begin.
+            if remaining_ms <= NEAR_END_MS + self.crossfade_ms && !self.near_end_emitted {
+                self.near_end_emitted = true;
+                let _ = self.event_tx.send(PlayerEvent::NearEnd { remaining_ms });
+            }
+
+            if self.crossfade_ms > 0 {
+                // Short tracks fade over at most their second half.
+                let fade_ms = self.crossfade_ms.min(dur / 2);
+                if remaining_ms <= fade_ms && self.fade.is_none() {
+                    self.start_crossfade(fade_ms);
+                }
+            } else if remaining_ms <= PREBUFFER_MS && self.appended.is_none() {
+                self.append_next();
+            }
+        }
+
+        let Some(sink) = &self.sink else {
+            return;
+        };
+
         if sink.empty() && self.current_path.is_some() && !self.ended_emitted {
             self.ended_emitted = true;
             let _ = self.event_tx.send(PlayerEvent::TrackEnded);
@@ -175,12 +380,12 @@ impl PlaybackEngine {
         self.stop_internal();
 
         let sink = Sink::connect_new(self.stream.mixer());
-        sink.set_volume(self.volume);
+        sink.set_volume(self.effective_volume());
 
         // decoder is responsible for seek + any fallback skipping.
         let (src, duration_ms) = open_source_at_ms(&path, start_ms)?;
 
-        sink.append(src);
+        sink.append(EqSource::new(src, self.eq.clone()));
 
         if resume_playing {
             sink.play();
@@ -212,13 +417,185 @@ impl PlaybackEngine {
         Ok(())
     }
 
+    /// Open `next_track` and queue it behind the current source (no-op if unset).
+    fn append_next(&mut self) {
+        let (Some(sink), Some((path, gain))) = (&self.sink, self.next_track.take()) else {
+            return;
+        };
+
+        match open_source_at_ms(&path, 0) {
+            Ok((src, duration_ms)) => {
+                #[cfg(debug_assertions)]
+                eprintln!("[ENGINE] Prebuffered next {}", path.display());
+
+                sink.append(EqSource::new(src, self.eq.clone()));
+                self.appended = Some(AppendedTrack {
+                    path,
+                    duration_ms,
+                    gain,
+                });
+            }
+            // Not fatal: the current track ends normally and the GUI falls back to TrackEnded.
+            Err(e) => {
+                let _ = self.event_tx.send(PlayerEvent::Error(e));
+            }
+        }
+    }
+
+    /// Start `next_track` on a second sink at volume 0 and hand the current one to `fade`.
+    /// No-op without a next track (the current one then just ends).
+    fn start_crossfade(&mut self, fade_ms: u64) {
+        let Some((path, gain)) = self.next_track.take() else {
+            return;
+        };
+
+        let (src, duration_ms) = match open_source_at_ms(&path, 0) {
+            Ok(opened) => opened,
+            // Not fatal: the current track ends normally and the GUI falls back to TrackEnded.
+            Err(e) => {
+                let _ = self.event_tx.send(PlayerEvent::Error(e));
+                return;
+            }
+        };
+
+        #[cfg(debug_assertions)]
+        eprintln!("[ENGINE] Crossfade {}ms into {}", fade_ms, path.display());
+
+        let sink = Sink::connect_new(self.stream.mixer());
+        sink.set_volume(0.0);
+        sink.append(EqSource::new(src, self.eq.clone()));
+        if self.sink.as_ref().is_some_and(|s| s.is_paused()) {
+            sink.pause();
+        }
+
+        if let Some(old) = self.sink.replace(sink) {
+            self.fade = Some(Fade {
+                sink: old,
+                gain: self.current_gain,
+                length_ms: fade_ms,
+            });
+        }
+
+        self.current_path = Some(path.clone());
+        self.current_duration_ms = duration_ms;
+        self.current_gain = gain;
+        self.base_position_ms = 0;
+        self.ended_emitted = false;
+        self.near_end_emitted = false;
+
+        let _ = self.event_tx.send(PlayerEvent::CrossfadeStarted {
+            next_path: path,
+            duration_ms,
+        });
+    }
+
+    /// Ramp the crossfade by the new track's play time (so pausing pauses the fade too);
+    /// the outgoing sink is stopped once the new one is at full volume.
+    fn update_fade(&mut self) {
+        let (Some(fade), Some(sink)) = (&self.fade, &self.sink) else {
+            return;
+        };
+
+        let t = (sink.get_pos().as_millis() as f32 / fade.length_ms.max(1) as f32).min(1.0);
+        fade.sink
+            .set_volume(self.volume_for(&fade.gain) * (1.0 - t));
+        sink.set_volume(self.effective_volume() * t);
+
+        if t >= 1.0
+            && let Some(fade) = self.fade.take()
+        {
+            fade.sink.stop();
+        }
+    }
+
+    /// Push volume/gain changes to the sink (mid-crossfade, to both at the ramp's levels).
+    fn apply_volume(&mut self) {
+        if self.fade.is_some() {
+            self.update_fade();
+        } else if let Some(sink) = &self.sink {
+            sink.set_volume(self.effective_volume());
+        }
+    }
+
+    /// The appended source is now playing: make it the current track.
+    fn advance_to_appended(&mut self) {
+        let Some(next) = self.appended.take() else {
+            return;
+        };
+
+        self.current_path = Some(next.path.clone());
+        self.current_duration_ms = next.duration_ms;
+        self.current_gain = next.gain;
+        self.base_position_ms = 0;
+        self.ended_emitted = false;
+        self.near_end_emitted = false;
+
+        if let Some(sink) = &self.sink {
+            sink.set_volume(self.effective_volume());
+        }
+
+        let _ = self.event_tx.send(PlayerEvent::AdvancedToNext {
+            path: next.path,
+            duration_ms: next.duration_ms,
+        });
+    }
+
+    /// User volume scaled by the ReplayGain factor and trim for the current file.
+    fn effective_volume(&self) -> f32 {
+        self.volume_for(&self.current_gain)
+    }
+
+    /// User volume scaled by `gain`'s ReplayGain factor and trim.
+    ///
+    /// Track mode falls back to the album gain (and vice versa) when one is missing.
+    fn volume_for(&self, gain: &TrackGain) -> f32 {
+        let db = match self.replaygain_mode {
+            ReplayGainMode::Off => None,
+            ReplayGainMode::Track => gain.track_db.or(gain.album_db),
+            ReplayGainMode::Album => gain.album_db.or(gain.track_db),
+        };
+
+        let factor = |db: Option<f32>| db.map_or(1.0, |db| 10f32.powf(db / 20.0));
+        self.volume * factor(db) * factor(gain.trim_db)
+    }
+
+    /// Swap the output stream, restarting the current track (if any) where it was.
+    /// On failure the old stream stays in place and playback is untouched.
+    fn switch_output_device(&mut self, name: &str) -> Result<(), String> {
+        let stream = open_stream(Some(name).filter(|n| !n.is_empty()))?;
+
+        let resume = self
+            .current_path
+            .clone()
+            .zip(self.sink.as_ref())
+            .map(|(path, sink)| {
+                let position_ms = self.base_position_ms + sink.get_pos().as_millis() as u64;
+                (path, position_ms, !sink.is_paused())
+            });
+
+        // Sinks are bound to the old stream's mixer; stop them before it goes away.
+        self.stop_internal();
+        self.stream.log_on_drop(false);
+        self.stream = stream;
+
+        match resume {
+            Some((path, position_ms, playing)) => self.play_file_at(path, position_ms, playing),
+            None => Ok(()),
+        }
+    }
+
     fn stop_internal(&mut self) {
         if let Some(sink) = self.sink.take() {
             sink.stop();
         }
+        if let Some(fade) = self.fade.take() {
+            fade.sink.stop();
+        }
         self.current_path = None;
         self.current_duration_ms = None;
         self.base_position_ms = 0;
         self.ended_emitted = false;
+        self.near_end_emitted = false;
+        self.appended = None;
     }
 }
@@ -0,0 +1,192 @@
+//! core/playback/eq.rs
+//! Software equalizer: a chain of peaking-EQ biquads wrapped around any rodio Source.
+//!
+//! - Coefficients follow the RBJ "Audio EQ Cookbook" peaking filter.
+//! - Bands live in `EqSettings`, shared (Arc) between the engine and every playing source.
+//!   `SetEq` bumps a generation counter; sources notice it on the next sample and
+//!   recompute their coefficients (filter state is kept, so changes don't click).
+
+use std::f32::consts::PI;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rodio::Source;
+
+use super::EqBand;
+
+/// Bands shared between the engine (writer) and playing sources (readers).
+#[derive(Debug, Default)]
+pub(crate) struct EqSettings {
+    bands: Mutex<Vec<EqBand>>,
+    generation: AtomicU64,
+}
+
+impl EqSettings {
+    pub(crate) fn set(&self, bands: Vec<EqBand>) {
+        if let Ok(mut guard) = self.bands.lock() {
+            *guard = bands;
+        }
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
+    fn snapshot(&self) -> Vec<EqBand> {
+        self.bands.lock().map(|b| b.clone()).unwrap_or_default()
+    }
+}
+
+/// Normalized biquad coefficients (a0 == 1).
+#[derive(Debug, Clone, Copy)]
+struct Coeffs {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+impl Coeffs {
+    /// Peaking EQ; `None` for flat or unusable bands (skipped entirely).
+    fn peaking(band: &EqBand, sample_rate: u32) -> Option<Self> {
+        let nyquist = sample_rate as f32 / 2.0;
+        if band.gain_db.abs() < 0.01 || band.q <= 0.0 || band.freq_hz <= 0.0 {
+            return None;
+        }
+        if band.freq_hz >= nyquist {
+            return None;
+        }
+
+        let a = 10f32.powf(band.gain_db / 40.0);
+        let w0 = 2.0 * PI * band.freq_hz / sample_rate as f32;
+        let alpha = w0.sin() / (2.0 * band.q);
+        let cos_w0 = w0.cos();
+
+        let a0 = 1.0 + alpha / a;
+        Some(Self {
+            b0: (1.0 + alpha * a) / a0,
+            b1: (-2.0 * cos_w0) / a0,
+            b2: (1.0 - alpha * a) / a0,
+            a1: (-2.0 * cos_w0) / a0,
+            a2: (1.0 - alpha / a) / a0,
+        })
+    }
+}
+
+/// Transposed direct form II state for one band on one channel.
+#[derive(Debug, Clone, Copy, Default)]
+struct BiquadState {
+    z1: f32,
+    z2: f32,
+}
+
+impl BiquadState {
+    fn process(&mut self, c: &Coeffs, x: f32) -> f32 {
+        let y = c.b0 * x + self.z1;
+        self.z1 = c.b1 * x - c.a1 * y + self.z2;
+        self.z2 = c.b2 * x - c.a2 * y;
+        y
+    }
+}
+
+/// rodio Source adapter applying the shared EQ bands to interleaved samples.
+pub(crate) struct EqSource<S> {
+    inner: S,
+    settings: Arc<EqSettings>,
+
+    // What the current coefficients were computed for.
+    generation: u64,
+    sample_rate: u32,
+    channels: u16,
+
+    coeffs: Vec<Coeffs>,
+    // [band][channel]
+    state: Vec<Vec<BiquadState>>,
+    channel: usize,
+}
+
+impl<S: Source<Item = f32>> EqSource<S> {
+    pub(crate) fn new(inner: S, settings: Arc<EqSettings>) -> Self {
+        let mut this = Self {
+            inner,
+            settings,
+            generation: 0,
+            sample_rate: 0,
+            channels: 0,
+            coeffs: Vec::new(),
+            state: Vec::new(),
+            channel: 0,
+        };
+        this.refresh();
+        this
+    }
+
+    /// Recompute coefficients for the current bands and stream format.
+    fn refresh(&mut self) {
+        self.generation = self.settings.generation.load(Ordering::Acquire);
+        let format_changed =
+            self.sample_rate != self.inner.sample_rate() || self.channels != self.inner.channels();
+        self.sample_rate = self.inner.sample_rate();
+        self.channels = self.inner.channels();
+
+        self.coeffs = self
+            .settings
+            .snapshot()
+            .iter()
+            .filter_map(|b| Coeffs::peaking(b, self.sample_rate))
+            .collect();
+
+        // Keep filter memory across band tweaks; reset it when the shape changes.
+        let chans = self.channels.max(1) as usize;
+        if format_changed || self.state.len() != self.coeffs.len() {
+            self.state = vec![vec![BiquadState::default(); chans]; self.coeffs.len()];
+            self.channel = 0;
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for EqSource<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut x = self.inner.next()?;
+
+        if self.channel == 0
+            && (self.generation != self.settings.generation.load(Ordering::Acquire)
+                || self.sample_rate != self.inner.sample_rate()
+                || self.channels != self.inner.channels())
+        {
+            self.refresh();
+        }
+
+        for (c, per_channel) in self.coeffs.iter().zip(self.state.iter_mut()) {
+            if let Some(s) = per_channel.get_mut(self.channel) {
+                x = s.process(c, x);
+            }
+        }
+
+        self.channel += 1;
+        if self.channel >= self.channels.max(1) as usize {
+            self.channel = 0;
+        }
+
+        Some(x)
+    }
+}
+
+impl<S: Source<Item = f32>> Source for EqSource<S> {
+    fn current_span_len(&self) -> Option<usize> {
+        self.inner.current_span_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
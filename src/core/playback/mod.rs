@@ -7,8 +7,30 @@ use std::thread;
 
 mod decoder;
 mod engine;
+mod eq;
 
-pub use engine::PlaybackEngine;
+pub(crate) use decoder::{duration_from_params, open_source_at_ms, probe_format};
+pub use engine::{PlaybackEngine, list_audio_devices};
+
+/// What happens when a track ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    #[default]
+    Off,
+    One,
+    All,
+}
+
+impl RepeatMode {
+    /// Off -> All -> One -> Off (playback bar button order).
+    pub fn cycled(self) -> Self {
+        match self {
+            RepeatMode::Off => RepeatMode::All,
+            RepeatMode::All => RepeatMode::One,
+            RepeatMode::One => RepeatMode::Off,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct PlaybackController {
@@ -22,14 +44,84 @@ impl PlaybackController {
     }
 }
 
+/// Which ReplayGain value (if any) scales playback volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayGainMode {
+    #[default]
+    Off,
+    Track,
+    Album,
+}
+
+impl ReplayGainMode {
+    /// Stable string used in `config.toml`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            ReplayGainMode::Off => "off",
+            ReplayGainMode::Track => "track",
+            ReplayGainMode::Album => "album",
+        }
+    }
+
+    pub fn from_config_key(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(ReplayGainMode::Off),
+            "track" => Some(ReplayGainMode::Track),
+            "album" => Some(ReplayGainMode::Album),
+            _ => None,
+        }
+    }
+
+    /// Off -> Track -> Album -> Off (sidebar button order).
+    pub fn cycled(self) -> Self {
+        match self {
+            ReplayGainMode::Off => ReplayGainMode::Track,
+            ReplayGainMode::Track => ReplayGainMode::Album,
+            ReplayGainMode::Album => ReplayGainMode::Off,
+        }
+    }
+}
+
+/// ReplayGain values for the file being played (dB, from its tags).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrackGain {
+    pub track_db: Option<f32>,
+    pub album_db: Option<f32>,
+    /// Per-track trim; applies in every ReplayGain mode (including Off).
+    pub trim_db: Option<f32>,
+}
+
+/// One peaking-EQ band (applied by the engine as a biquad filter).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EqBand {
+    pub freq_hz: f32,
+    pub gain_db: f32,
+    pub q: f32,
+}
+
 #[derive(Debug)]
 pub enum PlayerCommand {
-    PlayFile(PathBuf),
+    PlayFile(PathBuf, TrackGain),
     Pause,
     Resume,
+    // `Stop` / `Shutdown` are part of the engine protocol even if the GUI does not send them yet.
+    #[allow(dead_code)]
     Stop,
     Seek(u64),      // ms
     SetVolume(f32), // 0.0..=1.0
+    SetShuffle(bool),
+    SetRepeat(RepeatMode),
+    SetReplayGain(ReplayGainMode),
+    /// Track to play gaplessly after the current one (answer to `NearEnd`).
+    SetNext(PathBuf, TrackGain),
+    /// Replace the equalizer bands (applies to the playing track immediately).
+    SetEq(Vec<EqBand>),
+    /// Crossfade length in ms (0 = gapless, no overlap); takes effect at the next track change.
+    SetCrossfade(u64),
+    /// Play through the named output device (empty = system default); restarts the
+    /// current track on the new device at the same position.
+    SetOutputDevice(String),
+    #[allow(dead_code)]
     Shutdown,
 }
 
@@ -48,6 +140,22 @@ pub enum PlayerEvent {
         position_ms: u64,
     },
     TrackEnded,
+    /// The current track is about to end; reply with `SetNext` for gapless playback.
+    NearEnd {
+        remaining_ms: u64,
+    },
+    /// A `SetNext` track started seamlessly (no `TrackEnded` for the previous one).
+    AdvancedToNext {
+        path: PathBuf,
+        duration_ms: Option<u64>,
+    },
+    /// A `SetNext` track started fading in over the current one (now the current track).
+    CrossfadeStarted {
+        next_path: PathBuf,
+        duration_ms: Option<u64>,
+    },
+    ShuffleChanged(bool),
+    RepeatChanged(RepeatMode),
     Error(String),
 }
 
@@ -0,0 +1,89 @@
+//! core/playlist.rs
+//! Playlist files (M3U / M3U8).
+//!
+//! - Plain M3U: one path per line.
+//! - Extended M3U8: `#EXTM3U` header + `#EXTINF` lines; every `#` line is metadata we skip.
+//! - Relative entries resolve against the playlist's own folder.
+//! - Writing always produces extended M3U with absolute paths (UTF-8).
+
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+
+use super::types::TrackRow;
+
+/// Parse an M3U/M3U8 file into the audio paths it lists, in order.
+///
+/// Non-UTF-8 bytes (legacy Latin-1 `.m3u`) are replaced rather than rejected.
+pub fn parse_m3u(path: &Path) -> Result<Vec<PathBuf>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let text = String::from_utf8_lossy(&bytes);
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut out = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let entry = line.strip_prefix("file://").unwrap_or(line);
+        let entry = PathBuf::from(entry);
+
+        let full = if entry.is_absolute() {
+            entry
+        } else {
+            base.join(entry)
+        };
+
+        out.push(normalize_lexically(&full));
+    }
+
+    Ok(out)
+}
+
+/// Write `tracks` as an extended M3U playlist, in the given order.
+///
+/// `#EXTINF` uses whole seconds (-1 when the duration is unknown) and "artist - title".
+pub fn write_m3u(tracks: &[TrackRow], path: &Path) -> Result<(), String> {
+    let mut out = String::from("#EXTM3U\n");
+
+    for t in tracks {
+        let seconds: i64 = t.duration_ms.map(|ms| i64::from(ms / 1000)).unwrap_or(-1);
+
+        let title = t.title.clone().unwrap_or_else(|| {
+            t.path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let label = match &t.artist {
+            Some(artist) => format!("{artist} - {title}"),
+            None => title,
+        };
+
+        let abs = std::path::absolute(&t.path).unwrap_or_else(|_| t.path.clone());
+
+        let _ = writeln!(out, "#EXTINF:{seconds},{label}");
+        let _ = writeln!(out, "{}", abs.display());
+    }
+
+    std::fs::write(path, out).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Resolve `.` / `..` without touching the filesystem (so missing files still parse).
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push(c);
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
@@ -0,0 +1,188 @@
+//! core/scrobbler.rs
+//! Last.fm scrobbling (web service API 2.0; the old 1.x submission protocol is retired).
+//!
+//! - Blocking HTTP: callers run these on a worker thread.
+//! - Every write call is signed with `api_sig`: MD5 over the sorted `key + value`
+//!   pairs with the API secret appended (Last.fm's scheme, not a keyed HMAC).
+//! - Desktop auth flow: `get_token` -> user approves `auth_url` in a browser
+//!   -> `get_session` exchanges the token for a long-lived session key.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const API_ROOT: &str = "https://ws.audioscrobbler.com/2.0/";
+const AUTH_PAGE: &str = "https://www.last.fm/api/auth/";
+
+/// Tracks shorter than this are never scrobbled (Last.fm rule).
+const MIN_TRACK_MS: u64 = 30_000;
+
+/// Credentials persisted in `config.toml` (`[scrobbler]` table).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrobblerConfig {
+    pub api_key: String,
+    pub api_secret: String,
+    /// Empty until the auth flow completes.
+    pub session_key: String,
+    /// Last.fm account name the session belongs to.
+    pub username: String,
+}
+
+impl ScrobblerConfig {
+    pub fn has_api_credentials(&self) -> bool {
+        !self.api_key.trim().is_empty() && !self.api_secret.trim().is_empty()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.has_api_credentials() && !self.session_key.is_empty()
+    }
+}
+
+/// One finished play, as submitted to `track.scrobble`.
+#[derive(Debug, Clone)]
+pub struct Scrobble {
+    pub artist: String,
+    pub title: String,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub track_no: Option<u32>,
+    pub duration_ms: Option<u64>,
+    /// When playback started (UNIX seconds, UTC).
+    pub timestamp: u64,
+}
+
+/// Did this play count? At least 30s listened, or half the track (whichever comes first).
+/// Tracks under 30s never count.
+pub fn should_scrobble(listened_ms: u64, duration_ms: Option<u64>) -> bool {
+    if duration_ms.is_some_and(|d| d < MIN_TRACK_MS) {
+        return false;
+    }
+    listened_ms >= MIN_TRACK_MS || duration_ms.is_some_and(|d| listened_ms >= d / 2)
+}
+
+/// Step 1: request an unauthorized token.
+pub fn get_token(cfg: &ScrobblerConfig) -> Result<String, String> {
+    let body = call(cfg, "auth.getToken", Vec::new(), false)?;
+    body["token"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "Last.fm returned no token".to_string())
+}
+
+/// Step 2: page where the user grants Sonora access for `token`.
+pub fn auth_url(api_key: &str, token: &str) -> String {
+    format!("{AUTH_PAGE}?api_key={api_key}&token={token}")
+}
+
+/// Step 3: exchange an approved token for a session: (session key, username).
+pub fn get_session(cfg: &ScrobblerConfig, token: &str) -> Result<(String, String), String> {
+    let body = call(
+        cfg,
+        "auth.getSession",
+        vec![("token", token.to_string())],
+        false,
+    )?;
+    let key = body["session"]["key"]
+        .as_str()
+        .ok_or_else(|| "Last.fm returned no session (was access granted?)".to_string())?;
+    let name = body["session"]["name"].as_str().unwrap_or_default();
+    Ok((key.to_string(), name.to_string()))
+}
+
+/// Submit one play to the user's history.
+pub fn scrobble(cfg: &ScrobblerConfig, s: &Scrobble) -> Result<(), String> {
+    if !cfg.is_connected() {
+        return Err("Last.fm is not connected".to_string());
+    }
+
+    let mut params = vec![
+        ("artist", s.artist.clone()),
+        ("track", s.title.clone()),
+        ("timestamp", s.timestamp.to_string()),
+        ("sk", cfg.session_key.clone()),
+    ];
+    if let Some(album) = &s.album {
+        params.push(("album", album.clone()));
+    }
+    if let Some(album_artist) = &s.album_artist {
+        params.push(("albumArtist", album_artist.clone()));
+    }
+    if let Some(n) = s.track_no {
+        params.push(("trackNumber", n.to_string()));
+    }
+    if let Some(ms) = s.duration_ms {
+        params.push(("duration", (ms / 1000).to_string()));
+    }
+
+    let body = call(cfg, "track.scrobble", params, true)?;
+    if body["scrobbles"]["@attr"]["ignored"].as_u64().unwrap_or(0) > 0 {
+        let reason = body["scrobbles"]["scrobble"]["ignoredMessage"]["#text"]
+            .as_str()
+            .unwrap_or("no reason given");
+        return Err(format!("Last.fm ignored the scrobble: {reason}"));
+    }
+    Ok(())
+}
+
+/// Signed API call; returns the JSON body or Last.fm's error message.
+fn call(
+    cfg: &ScrobblerConfig,
+    method: &str,
+    mut params: Vec<(&str, String)>,
+    post: bool,
+) -> Result<Value, String> {
+    if !cfg.has_api_credentials() {
+        return Err("Last.fm API key and secret are required".to_string());
+    }
+
+    params.push(("method", method.to_string()));
+    params.push(("api_key", cfg.api_key.trim().to_string()));
+    let sig = api_sig(&params, cfg.api_secret.trim());
+    params.push(("api_sig", sig));
+    params.push(("format", "json".to_string()));
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!("Sonora/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("HTTP client init failed: {e}"))?;
+
+    let req = if post {
+        client.post(API_ROOT).form(&params)
+    } else {
+        client.get(API_ROOT).query(&params)
+    };
+
+    let resp = req
+        .send()
+        .map_err(|e| format!("Last.fm request failed: {e}"))?;
+    let status = resp.status();
+    let body: Value = resp
+        .json()
+        .map_err(|e| format!("Last.fm response was not JSON (HTTP {status}): {e}"))?;
+
+    if let Some(code) = body["error"].as_i64() {
+        let msg = body["message"].as_str().unwrap_or("unknown error");
+        return Err(format!("Last.fm error {code}: {msg}"));
+    }
+    if !status.is_success() {
+        return Err(format!("Last.fm returned HTTP {status}"));
+    }
+
+    Ok(body)
+}
+
+/// `api_sig`: params sorted by name, concatenated as `name + value`, secret appended, MD5 hex.
+/// `format` and `callback` are excluded by the spec (we add `format` after signing).
+fn api_sig(params: &[(&str, String)], secret: &str) -> String {
+    let mut sorted: Vec<&(&str, String)> = params.iter().collect();
+    sorted.sort_by_key(|(k, _)| *k);
+
+    let mut raw = String::new();
+    for (k, v) in sorted {
+        raw.push_str(k);
+        raw.push_str(v);
+    }
+    raw.push_str(secret);
+
+    format!("{:x}", md5::compute(raw.as_bytes()))
+}
@@ -0,0 +1,67 @@
+//! core/stats.rs
+//! Library overview numbers (one linear pass over in-memory rows, no IO).
+//!
+//! - Genres are counted by their trimmed text, case-sensitively (as tagged).
+//! - Decades come from `year` (1987 => 1980); tracks without a year are left out.
+//! - Average BPM only counts tracks that have a BPM.
+
+use std::collections::BTreeMap;
+
+use super::types::TrackRow;
+
+/// How many genres `top_genres` keeps.
+const TOP_GENRES: usize = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct LibraryStats {
+    pub total_tracks: usize,
+    /// Sum of known durations (tracks without one count as 0).
+    pub total_duration_ms: u64,
+    /// (genre, tracks), most common first; ties by name.
+    pub top_genres: Vec<(String, usize)>,
+    /// (decade start year, tracks), oldest first.
+    pub decades: Vec<(i32, usize)>,
+    pub average_bpm: Option<f32>,
+    pub missing_artwork: usize,
+}
+
+pub fn compute(tracks: &[TrackRow]) -> LibraryStats {
+    let mut genres: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut decades: BTreeMap<i32, usize> = BTreeMap::new();
+    let (mut bpm_sum, mut bpm_n) = (0u64, 0u64);
+    let mut stats = LibraryStats {
+        total_tracks: tracks.len(),
+        ..LibraryStats::default()
+    };
+
+    for t in tracks {
+        stats.total_duration_ms += u64::from(t.duration_ms.unwrap_or(0));
+
+        if let Some(g) = t.genre.as_deref().map(str::trim).filter(|g| !g.is_empty()) {
+            *genres.entry(g).or_default() += 1;
+        }
+        if let Some(y) = t.year {
+            *decades.entry(y.div_euclid(10) * 10).or_default() += 1;
+        }
+        if let Some(b) = t.bpm.filter(|&b| b > 0) {
+            bpm_sum += u64::from(b);
+            bpm_n += 1;
+        }
+        if t.artwork_count == 0 {
+            stats.missing_artwork += 1;
+        }
+    }
+
+    let mut top: Vec<(String, usize)> = genres
+        .into_iter()
+        .map(|(g, n)| (g.to_string(), n))
+        .collect();
+    // Stable sort: equal counts keep the map's alphabetical order.
+    top.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    top.truncate(TOP_GENRES);
+
+    stats.top_genres = top;
+    stats.decades = decades.into_iter().collect();
+    stats.average_bpm = (bpm_n > 0).then(|| bpm_sum as f32 / bpm_n as f32);
+    stats
+}
@@ -3,10 +3,22 @@
 
 use std::path::Path;
 
-use id3::Tag;
+use id3::frame::{Picture, PictureType};
+use id3::{Tag, TagLike};
+
+use super::super::library::{is_flac, is_ogg};
+use super::flac::read_flac_art;
+use super::write::{WriteOptions, check_writable, write_tag};
+
+/// ID3 picture type for "Cover (front)".
+pub const PICTURE_TYPE_FRONT_COVER: u8 = 3;
 
 /// Returns (image_bytes, mime) for the first embedded picture (APIC/PIC).
 pub fn read_embedded_art(path: &Path) -> Result<Option<(Vec<u8>, String)>, String> {
+    if is_flac(path) {
+        return read_flac_art(path);
+    }
+
     let tag = match Tag::read_from_path(path) {
         Ok(t) => t,
         Err(_) => return Ok(None),
@@ -20,3 +32,106 @@ pub fn read_embedded_art(path: &Path) -> Result<Option<(Vec<u8>, String)>, Strin
 
     Ok(None)
 }
+
+/// Replace all embedded pictures with a single one.
+///
+/// - Removes every existing `APIC` frame first (no duplicate accumulation)
+/// - `picture_type` is the raw ID3 byte (3 = front cover)
+/// - Other frames are left untouched (apart from `options.tag_version` conversion)
+pub fn write_embedded_art(
+    path: &Path,
+    image_bytes: Vec<u8>,
+    mime: &str,
+    picture_type: u8,
+    options: &WriteOptions,
+) -> Result<(), String> {
+    check_writable(path)?;
+    if is_ogg(path) || is_flac(path) {
+        return Err("Embedded art is only written to MP3 (ID3) tags".to_string());
+    }
+    let mut tag = Tag::read_from_path(path).unwrap_or_else(|_| Tag::new());
+
+    tag.remove_all_pictures();
+    tag.add_frame(Picture {
+        mime_type: mime.to_string(),
+        picture_type: picture_type_from_u8(picture_type),
+        description: String::new(),
+        data: image_bytes,
+    });
+
+    // Same version policy as `write_track_row`.
+    write_tag(&mut tag, path, options)
+}
+
+/// Sniff JPEG/PNG from magic bytes. Returns the MIME type, or `None` if neither.
+pub fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
+        Some("image/png")
+    } else {
+        None
+    }
+}
+
+/// Pixel size (width, height) from a JPEG/PNG header, without decoding the image.
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| -> Option<u32> {
+        Some(u32::from(u16::from_be_bytes([
+            *bytes.get(i)?,
+            *bytes.get(i + 1)?,
+        ])))
+    };
+
+    match sniff_image_mime(bytes)? {
+        // PNG: IHDR is always first; width/height are big-endian u32s at 16..24.
+        "image/png" => {
+            let w = bytes.get(16..20)?.try_into().ok().map(u32::from_be_bytes)?;
+            let h = bytes.get(20..24)?.try_into().ok().map(u32::from_be_bytes)?;
+            Some((w, h))
+        }
+        // JPEG: walk marker segments until a start-of-frame (SOF0..SOF15, minus DHT/JPG/DAC).
+        _ => {
+            let mut i = 2;
+            while i + 9 < bytes.len() {
+                if bytes[i] != 0xFF {
+                    return None;
+                }
+                let marker = bytes[i + 1];
+                if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                    return Some((be16(i + 7)?, be16(i + 5)?));
+                }
+                i += 2 + be16(i + 2)? as usize;
+            }
+            None
+        }
+    }
+}
+
+/// Map a raw ID3 picture type byte to the id3 crate enum.
+fn picture_type_from_u8(n: u8) -> PictureType {
+    match n {
+        0 => PictureType::Other,
+        1 => PictureType::Icon,
+        2 => PictureType::OtherIcon,
+        3 => PictureType::CoverFront,
+        4 => PictureType::CoverBack,
+        5 => PictureType::Leaflet,
+        6 => PictureType::Media,
+        7 => PictureType::LeadArtist,
+        8 => PictureType::Artist,
+        9 => PictureType::Conductor,
+        10 => PictureType::Band,
+        11 => PictureType::Composer,
+        12 => PictureType::Lyricist,
+        13 => PictureType::RecordingLocation,
+        14 => PictureType::DuringRecording,
+        15 => PictureType::DuringPerformance,
+        16 => PictureType::ScreenCapture,
+        17 => PictureType::BrightFish,
+        18 => PictureType::Illustration,
+        19 => PictureType::BandLogo,
+        20 => PictureType::PublisherLogo,
+        n => PictureType::Undefined(n),
+    }
+}
@@ -0,0 +1,52 @@
+//! core/tags/audio.rs
+//! Audio-stream properties (not tags): probed from the container via Symphonia.
+//!
+//! - Uses the same probe setup as playback (`playback::probe_format`).
+//! - One probe per file per scan; everything stream-level comes from it.
+//! - Best-effort: any probe failure yields `None`s and the caller falls back to tags.
+
+use std::path::Path;
+
+use crate::core::playback::{duration_from_params, probe_format};
+
+/// Stream-level facts about an audio file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioProperties {
+    /// Real stream duration (ms) from frame count + time base.
+    /// Preferred over `TLEN`, which encoders often leave wrong or absent.
+    pub duration_ms: Option<u32>,
+
+    /// Sample rate in Hz.
+    pub sample_rate: Option<u32>,
+
+    /// Average bit rate in kbps (file size / duration, so VBR reads as its average).
+    pub bit_rate: Option<u32>,
+
+    /// Channel count (1 = mono, 2 = stereo, ...).
+    pub channels: Option<u16>,
+}
+
+pub fn read_audio_properties(path: &Path) -> AudioProperties {
+    let Ok(format) = probe_format(path) else {
+        return AudioProperties::default();
+    };
+    let Some(track) = format.default_track() else {
+        return AudioProperties::default();
+    };
+    let params = &track.codec_params;
+
+    let duration_ms = duration_from_params(params.time_base, params.n_frames)
+        .and_then(|ms| u32::try_from(ms).ok());
+
+    let bit_rate = duration_ms.filter(|&ms| ms > 0).and_then(|ms| {
+        let bytes = std::fs::metadata(path).ok()?.len();
+        u32::try_from(bytes * 8 / u64::from(ms)).ok() // bits per ms == kbps
+    });
+
+    AudioProperties {
+        duration_ms,
+        sample_rate: params.sample_rate,
+        bit_rate,
+        channels: params.channels.map(|c| c.count() as u16),
+    }
+}
@@ -0,0 +1,140 @@
+//! core/tags/fields.rs
+//! Format-agnostic "what a write should leave in the file" for one `TrackRow`.
+//!
+//! All writers (ID3 for `.mp3`, Vorbis comments for `.flac`/`.ogg`) start from
+//! `desired_text_fields` and map each `TextField` to their own frame id / key.
+//! Fields that need format-specific shapes (track/disc pairs, year/date,
+//! comments, lyrics, trim) are handled by each writer directly.
+
+use super::super::types::TrackRow;
+
+/// A plain single-value text field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TextField {
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    Composer,
+    Genre,
+    Grouping,
+    Lyricist,
+    Conductor,
+    Remixer,
+    Publisher,
+    Subtitle,
+    Bpm,
+    Key,
+    Mood,
+    Language,
+    Isrc,
+    EncoderSettings,
+    EncodedBy,
+    Copyright,
+    TitleSort,
+    ArtistSort,
+    AlbumSort,
+    AlbumArtistSort,
+}
+
+impl TextField {
+    pub(super) fn id3_frame(self) -> &'static str {
+        match self {
+            TextField::Title => "TIT2",
+            TextField::Artist => "TPE1",
+            TextField::Album => "TALB",
+            TextField::AlbumArtist => "TPE2",
+            TextField::Composer => "TCOM",
+            TextField::Genre => "TCON",
+            TextField::Grouping => "TIT1",
+            TextField::Lyricist => "TEXT",
+            TextField::Conductor => "TPE3",
+            TextField::Remixer => "TPE4",
+            TextField::Publisher => "TPUB",
+            TextField::Subtitle => "TIT3",
+            TextField::Bpm => "TBPM",
+            TextField::Key => "TKEY",
+            TextField::Mood => "TMOO",
+            TextField::Language => "TLAN",
+            TextField::Isrc => "TSRC",
+            TextField::EncoderSettings => "TSSE",
+            TextField::EncodedBy => "TENC",
+            TextField::Copyright => "TCOP",
+            TextField::TitleSort => "TSOT",
+            TextField::ArtistSort => "TSOP",
+            TextField::AlbumSort => "TSOA",
+            TextField::AlbumArtistSort => "TSO2",
+        }
+    }
+
+    /// Vorbis comment key (common names, as used by e.g. MusicBrainz Picard).
+    pub(super) fn vorbis_key(self) -> &'static str {
+        match self {
+            TextField::Title => "TITLE",
+            TextField::Artist => "ARTIST",
+            TextField::Album => "ALBUM",
+            TextField::AlbumArtist => "ALBUMARTIST",
+            TextField::Composer => "COMPOSER",
+            TextField::Genre => "GENRE",
+            TextField::Grouping => "GROUPING",
+            TextField::Lyricist => "LYRICIST",
+            TextField::Conductor => "CONDUCTOR",
+            TextField::Remixer => "REMIXER",
+            TextField::Publisher => "LABEL",
+            TextField::Subtitle => "SUBTITLE",
+            TextField::Bpm => "BPM",
+            TextField::Key => "KEY",
+            TextField::Mood => "MOOD",
+            TextField::Language => "LANGUAGE",
+            TextField::Isrc => "ISRC",
+            TextField::EncoderSettings => "ENCODERSETTINGS",
+            TextField::EncodedBy => "ENCODEDBY",
+            TextField::Copyright => "COPYRIGHT",
+            TextField::TitleSort => "TITLESORT",
+            TextField::ArtistSort => "ARTISTSORT",
+            TextField::AlbumSort => "ALBUMSORT",
+            TextField::AlbumArtistSort => "ALBUMARTISTSORT",
+        }
+    }
+}
+
+/// Desired value per text field; `None` (or empty/whitespace) = remove it.
+/// Extended fields are only included when `write_extended` (else left as they are).
+pub(super) fn desired_text_fields(
+    row: &TrackRow,
+    write_extended: bool,
+) -> Vec<(TextField, Option<String>)> {
+    let mut out = vec![
+        (TextField::Title, row.title.clone()),
+        (TextField::Artist, row.artist.clone()),
+        (TextField::Album, row.album.clone()),
+        (TextField::AlbumArtist, row.album_artist.clone()),
+        (TextField::Composer, row.composer.clone()),
+        (TextField::Genre, row.genre.clone()),
+        (TextField::Grouping, row.grouping.clone()),
+        (TextField::Lyricist, row.lyricist.clone()),
+    ];
+
+    if write_extended {
+        out.extend([
+            (TextField::Conductor, row.conductor.clone()),
+            (TextField::Remixer, row.remixer.clone()),
+            (TextField::Publisher, row.publisher.clone()),
+            (TextField::Subtitle, row.subtitle.clone()),
+            (TextField::Bpm, row.bpm.map(|b| b.to_string())),
+            (TextField::Key, row.key.clone()),
+            (TextField::Mood, row.mood.clone()),
+            (TextField::Language, row.language.clone()),
+            (TextField::Isrc, row.isrc.clone()),
+            (TextField::EncoderSettings, row.encoder_settings.clone()),
+            (TextField::EncodedBy, row.encoded_by.clone()),
+            (TextField::Copyright, row.copyright.clone()),
+            (TextField::TitleSort, row.title_sort.clone()),
+            (TextField::ArtistSort, row.artist_sort.clone()),
+            (TextField::AlbumSort, row.album_sort.clone()),
+            (TextField::AlbumArtistSort, row.album_artist_sort.clone()),
+        ]);
+    }
+
+    out
+}
@@ -0,0 +1,153 @@
+//! core/tags/flac.rs
+//! Read/write a FLAC file's Vorbis comment block (metaflac).
+//!
+//! - Reads map the same keys as OGG (`ogg::row_from_comments`); artwork is the
+//!   file's PICTURE blocks.
+//! - Writes follow the ID3 writer's contract: `None`/empty removes the key, extended
+//!   fields only when `write_extended`, optional backup, atomic replace.
+//! - metaflac upper-cases keys on read, so lookups use upper case too.
+
+use std::path::Path;
+
+use metaflac::BlockType;
+
+use super::super::types::{TagFormat, TrackRow};
+use super::fields::desired_text_fields;
+use super::ogg::row_from_comments;
+use super::util::TRIM_DB_DESC;
+use super::write::{WriteOptions, backup_if_requested, write_atomic};
+
+/// Build a `TrackRow` from the file's Vorbis comments (duration etc. from the stream probe).
+pub(super) fn read_flac_row(path: &Path) -> Result<TrackRow, String> {
+    let tag = metaflac::Tag::read_from_path(path)
+        .map_err(|e| format!("Unreadable FLAC metadata: {e}"))?;
+
+    let mut row = row_from_comments(path, |key| {
+        tag.get_vorbis(&key.to_ascii_uppercase())
+            .and_then(|mut v| v.next())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    });
+    row.artwork_count = tag.pictures().count() as u32;
+    row.tag_version = Some("FLAC (Vorbis comment)".to_string());
+    row.tag_format = Some(TagFormat::FlacVorbis);
+    Ok(row)
+}
+
+/// First embedded picture as (bytes, mime), if any.
+pub(super) fn read_flac_art(path: &Path) -> Result<Option<(Vec<u8>, String)>, String> {
+    let tag = match metaflac::Tag::read_from_path(path) {
+        Ok(t) => t,
+        Err(_) => return Ok(None),
+    };
+    Ok(tag
+        .pictures()
+        .next()
+        .map(|p| (p.data.clone(), p.mime_type.clone())))
+}
+
+pub(super) fn write_flac_row(row: &TrackRow, options: &WriteOptions) -> Result<(), String> {
+    let path = &row.path;
+    let mut tag =
+        metaflac::Tag::read_from_path(path).map_err(|e| format!("FLAC read failed: {e}"))?;
+
+    for (field, value) in desired_text_fields(row, options.write_extended) {
+        set_opt(&mut tag, field.vorbis_key(), value.as_deref());
+    }
+
+    // Track/disc: number and total are separate keys in Vorbis comments.
+    set_opt(
+        &mut tag,
+        "TRACKNUMBER",
+        row.track_no.map(|n| n.to_string()).as_deref(),
+    );
+    set_opt(
+        &mut tag,
+        "TRACKTOTAL",
+        row.track_total.map(|n| n.to_string()).as_deref(),
+    );
+    set_opt(
+        &mut tag,
+        "DISCNUMBER",
+        row.disc_no.map(|n| n.to_string()).as_deref(),
+    );
+    set_opt(
+        &mut tag,
+        "DISCTOTAL",
+        row.disc_total.map(|n| n.to_string()).as_deref(),
+    );
+
+    // DATE carries both year and full date. Without extended fields, keep an
+    // existing full date as long as it still starts with the year.
+    let year = row.year.map(|y| y.to_string());
+    let date = if options.write_extended {
+        row.date.clone().or(year)
+    } else {
+        let current = tag
+            .get_vorbis("DATE")
+            .and_then(|mut v| v.next())
+            .map(str::to_string);
+        match (current, year) {
+            (Some(d), Some(y)) if d.starts_with(&y) => Some(d),
+            (_, y) => y,
+        }
+    };
+    set_opt(&mut tag, "DATE", date.as_deref());
+
+    set_opt(&mut tag, "COMMENT", row.comment.as_deref());
+    set_opt(&mut tag, "LYRICS", row.lyrics.as_deref());
+    // Picard's Vorbis keys for the MusicBrainz recording / release ids.
+    set_opt(
+        &mut tag,
+        "MUSICBRAINZ_TRACKID",
+        row.mb_recording_id.as_deref(),
+    );
+    set_opt(
+        &mut tag,
+        "MUSICBRAINZ_ALBUMID",
+        row.mb_release_id.as_deref(),
+    );
+
+    if options.write_extended {
+        let trim = row.volume_trim_db.map(|db| format!("{db}"));
+        set_opt(&mut tag, TRIM_DB_DESC, trim.as_deref());
+    }
+
+    backup_if_requested(path, options)?;
+    write_atomic(path, |tmp| {
+        tag.write_to_path(tmp)
+            .map_err(|e| format!("FLAC write failed: {e}"))
+    })
+}
+
+/// Set (or with `None`, remove) one key without touching the rest. No backup:
+/// used for bookkeeping keys (date added, favorite).
+pub(super) fn write_flac_key(path: &Path, key: &str, value: Option<&str>) -> Result<(), String> {
+    let mut tag =
+        metaflac::Tag::read_from_path(path).map_err(|e| format!("FLAC read failed: {e}"))?;
+    set_opt(&mut tag, key, value);
+    write_atomic(path, |tmp| {
+        tag.write_to_path(tmp)
+            .map_err(|e| format!("FLAC write failed: {e}"))
+    })
+}
+
+/// Drop every comment and embedded picture (the vendor string stays, it names the encoder).
+pub(super) fn strip_flac_comments(path: &Path) -> Result<(), String> {
+    let mut tag =
+        metaflac::Tag::read_from_path(path).map_err(|e| format!("FLAC read failed: {e}"))?;
+    tag.vorbis_comments_mut().comments.clear();
+    tag.remove_blocks(BlockType::Picture);
+    write_atomic(path, |tmp| {
+        tag.write_to_path(tmp)
+            .map_err(|e| format!("FLAC write failed: {e}"))
+    })
+}
+
+/// Set a key to a single value, or remove it when `None`/empty.
+fn set_opt(tag: &mut metaflac::Tag, key: &str, v: Option<&str>) {
+    match v.map(str::trim) {
+        Some(s) if !s.is_empty() => tag.set_vorbis(key, vec![s]),
+        _ => tag.remove_vorbis(key),
+    }
+}
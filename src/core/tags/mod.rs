@@ -4,17 +4,40 @@
 //!
 //! Public surface area is intentionally small:
 //! - `read_track_row(path) -> (TrackRow, failed)`
-//! - `write_track_row(row, write_extended) -> Result<(), String>`
+//! - `read_track_row_checked(path) -> (TrackRow, Option<reason>)` (same read, keeps the cause)
+//! - `write_track_row(row, options) -> Result<(), String>` (fails early on read-only files)
+//! - `check_writable(path) -> Result<(), String>`
+//! - `is_internal_user_text(description) -> bool` (TXXX frames the writer owns)
+//! - `strip_tags(path, options) -> Result<(), String>`
+//! - `write_rating(path, email, rating, options) -> Result<(), String>`
+//! - `write_date_added(path, date_added) -> Result<(), String>`
+//! - `write_favorite(path, favorite, options) -> Result<(), String>`
+//! - `validate_mp3(path) -> Result<Mp3Stats, String>` (frame-level sanity check, no tags)
 //! - `read_embedded_art(path) -> Result<Option<(bytes, mime)>, String>`
+//! - `write_embedded_art(path, bytes, mime, picture_type, options) -> Result<(), String>`
 //!
-//! Everything below this layer is "tag-format-specific" (ID3 today).
+//! Everything below this layer is "tag-format-specific" (ID3, plus Vorbis comments for
+//! OGG and FLAC reads/writes).
 //! The rest of the app should treat this as a pluggable backend.
 
 mod art;
+mod audio; // stream probing (duration, sample/bit rate) used while building rows
+mod fields; // format-agnostic field map shared by the writers
+mod flac; // FLAC Vorbis comments (read + write)
+mod ogg; // OGG Vorbis comments (read + write)
 mod read;
 mod util;
+mod validate;
 mod write;
 
-pub use art::read_embedded_art;
-pub use read::read_track_row;
-pub use write::write_track_row;
+pub use art::{
+    PICTURE_TYPE_FRONT_COVER, image_dimensions, read_embedded_art, sniff_image_mime,
+    write_embedded_art,
+};
+pub use read::{read_track_row, read_track_row_checked};
+pub use util::is_internal_user_text;
+pub use validate::{Mp3Stats, VbrMode, validate_mp3};
+pub use write::{
+    TagVersion, WriteOptions, check_writable, strip_tags, write_date_added, write_favorite,
+    write_rating, write_track_row,
+};
@@ -0,0 +1,302 @@
+//! core/tags/ogg.rs
+//! Read/write Vorbis comments in an OGG Vorbis file.
+//!
+//! - The comment header is the stream's second packet; reads parse it with lewton.
+//! - Writes rebuild that one packet and re-page the file with the ogg crate. Audio
+//!   packets are copied as-is (same granule positions, same page ends).
+//! - Same write contract as FLAC: `None`/empty removes the key, extended fields only
+//!   when `write_extended`, optional backup, atomic replace.
+//! - Keys are case-insensitive (the spec says so); writes use upper case.
+//! - Only the first logical stream is touched (chained files keep their later headers).
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use ogg::{PacketReader, PacketWriteEndInfo, PacketWriter};
+
+use super::super::types::{CommentFrame, TagFormat, TrackRow};
+use super::fields::desired_text_fields;
+use super::read::empty_row;
+use super::util::{
+    DATE_ADDED_DESC, FAVORITE_DESC, TRIM_DB_DESC, parse_boolish, parse_gain_db,
+    parse_slash_pair_u32,
+};
+use super::write::{WriteOptions, backup_if_requested, write_atomic};
+
+/// Vendor string + comments, in file order (keys may repeat).
+struct VorbisComments {
+    vendor: String,
+    list: Vec<(String, String)>,
+}
+
+impl VorbisComments {
+    /// First value for `key` (case-insensitive), trimmed; empty counts as missing.
+    fn get(&self, key: &str) -> Option<String> {
+        self.list
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Set a key to a single value, or remove it when `None`/empty.
+    fn set_opt(&mut self, key: &str, v: Option<&str>) {
+        self.list.retain(|(k, _)| !k.eq_ignore_ascii_case(key));
+        if let Some(s) = v.map(str::trim).filter(|s| !s.is_empty()) {
+            self.list.push((key.to_string(), s.to_string()));
+        }
+    }
+
+    /// Serialize as a Vorbis comment header packet (type 3, framing bit set).
+    fn to_packet(&self) -> Vec<u8> {
+        fn push_str(out: &mut Vec<u8>, s: &str) {
+            out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            out.extend_from_slice(s.as_bytes());
+        }
+
+        let mut out = b"\x03vorbis".to_vec();
+        push_str(&mut out, &self.vendor);
+        out.extend_from_slice(&(self.list.len() as u32).to_le_bytes());
+        for (k, v) in &self.list {
+            push_str(&mut out, &format!("{k}={v}"));
+        }
+        out.push(1);
+        out
+    }
+}
+
+/// Build a `TrackRow` from the file's Vorbis comments (duration etc. from the stream probe).
+pub(super) fn read_ogg_row(path: &Path) -> Result<TrackRow, String> {
+    let comments = read_comments(path)?;
+
+    let mut row = row_from_comments(path, |key| comments.get(key));
+    row.artwork_count = comments
+        .list
+        .iter()
+        .filter(|(k, _)| k.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE"))
+        .count() as u32;
+    row.tag_version = Some("Vorbis comment".to_string());
+    row.tag_format = Some(TagFormat::VorbisComment);
+    Ok(row)
+}
+
+/// Map Vorbis comment keys onto a `TrackRow` (shared with FLAC). `get` returns a key's
+/// first value, trimmed, `None` when missing or empty. Tag format and artwork are left
+/// to the caller.
+pub(super) fn row_from_comments(path: &Path, get: impl Fn(&str) -> Option<String>) -> TrackRow {
+    let (track_no, track_from_pair) = parse_slash_pair_u32(get("TRACKNUMBER").as_deref());
+    let (disc_no, disc_from_pair) = parse_slash_pair_u32(get("DISCNUMBER").as_deref());
+    let number = |key: &str| get(key).and_then(|s| s.parse::<u32>().ok());
+
+    // DATE is "YYYY" or a full ISO date; the year is its first four digits.
+    let date = get("DATE");
+    let year = date
+        .as_deref()
+        .and_then(|d| d.get(..4))
+        .and_then(|y| y.parse::<i32>().ok());
+
+    let comment = get("COMMENT").or_else(|| get("DESCRIPTION"));
+
+    // Stream facts (duration, sample rate, ...) come from the probe in `empty_row`.
+    let mut row = empty_row(path.to_path_buf());
+
+    row.title = get("TITLE");
+    row.artist = get("ARTIST");
+    row.album = get("ALBUM");
+    row.album_artist = get("ALBUMARTIST").or_else(|| get("ALBUM ARTIST"));
+    row.composer = get("COMPOSER");
+
+    row.track_no = track_no;
+    row.track_total = number("TRACKTOTAL")
+        .or_else(|| number("TOTALTRACKS"))
+        .or(track_from_pair);
+    row.disc_no = disc_no;
+    row.disc_total = number("DISCTOTAL")
+        .or_else(|| number("TOTALDISCS"))
+        .or(disc_from_pair);
+
+    row.year = year;
+    row.date = date;
+    row.genre = get("GENRE");
+
+    row.grouping = get("GROUPING");
+    row.comments = comment
+        .iter()
+        .map(|text| CommentFrame {
+            lang: "eng".to_string(),
+            description: String::new(),
+            text: text.clone(),
+        })
+        .collect();
+    row.comment = comment;
+    row.lyrics = get("LYRICS").or_else(|| get("UNSYNCEDLYRICS"));
+    row.lyricist = get("LYRICIST");
+
+    row.conductor = get("CONDUCTOR");
+    row.remixer = get("REMIXER");
+    row.publisher = get("LABEL").or_else(|| get("PUBLISHER"));
+    row.subtitle = get("SUBTITLE");
+    row.bpm = number("BPM");
+    row.key = get("KEY");
+    row.mood = get("MOOD");
+    row.language = get("LANGUAGE");
+    row.isrc = get("ISRC");
+    row.encoder_settings = get("ENCODERSETTINGS");
+    row.encoded_by = get("ENCODEDBY");
+    row.copyright = get("COPYRIGHT");
+
+    row.title_sort = get("TITLESORT");
+    row.artist_sort = get("ARTISTSORT");
+    row.album_sort = get("ALBUMSORT");
+    row.album_artist_sort = get("ALBUMARTISTSORT");
+
+    row.compilation = get("COMPILATION").and_then(|s| parse_boolish(&s));
+    row.replaygain_track_gain = get("REPLAYGAIN_TRACK_GAIN").and_then(|s| parse_gain_db(&s));
+    row.replaygain_album_gain = get("REPLAYGAIN_ALBUM_GAIN").and_then(|s| parse_gain_db(&s));
+    row.volume_trim_db = get(TRIM_DB_DESC).and_then(|s| parse_gain_db(&s));
+    row.date_added = get(DATE_ADDED_DESC);
+    row.is_favorite = get(FAVORITE_DESC).is_some_and(|s| s == "1");
+    row.mb_recording_id = get("MUSICBRAINZ_TRACKID");
+    row.mb_release_id = get("MUSICBRAINZ_ALBUMID");
+
+    row
+}
+
+/// Write a `TrackRow` into the file's Vorbis comments (see the module docs).
+pub(super) fn write_ogg_row(row: &TrackRow, options: &WriteOptions) -> Result<(), String> {
+    let path = &row.path;
+    let mut comments = read_comments(path)?;
+
+    for (field, value) in desired_text_fields(row, options.write_extended) {
+        comments.set_opt(field.vorbis_key(), value.as_deref());
+    }
+
+    // Track/disc: number and total are separate keys, like FLAC.
+    let num = |n: Option<u32>| n.map(|n| n.to_string());
+    comments.set_opt("TRACKNUMBER", num(row.track_no).as_deref());
+    comments.set_opt("TRACKTOTAL", num(row.track_total).as_deref());
+    comments.set_opt("DISCNUMBER", num(row.disc_no).as_deref());
+    comments.set_opt("DISCTOTAL", num(row.disc_total).as_deref());
+
+    // DATE carries both year and full date. Without extended fields, keep an
+    // existing full date as long as it still starts with the year.
+    let year = row.year.map(|y| y.to_string());
+    let date = if options.write_extended {
+        row.date.clone().or(year)
+    } else {
+        match (comments.get("DATE"), year) {
+            (Some(d), Some(y)) if d.starts_with(&y) => Some(d),
+            (_, y) => y,
+        }
+    };
+    comments.set_opt("DATE", date.as_deref());
+
+    comments.set_opt("COMMENT", row.comment.as_deref());
+    comments.set_opt("LYRICS", row.lyrics.as_deref());
+    // Picard's Vorbis keys for the MusicBrainz recording / release ids.
+    comments.set_opt("MUSICBRAINZ_TRACKID", row.mb_recording_id.as_deref());
+    comments.set_opt("MUSICBRAINZ_ALBUMID", row.mb_release_id.as_deref());
+
+    if options.write_extended {
+        let trim = row.volume_trim_db.map(|db| format!("{db}"));
+        comments.set_opt(TRIM_DB_DESC, trim.as_deref());
+    }
+
+    backup_if_requested(path, options)?;
+    write_comments(path, &comments)
+}
+
+/// Set (or with `None`, remove) one key without touching the rest. No backup:
+/// used for bookkeeping keys (date added, favorite).
+pub(super) fn write_ogg_key(path: &Path, key: &str, value: Option<&str>) -> Result<(), String> {
+    let mut comments = read_comments(path)?;
+    comments.set_opt(key, value);
+    write_comments(path, &comments)
+}
+
+/// Drop every comment (the vendor string stays, it names the encoder).
+pub(super) fn strip_ogg_comments(path: &Path) -> Result<(), String> {
+    let mut comments = read_comments(path)?;
+    comments.list.clear();
+    write_comments(path, &comments)
+}
+
+fn open_reader(path: &Path) -> Result<PacketReader<BufReader<File>>, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(PacketReader::new(BufReader::new(file)))
+}
+
+fn read_comments(path: &Path) -> Result<VorbisComments, String> {
+    let mut reader = open_reader(path)?;
+
+    let mut next = || {
+        reader
+            .read_packet()
+            .map_err(|e| format!("OGG read failed: {e}"))?
+            .ok_or_else(|| "OGG stream ends before the comment header".to_string())
+    };
+    let ident = next()?;
+    if !ident.data.starts_with(b"\x01vorbis") {
+        return Err("Not an OGG Vorbis stream".to_string());
+    }
+    let packet = next()?;
+
+    let header = lewton::header::read_header_comment(&packet.data)
+        .map_err(|e| format!("Unreadable Vorbis comment header: {e}"))?;
+    Ok(VorbisComments {
+        vendor: header.vendor,
+        list: header.comment_list,
+    })
+}
+
+/// Re-page the whole file with the comment header swapped for `comments`.
+fn write_comments(path: &Path, comments: &VorbisComments) -> Result<(), String> {
+    let read_err = |e: ogg::OggReadError| format!("OGG read failed: {e}");
+    let write_err = |e: std::io::Error| format!("OGG write failed: {e}");
+
+    write_atomic(path, |tmp| {
+        let mut reader = open_reader(path)?;
+        let file = File::create(tmp).map_err(|e| format!("{}: {e}", tmp.display()))?;
+        let mut writer = PacketWriter::new(BufWriter::new(file));
+
+        let mut first_serial: Option<u32> = None;
+        let mut index = 0usize;
+
+        while let Some(packet) = reader.read_packet().map_err(read_err)? {
+            let serial = packet.stream_serial();
+            let serial_first = *first_serial.get_or_insert(serial);
+
+            // Keep the original page ends so the headers still end their page
+            // before the first audio page, as the Vorbis spec requires.
+            let end = if packet.last_in_stream() {
+                PacketWriteEndInfo::EndStream
+            } else if packet.last_in_page() {
+                PacketWriteEndInfo::EndPage
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            let absgp = packet.absgp_page();
+
+            let data = if serial == serial_first {
+                index += 1;
+                if index == 2 {
+                    comments.to_packet()
+                } else {
+                    packet.data
+                }
+            } else {
+                packet.data
+            };
+
+            writer
+                .write_packet(data.into_boxed_slice(), serial, end, absgp)
+                .map_err(write_err)?;
+        }
+
+        if index < 2 {
+            return Err("OGG stream ends before the comment header".to_string());
+        }
+        writer.into_inner().flush().map_err(write_err)
+    })
+}
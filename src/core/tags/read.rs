@@ -1,26 +1,111 @@
 //! core/tags/read.rs
 //! Read ID3 tags from an MP3 and convert them into a `TrackRow`.
+//! (`.ogg` and `.flac` files are read from their Vorbis comments instead, see `ogg.rs`
+//! and `flac.rs`.)
 //!
 //! - Tag reading does NOT assign identity.
 //! - `TrackRow.id` is set by the scanning/DB layer (temporary id now; DB id later).
 //! - So this module always returns `id: None`.
+//!
+//! Fallback: files without an ID3v2 tag are tried as ID3v1 (128-byte footer) before
+//! being reported as a tag read failure.
 
 use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 
-use id3::frame::Content;
+use id3::frame::{Content, TimestampFormat};
 use id3::{Tag, TagLike};
 
-use super::super::types::TrackRow;
-use super::util::{parse_be_u64, parse_boolish, parse_slash_pair_u32};
+use super::super::library;
+use super::super::types::{CommentFrame, SyncedLyric, TagFormat, TrackRow};
+use super::audio::read_audio_properties;
+use super::flac::read_flac_row;
+use super::ogg::read_ogg_row;
+use super::util::{
+    DATE_ADDED_DESC, FAVORITE_DESC, MB_RECORDING_ID_DESC, MB_RELEASE_ID_DESC, TRIM_DB_DESC,
+    expand_genre, parse_be_u64, parse_boolish, parse_gain_db, parse_slash_pair_u32,
+};
 
 pub fn read_track_row(path: PathBuf) -> (TrackRow, bool) {
+    let (row, error) = read_track_row_checked(path);
+    (row, error.is_some())
+}
+
+/// Like `read_track_row`, but says why tags couldn't be read (scan error log).
+pub fn read_track_row_checked(path: PathBuf) -> (TrackRow, Option<String>) {
+    if library::is_ogg(&path) {
+        return match read_ogg_row(&path) {
+            Ok(row) => (row, None),
+            Err(e) => (empty_row(path), Some(e)),
+        };
+    }
+    if library::is_flac(&path) {
+        return match read_flac_row(&path) {
+            Ok(row) => (row, None),
+            Err(e) => (empty_row(path), Some(e)),
+        };
+    }
+
     match Tag::read_from_path(&path) {
-        Ok(tag) => (build_row_from_tag(path, &tag), false),
-        Err(_) => (empty_row(path), true),
+        Ok(tag) => (build_row_from_tag(path, &tag), None),
+        Err(v2) => match id3::v1::Tag::read_from_path(&path) {
+            Ok(v1) => (build_row_from_v1(path, &v1), None),
+            Err(_) => {
+                let reason = match v2.kind {
+                    id3::ErrorKind::NoTag => "No ID3v2 or ID3v1 tag".to_string(),
+                    _ => format!("Unreadable ID3v2 tag: {v2}"),
+                };
+                (empty_row(path), Some(reason))
+            }
+        },
     }
 }
 
+/// ID3v1 only carries a handful of fixed-width fields; everything else stays empty.
+fn build_row_from_v1(path: PathBuf, tag: &id3::v1::Tag) -> TrackRow {
+    fn field(s: &str) -> Option<String> {
+        let s = s.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        (!s.is_empty()).then(|| s.to_string())
+    }
+
+    let mut row = empty_row(path);
+
+    row.title = field(&tag.title);
+    row.artist = field(&tag.artist);
+    row.album = field(&tag.album);
+    row.year = tag.year.trim().parse::<i32>().ok();
+    row.comment = field(&tag.comment);
+    row.comments = row
+        .comment
+        .iter()
+        .map(|text| CommentFrame {
+            lang: "eng".to_string(),
+            description: String::new(),
+            text: text.clone(),
+        })
+        .collect();
+    row.track_no = tag.track.filter(|&n| n > 0).map(u32::from);
+    // Enhanced-tag free-text genre wins over the genre byte (255 = unset).
+    row.genre = tag
+        .genre_str
+        .as_deref()
+        .and_then(field)
+        .or_else(|| (tag.genre_id != 255).then(|| expand_genre(&tag.genre_id.to_string())));
+
+    // v1.1 steals the last comment byte for the track number.
+    row.tag_version = Some(
+        if tag.track.is_some() {
+            "ID3v1.1"
+        } else {
+            "ID3v1"
+        }
+        .to_string(),
+    );
+    row.tag_format = Some(TagFormat::Id3v1);
+
+    row
+}
+
 fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
     let (track_no_from_text, track_total) =
         parse_slash_pair_u32(text_frame(tag, "TRCK").as_deref());
@@ -29,7 +114,6 @@ fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
     let track_no = tag.track().or(track_no_from_text);
     let disc_no = tag.disc().or(disc_no_from_text);
 
-    let date = text_frame(tag, "TDRC").or_else(|| text_frame(tag, "TYER"));
     let year = tag.year();
 
     let artwork_count = tag
@@ -37,21 +121,45 @@ fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
         .filter(|f| f.id() == "APIC" || f.id() == "PIC")
         .count() as u32;
 
-    let comment = first_comment(tag);
+    let comments = collect_comments(tag);
+    let comment = comments.first().map(|c| c.text.clone());
     let lyrics = first_lyrics(tag);
+    let synced_lyrics = first_synced_lyrics(tag);
 
     let user_text = collect_user_text(tag);
     let urls = collect_urls(tag);
 
+    // v2.4-only frames: files written as v2.3 keep them in `TXXX:<frame id>`.
+    let v24_text = |id: &str| text_frame(tag, id).or_else(|| user_text.get(id).cloned());
+
+    let date = v24_text("TDRC").or_else(|| v23_date(tag));
+
     let compilation = text_frame(tag, "TCMP")
         .and_then(|s| parse_boolish(&s))
         .or_else(|| user_text.get("COMPILATION").and_then(|s| parse_boolish(s)));
 
+    // ReplayGain lives in TXXX; taggers disagree on case ("REPLAYGAIN_TRACK_GAIN").
+    let replaygain_track_gain =
+        user_text_ci(&user_text, "replaygain_track_gain").and_then(parse_gain_db);
+    let replaygain_album_gain =
+        user_text_ci(&user_text, "replaygain_album_gain").and_then(parse_gain_db);
+    let volume_trim_db = user_text.get(TRIM_DB_DESC).and_then(|s| parse_gain_db(s));
+    let date_added = user_text.get(DATE_ADDED_DESC).cloned();
+    let is_favorite = user_text
+        .get(FAVORITE_DESC)
+        .is_some_and(|s| s.trim() == "1");
+    let mb_recording_id = user_text.get(MB_RECORDING_ID_DESC).cloned();
+    let mb_release_id = user_text.get(MB_RELEASE_ID_DESC).cloned();
+
     let (rating, popm_count) = popm_rating_and_count(tag);
     let pcnt_count = pcnt_count(tag);
     let play_count = popm_count.or(pcnt_count);
 
-    let duration_ms = text_frame(tag, "TLEN").and_then(|s| s.trim().parse::<u32>().ok());
+    // Probe the stream for the real length; TLEN is only a fallback.
+    let audio = read_audio_properties(&path);
+    let duration_ms = audio
+        .duration_ms
+        .or_else(|| text_frame(tag, "TLEN").and_then(|s| s.trim().parse::<u32>().ok()));
 
     let extra_text = collect_extra_text(tag);
 
@@ -84,12 +192,14 @@ fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
         year,
         date,
 
-        genre: text_frame(tag, "TCON"),
+        genre: text_frame(tag, "TCON").map(|s| expand_genre(&s)),
 
         // Common extended tags
         grouping: text_frame(tag, "TIT1"),
         comment,
+        comments,
         lyrics,
+        synced_lyrics,
         lyricist: text_frame(tag, "TEXT"),
 
         conductor: text_frame(tag, "TPE3"),
@@ -98,7 +208,7 @@ fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
         subtitle: text_frame(tag, "TIT3"),
         bpm: text_frame(tag, "TBPM").and_then(|s| s.trim().parse::<u32>().ok()),
         key: text_frame(tag, "TKEY"),
-        mood: text_frame(tag, "TMOO"),
+        mood: v24_text("TMOO"),
         language: text_frame(tag, "TLAN"),
         isrc: text_frame(tag, "TSRC"),
         encoder_settings: text_frame(tag, "TSSE"),
@@ -106,15 +216,31 @@ fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
         copyright: text_frame(tag, "TCOP"),
         artwork_count,
 
-        title_sort: text_frame(tag, "TSOT"),
-        artist_sort: text_frame(tag, "TSOP"),
-        album_sort: text_frame(tag, "TSOA"),
+        title_sort: v24_text("TSOT"),
+        artist_sort: v24_text("TSOP"),
+        album_sort: v24_text("TSOA"),
         album_artist_sort: text_frame(tag, "TSO2"),
 
         duration_ms,
+        sample_rate: audio.sample_rate,
+        bit_rate: audio.bit_rate,
+        channels: audio.channels,
+        tag_version: Some(tag.version().to_string()),
+        tag_format: Some(match tag.version() {
+            id3::Version::Id3v22 => TagFormat::Id3v2_2,
+            id3::Version::Id3v23 => TagFormat::Id3v2_3,
+            id3::Version::Id3v24 => TagFormat::Id3v2_4,
+        }),
         rating,
         play_count,
         compilation,
+        replaygain_track_gain,
+        replaygain_album_gain,
+        volume_trim_db,
+        date_added,
+        is_favorite,
+        mb_recording_id,
+        mb_release_id,
 
         user_text,
         urls,
@@ -122,7 +248,10 @@ fn build_row_from_tag(path: PathBuf, tag: &Tag) -> TrackRow {
     }
 }
 
-fn empty_row(path: PathBuf) -> TrackRow {
+pub(super) fn empty_row(path: PathBuf) -> TrackRow {
+    // No usable tag doesn't mean no audio: still probe the stream.
+    let audio = read_audio_properties(&path);
+
     TrackRow {
         // Identity is assigned by scan/DB layer, not tag read.
         id: None,
@@ -146,7 +275,9 @@ fn empty_row(path: PathBuf) -> TrackRow {
 
         grouping: None,
         comment: None,
+        comments: Vec::new(),
         lyrics: None,
+        synced_lyrics: Vec::new(),
         lyricist: None,
         conductor: None,
         remixer: None,
@@ -168,10 +299,22 @@ fn empty_row(path: PathBuf) -> TrackRow {
         album_sort: None,
         album_artist_sort: None,
 
-        duration_ms: None,
+        duration_ms: audio.duration_ms,
+        sample_rate: audio.sample_rate,
+        bit_rate: audio.bit_rate,
+        channels: audio.channels,
+        tag_version: None,
+        tag_format: Some(TagFormat::None),
         rating: None,
         play_count: None,
         compilation: None,
+        replaygain_track_gain: None,
+        replaygain_album_gain: None,
+        volume_trim_db: None,
+        date_added: None,
+        is_favorite: false,
+        mb_recording_id: None,
+        mb_release_id: None,
 
         user_text: BTreeMap::new(),
         urls: BTreeMap::new(),
@@ -179,6 +322,24 @@ fn empty_row(path: PathBuf) -> TrackRow {
     }
 }
 
+/// v2.3 date: TYER "YYYY" + TDAT "DDMM" + TIME "HHMM" => "YYYY-MM-DDTHH:MM" (parts optional).
+fn v23_date(tag: &Tag) -> Option<String> {
+    let year = text_frame(tag, "TYER")?;
+    let four_digits = |s: String| {
+        let s = s.trim().to_string();
+        (s.len() == 4 && s.bytes().all(|b| b.is_ascii_digit())).then_some(s)
+    };
+
+    let mut out = year.trim().to_string();
+    if let Some(ddmm) = text_frame(tag, "TDAT").and_then(four_digits) {
+        out.push_str(&format!("-{}-{}", &ddmm[2..], &ddmm[..2]));
+        if let Some(hhmm) = text_frame(tag, "TIME").and_then(four_digits) {
+            out.push_str(&format!("T{}:{}", &hhmm[..2], &hhmm[2..]));
+        }
+    }
+    Some(out)
+}
+
 /// Get a best-effort string value from a frame id.
 /// This is intentionally defensive: some frames that are “text-ish” may not be Content::Text.
 fn text_frame(tag: &Tag, id: &str) -> Option<String> {
@@ -195,42 +356,71 @@ fn text_frame(tag: &Tag, id: &str) -> Option<String> {
     }
 }
 
-fn first_comment(tag: &Tag) -> Option<String> {
-    for frame in tag.frames() {
-        if frame.id() == "COMM" {
-            if let Content::Comment(c) = frame.content() {
-                return Some(c.text.clone());
-            }
-        }
-    }
-    None
+fn collect_comments(tag: &Tag) -> Vec<CommentFrame> {
+    tag.comments()
+        .map(|c| CommentFrame {
+            lang: c.lang.clone(),
+            description: c.description.clone(),
+            text: c.text.clone(),
+        })
+        .collect()
 }
 
 fn first_lyrics(tag: &Tag) -> Option<String> {
     for frame in tag.frames() {
-        if frame.id() == "USLT" {
-            if let Content::Lyrics(l) = frame.content() {
-                return Some(l.text.clone());
-            }
+        if frame.id() == "USLT"
+            && let Content::Lyrics(l) = frame.content()
+        {
+            return Some(l.text.clone());
         }
     }
     None
 }
 
+/// First SYLT frame timed in milliseconds, as lines sorted by timestamp.
+/// (MPEG-frame timestamps would need the stream's frame rate; those frames are skipped.)
+fn first_synced_lyrics(tag: &Tag) -> Vec<SyncedLyric> {
+    let Some(sylt) = tag
+        .synchronised_lyrics()
+        .find(|s| s.timestamp_format == TimestampFormat::Ms)
+    else {
+        return Vec::new();
+    };
+
+    let mut lines: Vec<SyncedLyric> = sylt
+        .content
+        .iter()
+        .map(|(timestamp_ms, text)| SyncedLyric {
+            timestamp_ms: *timestamp_ms,
+            text: text.trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect();
+    lines.sort_by_key(|l| l.timestamp_ms);
+    lines
+}
+
 fn collect_user_text(tag: &Tag) -> BTreeMap<String, String> {
     let mut out = BTreeMap::new();
 
     for frame in tag.frames() {
-        if frame.id() == "TXXX" {
-            if let Content::ExtendedText(et) = frame.content() {
-                out.insert(et.description.clone(), et.value.clone());
-            }
+        if frame.id() == "TXXX"
+            && let Content::ExtendedText(et) = frame.content()
+        {
+            out.insert(et.description.clone(), et.value.clone());
         }
     }
 
     out
 }
 
+/// Case-insensitive lookup of a TXXX description.
+fn user_text_ci<'a>(user_text: &'a BTreeMap<String, String>, key: &str) -> Option<&'a str> {
+    user_text
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.as_str())
+}
+
 fn collect_urls(tag: &Tag) -> BTreeMap<String, String> {
     let mut out = BTreeMap::new();
 
@@ -257,10 +447,10 @@ fn collect_urls(tag: &Tag) -> BTreeMap<String, String> {
 
 fn popm_rating_and_count(tag: &Tag) -> (Option<u8>, Option<u64>) {
     for frame in tag.frames() {
-        if frame.id() == "POPM" {
-            if let Content::Popularimeter(p) = frame.content() {
-                return (Some(p.rating), Some(p.counter));
-            }
+        if frame.id() == "POPM"
+            && let Content::Popularimeter(p) = frame.content()
+        {
+            return (Some(p.rating), Some(p.counter));
         }
     }
     (None, None)
@@ -46,3 +46,345 @@ pub(crate) fn parse_be_u64(bytes: &[u8]) -> Option<u64> {
     }
     Some(v)
 }
+
+/// Text frames that only exist in ID3v2.4. Written as `TXXX:<id>` for v2.3 files.
+pub(crate) const V24_ONLY_TEXT: &[&str] = &[
+    "TDEN", "TDOR", "TDRL", "TDTG", "TIPL", "TMCL", "TMOO", "TPRO", "TSOA", "TSOP", "TSOT", "TSST",
+];
+
+/// TXXX description holding Sonora's per-track volume trim (dB).
+pub(crate) const TRIM_DB_DESC: &str = "SONORA_TRIM_DB";
+
+/// TXXX description holding the time Sonora first scanned the file (RFC 3339).
+pub(crate) const DATE_ADDED_DESC: &str = "SONORA_DATE_ADDED";
+
+/// TXXX description marking a loved track ("1"; no frame = not a favorite).
+pub(crate) const FAVORITE_DESC: &str = "SONORA_FAVORITE";
+
+/// TXXX descriptions Picard uses for the MusicBrainz recording / release ids.
+pub(crate) const MB_RECORDING_ID_DESC: &str = "MusicBrainz Recording Id";
+pub(crate) const MB_RELEASE_ID_DESC: &str = "MusicBrainz Album Id";
+
+/// TXXX descriptions the writer manages itself (v2.3 stand-ins, trim, date added,
+/// favorite, MusicBrainz ids); hidden from the custom-tag editor and never written
+/// from `user_text`.
+pub fn is_internal_user_text(description: &str) -> bool {
+    description == TRIM_DB_DESC
+        || description == DATE_ADDED_DESC
+        || description == FAVORITE_DESC
+        || description == MB_RECORDING_ID_DESC
+        || description == MB_RELEASE_ID_DESC
+        || description == "TDRC"
+        || V24_ONLY_TEXT.contains(&description)
+}
+
+/// Parse a ReplayGain value like "-6.54 dB" / "+1.2dB" / "3" into dB.
+pub(crate) fn parse_gain_db(s: &str) -> Option<f32> {
+    let s = s.trim();
+    let s = s
+        .strip_suffix("dB")
+        .or_else(|| s.strip_suffix("db"))
+        .or_else(|| s.strip_suffix("DB"))
+        .unwrap_or(s);
+    s.trim().parse::<f32>().ok().filter(|v| v.is_finite())
+}
+
+/// The ID3v1 genre table (0-79 from the spec, 80-191 from Winamp's extensions).
+const ID3V1_GENRES: [&str; 192] = [
+    "Blues",
+    "Classic Rock",
+    "Country",
+    "Dance",
+    "Disco",
+    "Funk",
+    "Grunge",
+    "Hip-Hop",
+    "Jazz",
+    "Metal",
+    "New Age",
+    "Oldies",
+    "Other",
+    "Pop",
+    "R&B",
+    "Rap",
+    "Reggae",
+    "Rock",
+    "Techno",
+    "Industrial",
+    "Alternative",
+    "Ska",
+    "Death Metal",
+    "Pranks",
+    "Soundtrack",
+    "Euro-Techno",
+    "Ambient",
+    "Trip-Hop",
+    "Vocal",
+    "Jazz+Funk",
+    "Fusion",
+    "Trance",
+    "Classical",
+    "Instrumental",
+    "Acid",
+    "House",
+    "Game",
+    "Sound Clip",
+    "Gospel",
+    "Noise",
+    "Alternative Rock",
+    "Bass",
+    "Soul",
+    "Punk",
+    "Space",
+    "Meditative",
+    "Instrumental Pop",
+    "Instrumental Rock",
+    "Ethnic",
+    "Gothic",
+    "Darkwave",
+    "Techno-Industrial",
+    "Electronic",
+    "Pop-Folk",
+    "Eurodance",
+    "Dream",
+    "Southern Rock",
+    "Comedy",
+    "Cult",
+    "Gangsta",
+    "Top 40",
+    "Christian Rap",
+    "Pop/Funk",
+    "Jungle",
+    "Native US",
+    "Cabaret",
+    "New Wave",
+    "Psychedelic",
+    "Rave",
+    "Showtunes",
+    "Trailer",
+    "Lo-Fi",
+    "Tribal",
+    "Acid Punk",
+    "Acid Jazz",
+    "Polka",
+    "Retro",
+    "Musical",
+    "Rock & Roll",
+    "Hard Rock",
+    "Folk",
+    "Folk-Rock",
+    "National Folk",
+    "Swing",
+    "Fast Fusion",
+    "Bebop",
+    "Latin",
+    "Revival",
+    "Celtic",
+    "Bluegrass",
+    "Avantgarde",
+    "Gothic Rock",
+    "Progressive Rock",
+    "Psychedelic Rock",
+    "Symphonic Rock",
+    "Slow Rock",
+    "Big Band",
+    "Chorus",
+    "Easy Listening",
+    "Acoustic",
+    "Humour",
+    "Speech",
+    "Chanson",
+    "Opera",
+    "Chamber Music",
+    "Sonata",
+    "Symphony",
+    "Booty Bass",
+    "Primus",
+    "Porn Groove",
+    "Satire",
+    "Slow Jam",
+    "Club",
+    "Tango",
+    "Samba",
+    "Folklore",
+    "Ballad",
+    "Power Ballad",
+    "Rhythmic Soul",
+    "Freestyle",
+    "Duet",
+    "Punk Rock",
+    "Drum Solo",
+    "A Cappella",
+    "Euro-House",
+    "Dance Hall",
+    "Goa",
+    "Drum & Bass",
+    "Club-House",
+    "Hardcore Techno",
+    "Terror",
+    "Indie",
+    "BritPop",
+    "Negerpunk",
+    "Polsk Punk",
+    "Beat",
+    "Christian Gangsta Rap",
+    "Heavy Metal",
+    "Black Metal",
+    "Crossover",
+    "Contemporary Christian",
+    "Christian Rock",
+    "Merengue",
+    "Salsa",
+    "Thrash Metal",
+    "Anime",
+    "JPop",
+    "Synthpop",
+    "Abstract",
+    "Art Rock",
+    "Baroque",
+    "Bhangra",
+    "Big Beat",
+    "Breakbeat",
+    "Chillout",
+    "Downtempo",
+    "Dub",
+    "EBM",
+    "Eclectic",
+    "Electro",
+    "Electroclash",
+    "Emo",
+    "Experimental",
+    "Garage",
+    "Global",
+    "IDM",
+    "Illbient",
+    "Industro-Goth",
+    "Jam Band",
+    "Krautrock",
+    "Leftfield",
+    "Lounge",
+    "Math Rock",
+    "New Romantic",
+    "Nu-Breakz",
+    "Post-Punk",
+    "Post-Rock",
+    "Psytrance",
+    "Shoegaze",
+    "Space Rock",
+    "Trop Rock",
+    "World Music",
+    "Neoclassical",
+    "Audiobook",
+    "Audio Theatre",
+    "Neue Deutsche Welle",
+    "Podcast",
+    "Indie Rock",
+    "G-Funk",
+    "Dubstep",
+    "Garage Rock",
+    "Psybient",
+];
+
+/// Expand ID3v1-style genre references in a TCON value.
+///
+/// - "(13)" -> "Pop", bare "13" -> "Pop" (ID3v2.4 style)
+/// - "(13)Alternative" -> "Pop Alternative" (refinement appended; dropped if it repeats)
+/// - "(13)(17)" -> "Pop Rock", "(RX)" -> "Remix", "(CR)" -> "Cover"
+/// - "((text" is an escaped literal "(text"; unknown codes are left as-is
+pub(crate) fn expand_genre(s: &str) -> String {
+    let s = s.trim();
+
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        return s
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| ID3V1_GENRES.get(n))
+            .map(|g| g.to_string())
+            .unwrap_or_else(|| s.to_string());
+    }
+
+    let mut parts: Vec<&str> = Vec::new();
+    let mut rest = s;
+
+    while let Some(after) = rest.strip_prefix('(') {
+        if after.starts_with('(') {
+            rest = after;
+            break;
+        }
+        let Some(close) = after.find(')') else {
+            break;
+        };
+        let name = match &after[..close] {
+            "RX" => Some("Remix"),
+            "CR" => Some("Cover"),
+            code => code
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| ID3V1_GENRES.get(n).copied()),
+        };
+        let Some(name) = name else {
+            break;
+        };
+        parts.push(name);
+        rest = &after[close + 1..];
+    }
+
+    let refinement = rest.trim();
+    if !refinement.is_empty() && !parts.iter().any(|p| p.eq_ignore_ascii_case(refinement)) {
+        parts.push(refinement);
+    }
+
+    if parts.is_empty() {
+        return s.to_string();
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_genre;
+
+    #[test]
+    fn expands_parenthesized_codes() {
+        assert_eq!(expand_genre("(13)"), "Pop");
+        assert_eq!(expand_genre("(17)"), "Rock");
+        assert_eq!(expand_genre("(13)(17)"), "Pop Rock");
+    }
+
+    #[test]
+    fn expands_bare_codes() {
+        assert_eq!(expand_genre("13"), "Pop");
+        assert_eq!(expand_genre(" 17 "), "Rock");
+    }
+
+    #[test]
+    fn appends_refinement_unless_repeated() {
+        assert_eq!(expand_genre("(13)Alternative"), "Pop Alternative");
+        assert_eq!(expand_genre("(17)Rock"), "Rock");
+        assert_eq!(expand_genre("(17)rock"), "Rock");
+    }
+
+    #[test]
+    fn expands_remix_and_cover() {
+        assert_eq!(expand_genre("(RX)"), "Remix");
+        assert_eq!(expand_genre("(CR)"), "Cover");
+    }
+
+    #[test]
+    fn leaves_unknown_codes_as_is() {
+        assert_eq!(expand_genre("(999)"), "(999)");
+        assert_eq!(expand_genre("999"), "999");
+    }
+
+    #[test]
+    fn passes_plain_text_through() {
+        assert_eq!(expand_genre("Shoegaze"), "Shoegaze");
+        assert_eq!(expand_genre("Drum & Bass"), "Drum & Bass");
+        assert_eq!(expand_genre(""), "");
+    }
+
+    #[test]
+    fn unescapes_double_paren() {
+        assert_eq!(expand_genre("((Live)"), "(Live)");
+    }
+}
@@ -0,0 +1,223 @@
+//! core/tags/validate.rs
+//! MP3 stream sanity check (run before tag writes when enabled).
+//!
+//! - Reads the raw file: skips a leading ID3v2 tag, then looks for the first frame sync
+//!   that is followed by a second, matching frame header (rules out stray 0xFF bytes).
+//! - The first `CHECKED_FRAMES` frames must agree on MPEG version, sample rate and
+//!   channel mode; the rest of the stream is only walked to count frames.
+//! - A frame that runs past the end of the file = truncated.
+//! - MPEG audio Layer III only (what `.mp3` means in practice).
+
+use std::path::Path;
+
+/// Frames whose headers are checked for consistency after the first sync.
+const CHECKED_FRAMES: u32 = 10;
+
+/// Bit rates (kbps) by header index: MPEG-1 Layer III, then MPEG-2/2.5 Layer III.
+const BITRATES_V1: [u32; 15] = [
+    0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+];
+const BITRATES_V2: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+/// MPEG-1 sample rates by header index (MPEG-2 halves them, MPEG-2.5 quarters them).
+const SAMPLE_RATES_V1: [u32; 3] = [44_100, 48_000, 32_000];
+
+/// Constant or variable bit rate, from the Xing/Info/VBRI header or the frames themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VbrMode {
+    Cbr,
+    Vbr,
+}
+
+/// What `validate_mp3` found in a healthy file (shown in the save status).
+#[derive(Debug, Clone, Copy)]
+pub struct Mp3Stats {
+    pub frame_count: u32,
+    pub bitrate_mode: VbrMode,
+    /// Byte offset of the first audio frame (i.e. the ID3v2 tag size, plus any junk).
+    pub first_frame_offset: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MpegVersion {
+    V1,
+    V2,
+    V25,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FrameHeader {
+    version: MpegVersion,
+    bitrate_kbps: u32,
+    sample_rate: u32,
+    mono: bool,
+    crc: bool,
+    len: usize,
+}
+
+impl FrameHeader {
+    /// Two frames belong to the same stream.
+    fn matches(&self, other: &FrameHeader) -> bool {
+        self.version == other.version
+            && self.sample_rate == other.sample_rate
+            && self.mono == other.mono
+    }
+}
+
+/// Check that `path` holds a sane MPEG Layer III stream.
+///
+/// Returns `Err` with a human-readable reason if no frame sync is found, the first
+/// frames disagree with each other, or the last frame is cut off.
+pub fn validate_mp3(path: &Path) -> Result<Mp3Stats, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let start = id3v2_len(&bytes);
+    let first = find_first_frame(&bytes, start)
+        .ok_or_else(|| "no MPEG audio frame found (not an MP3, or badly corrupted)".to_string())?;
+    let first_header =
+        parse_header(&bytes[first..]).ok_or_else(|| "invalid first frame".to_string())?;
+
+    let mut pos = first;
+    let mut frame_count: u32 = 0;
+    let mut bitrates_differ = false;
+
+    while pos + 4 <= bytes.len() {
+        let Some(header) = parse_header(&bytes[pos..]) else {
+            if frame_count < CHECKED_FRAMES {
+                return Err(format!(
+                    "corrupt frame header at byte {pos} (frame {})",
+                    frame_count + 1
+                ));
+            }
+            // Past the checked frames: trailing tags (ID3v1, APE) or junk end the stream.
+            break;
+        };
+
+        if frame_count < CHECKED_FRAMES {
+            if !header.matches(&first_header) {
+                return Err(format!(
+                    "frame {} at byte {pos} doesn't match the stream (sample rate / channels changed)",
+                    frame_count + 1
+                ));
+            }
+            bitrates_differ |= header.bitrate_kbps != first_header.bitrate_kbps;
+        }
+
+        if pos + header.len > bytes.len() {
+            return Err(format!(
+                "file looks truncated (frame {} at byte {pos} is cut off)",
+                frame_count + 1
+            ));
+        }
+
+        frame_count += 1;
+        pos += header.len;
+    }
+
+    let bitrate_mode = vbr_header(&bytes[first..], &first_header).unwrap_or(if bitrates_differ {
+        VbrMode::Vbr
+    } else {
+        VbrMode::Cbr
+    });
+
+    Ok(Mp3Stats {
+        frame_count,
+        bitrate_mode,
+        first_frame_offset: first as u64,
+    })
+}
+
+/// Size of a leading ID3v2 tag (header + body + optional footer), else 0.
+fn id3v2_len(bytes: &[u8]) -> usize {
+    if bytes.len() < 10 || &bytes[..3] != b"ID3" {
+        return 0;
+    }
+    // Sync-safe: 7 bits per byte.
+    let size = bytes[6..10]
+        .iter()
+        .fold(0usize, |acc, &b| (acc << 7) | usize::from(b & 0x7F));
+    let footer = if bytes[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + size + footer
+}
+
+/// First offset at or after `from` with a valid header whose successor is valid and
+/// matching too (a lone 0xFF 0xE? pair inside junk isn't enough).
+fn find_first_frame(bytes: &[u8], from: usize) -> Option<usize> {
+    (from..bytes.len().saturating_sub(4)).find(|&i| {
+        let Some(header) = parse_header(&bytes[i..]) else {
+            return false;
+        };
+        let next = i + header.len;
+        // A single-frame file has no successor to compare against.
+        next + 4 > bytes.len() || parse_header(&bytes[next..]).is_some_and(|h| h.matches(&header))
+    })
+}
+
+/// Decode a Layer III frame header (`None` if the 4 bytes aren't one).
+fn parse_header(bytes: &[u8]) -> Option<FrameHeader> {
+    let h: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+
+    // 11 sync bits.
+    if h[0] != 0xFF || h[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+    let version = match (h[1] >> 3) & 0b11 {
+        0b00 => MpegVersion::V25,
+        0b10 => MpegVersion::V2,
+        0b11 => MpegVersion::V1,
+        _ => return None, // reserved
+    };
+    // Layer bits 01 = Layer III.
+    if (h[1] >> 1) & 0b11 != 0b01 {
+        return None;
+    }
+    let crc = h[1] & 0x01 == 0;
+
+    // Index 0 = free format (no computable length), 15 = invalid.
+    let bitrate_idx = usize::from(h[2] >> 4);
+    if bitrate_idx == 0 || bitrate_idx == 15 {
+        return None;
+    }
+    let rate_idx = usize::from((h[2] >> 2) & 0b11);
+    if rate_idx == 3 {
+        return None;
+    }
+    let padding = usize::from((h[2] >> 1) & 0x01);
+    let mono = h[3] >> 6 == 0b11;
+
+    // Frame bytes = factor * bit rate / sample rate (+ padding byte).
+    let (bitrate_kbps, sample_rate, factor) = match version {
+        MpegVersion::V1 => (BITRATES_V1[bitrate_idx], SAMPLE_RATES_V1[rate_idx], 144),
+        MpegVersion::V2 => (BITRATES_V2[bitrate_idx], SAMPLE_RATES_V1[rate_idx] / 2, 72),
+        MpegVersion::V25 => (BITRATES_V2[bitrate_idx], SAMPLE_RATES_V1[rate_idx] / 4, 72),
+    };
+    let len = factor * bitrate_kbps as usize * 1000 / sample_rate as usize + padding;
+
+    Some(FrameHeader {
+        version,
+        bitrate_kbps,
+        sample_rate,
+        mono,
+        crc,
+        len,
+    })
+}
+
+/// Bit rate mode declared by a Xing/Info or VBRI header in the first frame, if any.
+fn vbr_header(frame: &[u8], header: &FrameHeader) -> Option<VbrMode> {
+    // Xing/Info sits right after the side info, whose size depends on version + channels.
+    let side_info = match (header.version, header.mono) {
+        (MpegVersion::V1, false) => 32,
+        (MpegVersion::V1, true) => 17,
+        (_, false) => 17,
+        (_, true) => 9,
+    };
+    let xing_at = 4 + if header.crc { 2 } else { 0 } + side_info;
+
+    match frame.get(xing_at..xing_at + 4)? {
+        b"Xing" => Some(VbrMode::Vbr),
+        b"Info" => Some(VbrMode::Cbr),
+        _ if frame.get(36..40) == Some(b"VBRI") => Some(VbrMode::Vbr),
+        _ => None,
+    }
+}
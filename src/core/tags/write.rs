@@ -1,10 +1,248 @@
 //! core/tags/write.rs
 //! Write selected ID3 tags back to an MP3, based on a `TrackRow`.
+//! (`write_track_row` hands `.flac` files to `flac.rs` and `.ogg` files to `ogg.rs`;
+//! all of them share `fields.rs`.)
+//!
+//! Version policy (`TagVersion`):
+//! - V24: write ID3v2.4, falling back to v2.3 if that fails.
+//! - V23: convert v2.4-only frames first (TDRC -> TYER/TDAT/TIME, others -> TXXX:<id>),
+//!   since many car stereos only read v2.3. Reads map those TXXX frames back.
+//!
+//! Backups (`WriteOptions::backup`): the file is copied to `<name>.bak` (e.g. `song.mp3.bak`)
+//! right before each write. Scans only pick up `.mp3`/`.ogg`/`.flac`, so backups never show up as tracks.
+//!
+//! Atomicity: tags are written into a temp copy in the same folder, which is then renamed
+//! over the original. A crash or failed write leaves the original untouched.
 
-use id3::frame::{Comment, Lyrics};
+use id3::frame::{Comment, Content, ExtendedText, Frame, Lyrics, Popularimeter};
 use id3::{Tag, TagLike, Version};
+use std::path::{Path, PathBuf};
 
-use super::super::types::TrackRow;
+use super::super::library::{is_flac, is_ogg};
+use super::super::types::{CommentFrame, TrackRow};
+use super::fields::desired_text_fields;
+use super::flac::{strip_flac_comments, write_flac_key, write_flac_row};
+use super::ogg::{strip_ogg_comments, write_ogg_key, write_ogg_row};
+use super::util::{
+    DATE_ADDED_DESC, FAVORITE_DESC, MB_RECORDING_ID_DESC, MB_RELEASE_ID_DESC, TRIM_DB_DESC,
+    V24_ONLY_TEXT, is_internal_user_text,
+};
+
+/// ID3v2 version used for every tag write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TagVersion {
+    V23,
+    #[default]
+    V24,
+}
+
+impl TagVersion {
+    /// Stable string used in `config.toml`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            TagVersion::V23 => "2.3",
+            TagVersion::V24 => "2.4",
+        }
+    }
+
+    pub fn from_config_key(s: &str) -> Option<Self> {
+        match s {
+            "2.3" => Some(TagVersion::V23),
+            "2.4" => Some(TagVersion::V24),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TagVersion::V23 => "ID3v2.3",
+            TagVersion::V24 => "ID3v2.4",
+        }
+    }
+}
+
+/// How tag writes behave (`WriteOptions::default()` = all fields, no backup, v2.4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Also write the "extended" fields (see `write_track_row`).
+    pub write_extended: bool,
+    /// Copy the file to `<name>.bak` before touching it.
+    pub backup: bool,
+    pub tag_version: TagVersion,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            write_extended: true,
+            backup: false,
+            tag_version: TagVersion::default(),
+        }
+    }
+}
+
+/// Fail early (with a readable message) if `path` is read-only,
+/// instead of surfacing a cryptic io::Error from the write itself.
+pub fn check_writable(path: &Path) -> Result<(), String> {
+    let md =
+        std::fs::metadata(path).map_err(|e| format!("Can't access {}: {e}", path.display()))?;
+    if md.permissions().readonly() {
+        return Err(format!("File is read-only: {}", path.display()));
+    }
+    Ok(())
+}
+
+/// `song.mp3` => `song.mp3.bak`.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(".bak");
+    PathBuf::from(s)
+}
+
+/// Copy `path` to `<name>.bak` if `options.backup` is set.
+pub(super) fn backup_if_requested(path: &Path, options: &WriteOptions) -> Result<(), String> {
+    if options.backup {
+        let bak = backup_path(path);
+        std::fs::copy(path, &bak)
+            .map_err(|e| format!("Backup to {} failed: {e}", bak.display()))?;
+    }
+    Ok(())
+}
+
+/// Write `tag` to `path` per `options` (backup first, then the version policy above).
+pub(super) fn write_tag(tag: &mut Tag, path: &Path, options: &WriteOptions) -> Result<(), String> {
+    backup_if_requested(path, options)?;
+
+    let id3_write = |tag: &Tag, version: Version| {
+        write_atomic(path, |tmp| {
+            tag.write_to_path(tmp, version).map_err(|e| e.to_string())
+        })
+    };
+
+    match options.tag_version {
+        TagVersion::V23 => {
+            downgrade_to_v23(tag);
+            id3_write(tag, Version::Id3v23).map_err(|e| format!("write_to_path failed: v2.3={e}"))
+        }
+        TagVersion::V24 => {
+            upgrade_from_v23(tag);
+            if let Err(e) = id3_write(tag, Version::Id3v24) {
+                downgrade_to_v23(tag);
+                id3_write(tag, Version::Id3v23)
+                    .map_err(|e2| format!("write_to_path failed: v2.4={e} ; v2.3={e2}"))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Run `write` on a temp copy of `path` (same folder, so the rename stays on one
+/// filesystem), then rename it over the original with the original's permissions.
+/// On any error the temp file is deleted on drop and `path` is untouched.
+pub(super) fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&Path) -> Result<(), String>,
+) -> Result<(), String> {
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let perms = std::fs::metadata(path)
+        .map_err(|e| format!("metadata failed: {e}"))?
+        .permissions();
+
+    let tmp = tempfile::NamedTempFile::new_in(dir).map_err(|e| format!("temp file failed: {e}"))?;
+    std::fs::copy(path, tmp.path()).map_err(|e| format!("temp copy failed: {e}"))?;
+    write(tmp.path())?;
+    std::fs::set_permissions(tmp.path(), perms)
+        .map_err(|e| format!("set_permissions failed: {e}"))?;
+
+    let tmp = tmp.into_temp_path();
+    std::fs::rename(&tmp, path).map_err(|e| format!("rename failed: {e}"))?;
+    // Already renamed away; nothing left to clean up.
+    let _ = tmp.keep();
+    Ok(())
+}
+
+/// Rewrite v2.4-only frames into their v2.3 equivalents (in place).
+fn downgrade_to_v23(tag: &mut Tag) {
+    // TDRC "YYYY-MM-DDTHH:MM" -> TYER "YYYY" + TDAT "DDMM" + TIME "HHMM" (v2.3 spec order).
+    if let Some(date) = tag
+        .get("TDRC")
+        .and_then(|f| f.content().text())
+        .map(str::to_string)
+    {
+        remove_all(tag, "TDRC");
+
+        let digits = |s: Option<&str>, n: usize| {
+            s.filter(|s| s.len() == n && s.bytes().all(|b| b.is_ascii_digit()))
+                .map(str::to_string)
+        };
+        let (day_part, time_part) = date.split_once('T').unwrap_or((&date, ""));
+        let mut ymd = day_part.trim().split('-');
+        let mut hm = time_part.split(':');
+
+        match digits(ymd.next(), 4) {
+            Some(year) => {
+                if tag.get("TYER").is_none() {
+                    tag.set_text("TYER", year);
+                }
+                if let (Some(m), Some(d)) = (digits(ymd.next(), 2), digits(ymd.next(), 2)) {
+                    remove_all(tag, "TDAT");
+                    tag.set_text("TDAT", format!("{d}{m}"));
+                    if let (Some(h), Some(min)) = (digits(hm.next(), 2), digits(hm.next(), 2)) {
+                        remove_all(tag, "TIME");
+                        tag.set_text("TIME", format!("{h}{min}"));
+                    }
+                }
+            }
+            // Not a date we can split: keep the text rather than drop it.
+            None => set_user_text(tag, "TDRC", &date),
+        }
+    }
+
+    for &id in V24_ONLY_TEXT {
+        if let Some(value) = tag
+            .get(id)
+            .and_then(|f| f.content().text())
+            .map(str::to_string)
+        {
+            remove_all(tag, id);
+            set_user_text(tag, id, &value);
+        }
+    }
+}
+
+/// Undo `downgrade_to_v23` for the TXXX stand-ins (native frames win if both exist).
+fn upgrade_from_v23(tag: &mut Tag) {
+    for &id in V24_ONLY_TEXT.iter().chain(&["TDRC"]) {
+        let stand_in = tag
+            .extended_texts()
+            .find(|t| t.description == id)
+            .map(|t| t.value.clone());
+        if let Some(value) = stand_in {
+            tag.remove_extended_text(Some(id), None);
+            if tag.get(id).is_none() {
+                tag.set_text(id, value);
+            }
+        }
+    }
+
+    // TDAT/TIME aren't v2.4 frames; once TDRC exists it carries the full date.
+    if tag.get("TDRC").is_some() {
+        remove_all(tag, "TDAT");
+        remove_all(tag, "TIME");
+    }
+}
+
+/// Replace any TXXX frame with this description by a single one holding `value`.
+fn set_user_text(tag: &mut Tag, description: &str, value: &str) {
+    tag.remove_extended_text(Some(description), None);
+    tag.add_frame(ExtendedText {
+        description: description.to_string(),
+        value: value.to_string(),
+    });
+}
 
 /// Helper: remove all frames with a given id.
 /// (TagLike::remove returns Vec<Frame>; discard it.)
@@ -40,19 +278,34 @@ fn set_slash_pair(tag: &mut Tag, id: &str, n: Option<u32>, total: Option<u32>) {
     }
 }
 
-/// Helper: replace with a single COMM (eng, empty desc) or remove all COMM if empty/None
-fn set_comment_opt(tag: &mut Tag, v: &Option<String>) {
-    match v.as_deref().map(str::trim) {
-        Some(s) if !s.is_empty() => {
-            remove_all(tag, "COMM");
-            // id3 crate supports adding Comment directly (your code already assumes this).
-            tag.add_frame(Comment {
+/// Helper: rewrite every COMM frame from `comments`, with `primary` as the first one's text.
+/// - `primary` None/empty => the first frame is dropped (the rest are kept)
+/// - no frames yet => `primary` becomes a new (eng, empty desc) frame
+fn set_comments(tag: &mut Tag, primary: &Option<String>, comments: &[CommentFrame]) {
+    let mut frames: Vec<CommentFrame> = comments.to_vec();
+
+    match primary.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(s) => match frames.first_mut() {
+            Some(first) => first.text = s.to_string(),
+            None => frames.push(CommentFrame {
                 lang: "eng".to_string(),
-                description: "".to_string(),
+                description: String::new(),
                 text: s.to_string(),
-            });
+            }),
+        },
+        None if !frames.is_empty() => {
+            frames.remove(0);
         }
-        _ => remove_all(tag, "COMM"),
+        None => {}
+    }
+
+    remove_all(tag, "COMM");
+    for c in frames {
+        tag.add_frame(Comment {
+            lang: c.lang,
+            description: c.description,
+            text: c.text,
+        });
     }
 }
 
@@ -73,26 +326,41 @@ fn set_lyrics_opt(tag: &mut Tag, v: &Option<String>) {
 
 /// Write tags for a single file, based on the desired contents of `row`.
 /// - Always writes "standard" fields (visible by default in UI).
-/// - Writes "extended" fields only if `write_extended == true`.
+/// - Writes "extended" fields only if `options.write_extended == true`.
 ///
 /// Semantics:
 /// - `None` (or empty/whitespace string) => remove that frame from the file.
-pub fn write_track_row(row: &TrackRow, write_extended: bool) -> Result<(), String> {
+/// - `.flac` and `.ogg` files get Vorbis comments; everything else gets ID3.
+/// - ID3 is written as `options.tag_version` (v2.4-only frames are converted for V23).
+pub fn write_track_row(row: &TrackRow, options: &WriteOptions) -> Result<(), String> {
+    check_writable(&row.path)?;
+
+    if is_flac(&row.path) {
+        return write_flac_row(row, options);
+    }
+    if is_ogg(&row.path) {
+        return write_ogg_row(row, options);
+    }
+
+    write_id3_row(row, options)
+}
+
+fn write_id3_row(row: &TrackRow, options: &WriteOptions) -> Result<(), String> {
     let path = &row.path;
 
     // Load existing tag if possible; otherwise start fresh.
     let mut tag = Tag::read_from_path(path).unwrap_or_else(|_| Tag::new());
+    // Edit native frames only; `write_tag` converts again for v2.3.
+    upgrade_from_v23(&mut tag);
+
+    // Plain text frames (standard always, extended per `write_extended`).
+    for (field, value) in desired_text_fields(row, options.write_extended) {
+        set_text_opt(&mut tag, field.id3_frame(), &value);
+    }
 
     // -------------------------
     // Standard (always written)
     // -------------------------
-    set_text_opt(&mut tag, "TIT2", &row.title); // title
-    set_text_opt(&mut tag, "TPE1", &row.artist); // artist
-    set_text_opt(&mut tag, "TALB", &row.album); // album
-    set_text_opt(&mut tag, "TPE2", &row.album_artist); // album artist
-    set_text_opt(&mut tag, "TCOM", &row.composer); // composer
-    set_text_opt(&mut tag, "TCON", &row.genre); // genre
-
     // Track/disc (use standard TRCK/TPOS formatting)
     set_slash_pair(&mut tag, "TRCK", row.track_no, row.track_total);
     set_slash_pair(&mut tag, "TPOS", row.disc_no, row.disc_total);
@@ -112,62 +380,184 @@ pub fn write_track_row(row: &TrackRow, write_extended: bool) -> Result<(), Strin
     }
 
     // These are "standard" in your UI (good call keeping them always writable).
-    set_text_opt(&mut tag, "TIT1", &row.grouping); // grouping
-    set_comment_opt(&mut tag, &row.comment); // comment
+    set_comments(&mut tag, &row.comment, &row.comments); // comment (+ other COMM frames)
     set_lyrics_opt(&mut tag, &row.lyrics); // lyrics
-    set_text_opt(&mut tag, "TEXT", &row.lyricist); // lyricist
 
     // -------------------------
     // Extended (toggleable)
     // -------------------------
-    if write_extended {
+    if options.write_extended {
         // Date string: use TDRC (v2.4-friendly), but also mirror to TYER if year is None
         // and the date begins with "YYYY".
         set_text_opt(&mut tag, "TDRC", &row.date);
+        // v2.3 date parts are rebuilt from TDRC by `write_tag` (stale ones would win on read).
+        remove_all(&mut tag, "TDAT");
+        remove_all(&mut tag, "TIME");
 
         // If user typed a date like "1999-05-14" and year wasn't explicitly set,
         // ensure year() stays consistent for older players.
-        if row.year.is_none() {
-            if let Some(d) = row.date.as_deref().map(str::trim) {
-                if d.len() >= 4 {
-                    if let Ok(y) = d[0..4].parse::<i32>() {
-                        tag.set_year(y);
-                        remove_all(&mut tag, "TYER");
-                        tag.set_text("TYER", y.to_string());
-                    }
-                }
+        if row.year.is_none()
+            && let Some(d) = row.date.as_deref().map(str::trim)
+            && d.len() >= 4
+            && let Ok(y) = d[0..4].parse::<i32>()
+        {
+            tag.set_year(y);
+            remove_all(&mut tag, "TYER");
+            tag.set_text("TYER", y.to_string());
+        }
+
+        // Custom TXXX frames: `row.user_text` is the full desired set
+        // (internal descriptions are handled elsewhere and skipped here).
+        let stale: Vec<String> = tag
+            .extended_texts()
+            .map(|t| t.description.clone())
+            .filter(|d| !is_internal_user_text(d))
+            .collect();
+        for description in stale {
+            tag.remove_extended_text(Some(&description), None);
+        }
+        for (description, value) in &row.user_text {
+            if !is_internal_user_text(description) && !value.trim().is_empty() {
+                set_user_text(&mut tag, description, value.trim());
             }
         }
 
-        set_text_opt(&mut tag, "TPE3", &row.conductor);
-        set_text_opt(&mut tag, "TPE4", &row.remixer);
-        set_text_opt(&mut tag, "TPUB", &row.publisher);
-        set_text_opt(&mut tag, "TIT3", &row.subtitle);
+        // Per-track trim lives in our own TXXX frame; no trim = no frame.
+        tag.remove_extended_text(Some(TRIM_DB_DESC), None);
+        if let Some(db) = row.volume_trim_db {
+            set_user_text(&mut tag, TRIM_DB_DESC, &format!("{db}"));
+        }
+    }
 
-        match row.bpm {
-            Some(b) => {
-                remove_all(&mut tag, "TBPM");
-                tag.set_text("TBPM", b.to_string());
-            }
-            None => remove_all(&mut tag, "TBPM"),
+    // MusicBrainz ids aren't edited by hand (lookups fill them), so they're always written.
+    for (description, value) in [
+        (MB_RECORDING_ID_DESC, &row.mb_recording_id),
+        (MB_RELEASE_ID_DESC, &row.mb_release_id),
+    ] {
+        tag.remove_extended_text(Some(description), None);
+        if let Some(v) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            set_user_text(&mut tag, description, v);
         }
+    }
+
+    write_tag(&mut tag, path, options)
+}
 
-        set_text_opt(&mut tag, "TKEY", &row.key);
-        set_text_opt(&mut tag, "TMOO", &row.mood);
-        set_text_opt(&mut tag, "TLAN", &row.language);
-        set_text_opt(&mut tag, "TSRC", &row.isrc);
-        set_text_opt(&mut tag, "TSSE", &row.encoder_settings);
-        set_text_opt(&mut tag, "TENC", &row.encoded_by);
-        set_text_opt(&mut tag, "TCOP", &row.copyright);
+/// Set the star rating (raw POPM byte, 0 = unrated) without touching other frames.
+/// - Every existing POPM frame gets the new rating (reads use the first one),
+///   keeping each frame's play counter.
+/// - Adds a POPM frame for `email` if none exists for it yet.
+/// - A user edit: honours `options` (backup, tag version) like `write_track_row`.
+pub fn write_rating(
+    path: &Path,
+    email: &str,
+    rating: u8,
+    options: &WriteOptions,
+) -> Result<(), String> {
+    check_writable(path)?;
+    if is_ogg(path) || is_flac(path) {
+        return Err("Ratings are only stored in MP3 (ID3) tags".to_string());
     }
 
-    // Write back to file:
-    // - Prefer v2.4 (modern frames like TDRC).
-    // - If that fails for some reason, fall back to v2.3.
-    if let Err(e) = tag.write_to_path(path, Version::Id3v24) {
-        tag.write_to_path(path, Version::Id3v23)
-            .map_err(|e2| format!("write_to_path failed: v2.4={e} ; v2.3={e2}"))?;
+    let mut tag = Tag::read_from_path(path).unwrap_or_else(|_| Tag::new());
+
+    let mut popms: Vec<Popularimeter> = tag
+        .frames()
+        .filter_map(|f| match f.content() {
+            Content::Popularimeter(p) if f.id() == "POPM" => Some(p.clone()),
+            _ => None,
+        })
+        .collect();
+    if !popms.iter().any(|p| p.user == email) {
+        popms.push(Popularimeter {
+            user: email.to_string(),
+            rating: 0,
+            counter: 0,
+        });
     }
 
-    Ok(())
+    remove_all(&mut tag, "POPM");
+    for mut p in popms {
+        p.rating = rating;
+        tag.add_frame(Frame::with_content("POPM", Content::Popularimeter(p)));
+    }
+
+    write_tag(&mut tag, path, options)
+}
+
+/// Record when Sonora first saw the file (`TXXX:SONORA_DATE_ADDED`) without touching other frames.
+/// - Bookkeeping, not an edit: no backup, and the file keeps its tag version.
+pub fn write_date_added(path: &Path, date_added: &str) -> Result<(), String> {
+    check_writable(path)?;
+    if is_ogg(path) {
+        return write_ogg_key(path, DATE_ADDED_DESC, Some(date_added));
+    }
+    if is_flac(path) {
+        return write_flac_key(path, DATE_ADDED_DESC, Some(date_added));
+    }
+
+    let mut tag = Tag::read_from_path(path).unwrap_or_else(|_| Tag::new());
+    set_user_text(&mut tag, DATE_ADDED_DESC, date_added);
+
+    let version = match tag.version() {
+        Version::Id3v22 => Version::Id3v23,
+        v => v,
+    };
+    write_atomic(path, |tmp| {
+        tag.write_to_path(tmp, version)
+            .map_err(|e| format!("write_to_path failed: {e}"))
+    })
+}
+
+/// Mark or unmark the file as a favorite (`TXXX:SONORA_FAVORITE`) without touching other frames.
+/// - Unmarking removes the frame rather than writing "0".
+/// - A user edit: honours `options` (backup, ID3 tag version) like `write_track_row`.
+pub fn write_favorite(path: &Path, favorite: bool, options: &WriteOptions) -> Result<(), String> {
+    check_writable(path)?;
+    if is_ogg(path) {
+        backup_if_requested(path, options)?;
+        return write_ogg_key(path, FAVORITE_DESC, favorite.then_some("1"));
+    }
+    if is_flac(path) {
+        backup_if_requested(path, options)?;
+        return write_flac_key(path, FAVORITE_DESC, favorite.then_some("1"));
+    }
+
+    let mut tag = Tag::read_from_path(path).unwrap_or_else(|_| Tag::new());
+    tag.remove_extended_text(Some(FAVORITE_DESC), None);
+    if favorite {
+        set_user_text(&mut tag, FAVORITE_DESC, "1");
+    }
+
+    write_tag(&mut tag, path, options)
+}
+
+/// Remove every tag frame from a file (start-fresh batch operation).
+/// - Writes an empty ID3v2 tag (in `options.tag_version`) in place of whatever was there.
+/// - Also drops a trailing ID3v1 tag, since reads fall back to it.
+/// - Both steps run on the temp copy, so a crash leaves the original untouched.
+/// - `.ogg`: clears the Vorbis comments instead; `.flac`: its comments and pictures.
+pub fn strip_tags(path: &Path, options: &WriteOptions) -> Result<(), String> {
+    check_writable(path)?;
+    backup_if_requested(path, options)?;
+
+    if is_ogg(path) {
+        return strip_ogg_comments(path);
+    }
+    if is_flac(path) {
+        return strip_flac_comments(path);
+    }
+
+    let version = match options.tag_version {
+        TagVersion::V23 => Version::Id3v23,
+        TagVersion::V24 => Version::Id3v24,
+    };
+    write_atomic(path, |tmp| {
+        Tag::new()
+            .write_to_path(tmp, version)
+            .map_err(|e| format!("write_to_path failed: {e}"))?;
+        id3::v1::Tag::remove_from_path(tmp)
+            .map(|_| ())
+            .map_err(|e| format!("ID3v1 removal failed: {e}"))
+    })
 }
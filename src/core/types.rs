@@ -24,31 +24,47 @@
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
 // Stable identifier for a track.
 //
 // Why have this?
 // - `Vec` indices are not stable (rescans, sorts, inserts, deletes)
-// - once SQLite exists, this becomes the DB primary key
+// - it is the `core::db` library cache primary key (`tracks.id`)
 //
-// MVP note:
-// - while you don't have a DB yet, `TrackRow::id` may be `None`
-// - once SQLite lands, every row should have `Some(id)`
+// Notes:
+// - rows loaded through the cache always have `Some(id)`, stable across rescans
+// - if the cache can't be opened, the GUI falls back to temporary per-scan ids
 //
 // We choose `i64` because it matches SQLite `INTEGER PRIMARY KEY` nicely.
 pub type TrackId = i64;
 
+// Tag container a `TrackRow` was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagFormat {
+    Id3v2_2,
+    Id3v2_3,
+    Id3v2_4,
+    Id3v1,
+    // OGG Vorbis comment header.
+    VorbisComment,
+    // FLAC Vorbis comment block.
+    FlacVorbis,
+    None,
+}
+
 // Minimal "row" of track metadata for display/edit.
 // One `TrackRow` = one audio file + the metadata we know about it.
 //
 // This struct is intentionally **format-agnostic**: it describes *music metadata*,
 // not "ID3 tags". The tags layer is responsible for mapping between containers
 // (MP3/ID3 today) and this record.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackRow {
-    // Stable identity (DB primary key once SQLite is added).
+    // Stable identity (library cache primary key).
     //
-    // - `None` in the pre-DB MVP can be acceptable.
-    // - Once SQLite is introduced, treat `None` as a bug.
+    // - Tag reading always returns `None`; the cache / scan layer assigns it.
+    // - After a scan, `None` is a bug.
     pub id: Option<TrackId>,
 
     // Canonical file location for this track.
@@ -103,13 +119,21 @@ pub struct TrackRow {
     pub grouping: Option<String>,
 
     // A short comment (ID3: `COMM`).
-    // If multiple comment frames exist, keep the first one.
+    // If multiple comment frames exist, this is the first one (`comments[0]`).
     pub comment: Option<String>,
 
+    // Every comment frame, in tag order (language + description + text).
+    // `comment` edits the first entry; writes keep the rest untouched.
+    pub comments: Vec<CommentFrame>,
+
     // Unsynced lyrics (ID3: `USLT`).
     // If multiple lyrics frames exist, keep the first one.
     pub lyrics: Option<String>,
 
+    // Synchronized (timestamped) lyrics (ID3: `SYLT`), sorted by time.
+    // First millisecond-timed frame only; MPEG-frame timestamps are skipped.
+    pub synced_lyrics: Vec<SyncedLyric>,
+
     // Lyricist / text writer (ID3: `TEXT`)
     pub lyricist: Option<String>,
 
@@ -169,11 +193,28 @@ pub struct TrackRow {
     pub album_artist_sort: Option<String>,
 
     // Library/stats-ish fields (not always present, but useful)
-    // Duration in milliseconds (ID3: `TLEN`).
+    // Duration in milliseconds, probed from the audio stream.
     //
-    // Many files/libraries do not store this reliably; treat as optional hint.
+    // Falls back to ID3 `TLEN` (often unreliable) when the probe can't tell.
     pub duration_ms: Option<u32>,
 
+    // Sample rate in Hz (stream probe).
+    pub sample_rate: Option<u32>,
+
+    // Average bit rate in kbps (stream probe: file size / duration).
+    pub bit_rate: Option<u32>,
+
+    // Audio channel count (stream probe).
+    pub channels: Option<u16>,
+
+    // Which tag the metadata came from ("ID3v2.3", "ID3v1.1", ...); `None` if untagged.
+    pub tag_version: Option<String>,
+
+    // Tag format that was actually read (inspector "Tags:" line).
+    //
+    // `Some(TagFormat::None)` = the file was read but had no tags.
+    pub tag_format: Option<TagFormat>,
+
     // Rating (0–255 in `POPM`; stored as raw byte).
     pub rating: Option<u8>,
 
@@ -183,6 +224,27 @@ pub struct TrackRow {
     // Compilation flag (commonly `TCMP` or `TXXX:COMPILATION`).
     pub compilation: Option<bool>,
 
+    // ReplayGain track gain in dB (`TXXX:replaygain_track_gain`, e.g. "-6.54 dB").
+    pub replaygain_track_gain: Option<f32>,
+
+    // ReplayGain album gain in dB (`TXXX:replaygain_album_gain`).
+    pub replaygain_album_gain: Option<f32>,
+
+    // Per-track pre-amp trim in dB, applied on top of ReplayGain (`TXXX:SONORA_TRIM_DB`).
+    pub volume_trim_db: Option<f32>,
+
+    // When Sonora first saw the file, RFC 3339 local time (`TXXX:SONORA_DATE_ADDED`).
+    pub date_added: Option<String>,
+
+    // Loved track (`TXXX:SONORA_FAVORITE` = "1").
+    pub is_favorite: bool,
+
+    // MusicBrainz recording id (`TXXX:MusicBrainz Recording Id`, Picard's naming).
+    pub mb_recording_id: Option<String>,
+
+    // MusicBrainz release id (`TXXX:MusicBrainz Album Id`).
+    pub mb_release_id: Option<String>,
+
     // Escape hatches: preserve unknown/extra tags without redesigning the struct
     // User-defined text frames (ID3: `TXXX`).
     // Key = description, Value = value.
@@ -196,3 +258,80 @@ pub struct TrackRow {
     // Key = frame id (e.g. "TOPE"), Value = best-effort text value.
     pub extra_text: BTreeMap<String, String>,
 }
+
+// One comment frame (ID3: `COMM`). Frames are keyed by (lang, description).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentFrame {
+    // ISO-639-2 language code, e.g. "eng".
+    pub lang: String,
+    pub description: String,
+    pub text: String,
+}
+
+// One timestamped line of synchronized lyrics (ID3: `SYLT`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedLyric {
+    // Start of the line, from the beginning of the track.
+    pub timestamp_ms: u32,
+    pub text: String,
+}
+
+// A file a scan could not read tags from (it still gets an "empty-ish" row).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanError {
+    pub path: PathBuf,
+    // Human-readable cause, e.g. the ID3 reader's error.
+    pub reason: String,
+}
+
+// Album grouping key: (album artist, album) with the UI's fallbacks applied.
+//
+// - Album artist falls back to the track artist, then "Unknown Artist"
+// - Album falls back to "Unknown Album"
+// - `album_artist_sort` (TSO2, else the matching TSOP, else the display name) comes
+//   first, so ordered maps list "The Beatles" under "B"
+// - `year` is `None` unless grouping by year is on (`for_track_by_year`); then two
+//   editions with the same artist + title become separate albums
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AlbumKey {
+    pub album_artist_sort: String,
+    pub album_artist: String,
+    pub album: String,
+    pub year: Option<i32>,
+}
+
+impl AlbumKey {
+    pub fn for_track(t: &TrackRow) -> Self {
+        let (album_artist, artist_sort) = match &t.album_artist {
+            Some(aa) => (aa.clone(), None),
+            None => (
+                t.artist
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Artist".to_string()),
+                t.artist_sort.clone(),
+            ),
+        };
+
+        AlbumKey {
+            album_artist_sort: t
+                .album_artist_sort
+                .clone()
+                .or(artist_sort)
+                .unwrap_or_else(|| album_artist.clone()),
+            album_artist,
+            album: t
+                .album
+                .clone()
+                .unwrap_or_else(|| "Unknown Album".to_string()),
+            year: None,
+        }
+    }
+
+    // Like `for_track`, plus the track's year when `by_year` is set.
+    pub fn for_track_by_year(t: &TrackRow, by_year: bool) -> Self {
+        AlbumKey {
+            year: if by_year { t.year } else { None },
+            ..Self::for_track(t)
+        }
+    }
+}
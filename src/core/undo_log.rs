@@ -0,0 +1,154 @@
+//! core/undo_log.rs
+//!
+//! Tag-edit undo history persisted to disk as JSON, so a crash mid-edit doesn't lose it.
+//!
+//! - One file per session in the data dir: `<data dir>/sonora/undo_<timestamp>.json`
+//! - Startup loads the newest file; saving removes the older ones
+//! - Steps whose files are gone are dropped on load (they can't be written back)
+//! - `schema_version` guards the format; an unknown version => empty history
+//! - Missing or unparseable file => empty history (like `history::load`)
+
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use super::types::TrackRow;
+
+const APP_DIR: &str = "sonora";
+const FILE_PREFIX: &str = "undo_";
+const FILE_EXT: &str = "json";
+
+/// Bump when the JSON layout changes (and migrate in `load`).
+const SCHEMA_VERSION: u32 = 1;
+
+/// Steps kept on disk (oldest dropped first).
+pub const MAX_STEPS: usize = 20;
+
+#[derive(Serialize)]
+struct UndoFileRef<'a> {
+    schema_version: u32,
+    steps: Vec<UndoStepRef<'a>>,
+}
+
+#[derive(Serialize)]
+struct UndoStepRef<'a> {
+    label: &'a str,
+    rows: &'a [TrackRow],
+}
+
+#[derive(Deserialize)]
+struct UndoFile {
+    schema_version: u32,
+    #[serde(default)]
+    steps: Vec<UndoStep>,
+}
+
+#[derive(Deserialize)]
+struct UndoStep {
+    label: String,
+    rows: Vec<TrackRow>,
+}
+
+fn dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join(APP_DIR))
+}
+
+/// Path for this session's file (`undo_<local time>.json`), if the platform has a data dir.
+pub fn session_path() -> Option<PathBuf> {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    dir().map(|d| d.join(format!("{FILE_PREFIX}{stamp}.{FILE_EXT}")))
+}
+
+/// Every `undo_*.json` in the data dir, oldest first (the timestamps sort by name).
+fn existing_files() -> Vec<PathBuf> {
+    let Some(dir) = dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == FILE_EXT)
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(FILE_PREFIX))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Load the newest saved undo history as (label, rows) steps, oldest first.
+pub fn load() -> Vec<(String, Vec<TrackRow>)> {
+    let Some(path) = existing_files().pop() else {
+        return Vec::new();
+    };
+    let Ok(s) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    let file: UndoFile = match serde_json::from_str(&s) {
+        Ok(file) => file,
+        Err(_e) => {
+            #[cfg(debug_assertions)]
+            eprintln!("[UNDO] parse failed for {}: {_e}", path.display());
+            return Vec::new();
+        }
+    };
+    if file.schema_version != SCHEMA_VERSION {
+        #[cfg(debug_assertions)]
+        eprintln!(
+            "[UNDO] unknown schema_version {} in {}",
+            file.schema_version,
+            path.display()
+        );
+        return Vec::new();
+    }
+
+    let mut steps: Vec<(String, Vec<TrackRow>)> = file
+        .steps
+        .into_iter()
+        .filter(|step| step.rows.iter().all(|r| r.path.exists()))
+        .map(|step| (step.label, step.rows))
+        .collect();
+    let excess = steps.len().saturating_sub(MAX_STEPS);
+    steps.drain(..excess);
+    steps
+}
+
+/// Write the undo history to `path` (newest `MAX_STEPS` steps), then remove older
+/// session files.
+pub fn save<'a>(
+    path: &Path,
+    steps: impl IntoIterator<Item = (&'a str, &'a [TrackRow])>,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+
+    let mut steps: Vec<UndoStepRef<'a>> = steps
+        .into_iter()
+        .map(|(label, rows)| UndoStepRef { label, rows })
+        .collect();
+    let excess = steps.len().saturating_sub(MAX_STEPS);
+    steps.drain(..excess);
+
+    let file = UndoFileRef {
+        schema_version: SCHEMA_VERSION,
+        steps,
+    };
+    let s = serde_json::to_string(&file).map_err(|e| format!("Serialize failed: {e}"))?;
+    std::fs::write(path, s).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    // Best-effort: earlier sessions' files are superseded by this one.
+    for old in existing_files() {
+        if old != path {
+            let _ = std::fs::remove_file(old);
+        }
+    }
+    Ok(())
+}
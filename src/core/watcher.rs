@@ -0,0 +1,43 @@
+//! core/watcher.rs
+//!
+//! Watch library roots for files appearing/disappearing (notify crate).
+//!
+//! - Reports raw paths from Create / Remove / Rename events; no filtering or
+//!   debouncing here (the GUI batches them and decides what to re-read).
+//! - Content edits (Modify::Data) are ignored: tag changes made by other tools
+//!   are picked up by "Refresh tags" or the next scan.
+//! - The watch lives as long as the returned watcher; drop it to stop.
+
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+
+/// Watch every root recursively, calling `on_change` (on notify's thread) per changed path.
+///
+/// Roots that can't be watched (missing, unreadable) are skipped.
+pub fn watch_roots(
+    roots: &[PathBuf],
+    mut on_change: impl FnMut(PathBuf) + Send + 'static,
+) -> Result<RecommendedWatcher, String> {
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let Ok(event) = res else { return };
+        if matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+        ) {
+            for path in event.paths {
+                on_change(path);
+            }
+        }
+    })
+    .map_err(|e| format!("Folder watch failed: {e}"))?;
+
+    for root in roots {
+        if let Err(_e) = watcher.watch(root, RecursiveMode::Recursive) {
+            #[cfg(debug_assertions)]
+            eprintln!("[WATCH] can't watch {}: {_e}", root.display());
+        }
+    }
+
+    Ok(watcher)
+}
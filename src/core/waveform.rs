@@ -0,0 +1,86 @@
+//! core/waveform.rs
+//! Amplitude overview of a whole file for the playback bar.
+//!
+//! - Decodes through the playback path (`open_source_at_ms`), so anything that plays
+//!   gets a waveform.
+//! - Only every `SAMPLE_STRIDE`th frame is measured (channels mixed down to mono);
+//!   plenty for a thumbnail and much cheaper than touching every sample.
+//! - The result is RMS per bucket, scaled so the loudest bucket is 1.0.
+
+use std::path::Path;
+
+use rodio::Source;
+
+use super::playback::open_source_at_ms;
+
+/// Measure one frame out of this many.
+const SAMPLE_STRIDE: usize = 16;
+
+/// Measured frames per intermediate block (blocks are merged into buckets at the end,
+/// so the total length doesn't need to be known up front).
+const BLOCK_FRAMES: usize = 256;
+
+/// `buckets` RMS values in 0.0..=1.0, in playback order.
+///
+/// Blocking (decodes the entire file); run it off the UI thread.
+pub fn compute_waveform(path: &Path, buckets: usize) -> Result<Vec<f32>, String> {
+    if buckets == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (src, _duration) = open_source_at_ms(path, 0)?;
+    let channels = usize::from(src.channels().max(1));
+
+    // (sum of squares, measured frames) per block.
+    let mut blocks: Vec<(f32, usize)> = Vec::new();
+    let mut current = (0.0f32, 0usize);
+    let mut frame_sum = 0.0f32;
+
+    for (i, s) in src.enumerate() {
+        let frame = i / channels;
+        if frame % SAMPLE_STRIDE != 0 {
+            continue;
+        }
+
+        frame_sum += s;
+        if i % channels + 1 < channels {
+            continue;
+        }
+
+        let mono = frame_sum / channels as f32;
+        frame_sum = 0.0;
+        current.0 += mono * mono;
+        current.1 += 1;
+        if current.1 == BLOCK_FRAMES {
+            blocks.push(std::mem::take(&mut current));
+        }
+    }
+    if current.1 > 0 {
+        blocks.push(current);
+    }
+
+    if blocks.is_empty() {
+        return Err("No audio decoded".to_string());
+    }
+
+    // Merge blocks into buckets (short files: several buckets share a block).
+    let mut out: Vec<f32> = (0..buckets)
+        .map(|b| {
+            let start = b * blocks.len() / buckets;
+            let end = ((b + 1) * blocks.len() / buckets).max(start + 1);
+            let (sum, n) = blocks[start..end.min(blocks.len())]
+                .iter()
+                .fold((0.0, 0), |(s, n), &(bs, bn)| (s + bs, n + bn));
+            if n == 0 { 0.0 } else { (sum / n as f32).sqrt() }
+        })
+        .collect();
+
+    let peak = out.iter().copied().fold(0.0f32, f32::max);
+    if peak > 0.0 {
+        for v in &mut out {
+            *v /= peak;
+        }
+    }
+
+    Ok(out)
+}
@@ -18,4 +18,4 @@ pub(crate) mod view;
 pub(crate) use state::Sonora;
 pub(crate) use subscription::subscription;
 pub(crate) use update::update;
-pub(crate) use view::view;
+pub(crate) use view::{title, view};
@@ -15,16 +15,60 @@
 //! - We still keep `tracks: Vec<TrackRow>` for display order, but we do NOT treat indices as identity.
 
 use std::cell::RefCell;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
-
-use crate::core::playback::{PlaybackController, PlayerEvent, start_playback};
-use crate::core::types::{TrackId, TrackRow};
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
+
+use crate::core::config::Config;
+use crate::core::fileops::OrgReport;
+use crate::core::history;
+use crate::core::lint::{AlbumLint, RequiredField, find_incomplete_albums, find_missing_tags};
+use crate::core::musicbrainz::MbRelease;
+use crate::core::playback::{
+    EqBand, PlaybackController, PlayerCommand, PlayerEvent, RepeatMode, ReplayGainMode,
+    start_playback,
+};
+use crate::core::scrobbler::ScrobblerConfig;
+use crate::core::stats::{self, LibraryStats};
+use crate::core::tags::{Mp3Stats, TagVersion, WriteOptions};
+use crate::core::types::{ScanError, TrackId, TrackRow};
+use crate::core::undo_log;
+
+use super::util::{cmp_tracks, matches_filter};
 
 /// Dev convenience: if user didn’t add roots, scan `/test`.
 pub(crate) const TEST_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test");
 
+/// Preset equalizer band centers (Hz) shown in the EQ panel.
+pub(crate) const EQ_FREQS_HZ: [f32; 5] = [60.0, 250.0, 1000.0, 4000.0, 16000.0];
+
+/// Flat EQ: one band per preset frequency at 0 dB.
+pub(crate) fn flat_eq_bands() -> Vec<EqBand> {
+    EQ_FREQS_HZ
+        .iter()
+        .map(|&freq_hz| EqBand {
+            freq_hz,
+            gain_db: 0.0,
+            q: 1.0,
+        })
+        .collect()
+}
+
+/// Longest crossfade the settings slider offers (ms).
+pub(crate) const MAX_CROSSFADE_MS: u64 = 10_000;
+
+/// Play history cap (oldest entries drop off first).
+pub(crate) const PLAYBACK_HISTORY_MAX: usize = 500;
+
+/// How many history entries the "Recently played" view lists.
+pub(crate) const RECENTLY_PLAYED_SHOWN: usize = 50;
+
+/// Output device list entry for "whatever the OS picks" (`output_device: None`).
+pub(crate) const SYSTEM_DEFAULT_DEVICE: &str = "System default";
+
 /// What the inspector shows when selected files disagree.
 ///
 /// Semantics:
@@ -32,21 +76,265 @@ pub(crate) const TEST_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test");
 /// - On save, `<keep>` means “leave the file’s existing value as-is”
 pub(crate) const KEEP_SENTINEL: &str = "<keep>";
 
-/// Albums vs Tracks list mode.
+/// Fields "Copy tags" takes from a track: the album-level ones, which are the same
+/// across an album and safe to paste onto its other tracks. Core fields only: extended
+/// ones are skipped on save while the extended section is hidden.
+pub(crate) const TAG_CLIPBOARD_FIELDS: [InspectorField; 7] = [
+    InspectorField::Album,
+    InspectorField::AlbumArtist,
+    InspectorField::TrackTotal,
+    InspectorField::DiscNo,
+    InspectorField::DiscTotal,
+    InspectorField::Year,
+    InspectorField::Genre,
+];
+
+/// Widget id of the track table's inline cell editor (focused when editing starts).
+pub(crate) const INLINE_EDIT_ID: &str = "inline-edit";
+
+/// Widget id of the track table's scrollable (scrolled to keep the keyboard selection in view).
+pub(crate) const TRACK_LIST_ID: &str = "track-list";
+
+/// Center list mode: albums, tracks, the cover wall, or the duplicate finder.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ViewMode {
     Albums,
     Tracks,
+    /// Grid of album covers; clicking one opens it in `Albums`.
+    ArtGrid,
+    Duplicates,
+    /// Play history, most recent first.
+    RecentlyPlayed,
+    /// The track table, limited to favorites.
+    Favorites,
+}
+
+impl ViewMode {
+    /// Stable string used in `config.toml`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            ViewMode::Albums => "albums",
+            ViewMode::Tracks => "tracks",
+            ViewMode::ArtGrid => "art",
+            ViewMode::Duplicates => "duplicates",
+            ViewMode::RecentlyPlayed => "recent",
+            ViewMode::Favorites => "favorites",
+        }
+    }
+
+    pub fn from_config_key(s: &str) -> Option<Self> {
+        match s {
+            "albums" => Some(ViewMode::Albums),
+            "tracks" => Some(ViewMode::Tracks),
+            "art" => Some(ViewMode::ArtGrid),
+            "duplicates" => Some(ViewMode::Duplicates),
+            "recent" => Some(ViewMode::RecentlyPlayed),
+            "favorites" => Some(ViewMode::Favorites),
+            _ => None,
+        }
+    }
 }
 
 /// Grouping key for Album View.
 ///
 /// Important: This is a *UI grouping key*, not a DB key.
-/// It’s derived from `TrackRow` values using your grouping rules.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) struct AlbumKey {
-    pub album_artist: String,
-    pub album: String,
+/// Lives in core so library checks (`core::lint`) group albums the same way.
+pub(crate) use crate::core::types::AlbumKey;
+
+/// Track table sort key (header click).
+///
+/// `Unsorted` keeps `tracks` order (scan order: sorted by path).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SortColumn {
+    #[default]
+    Unsorted,
+    TrackNo,
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    Year,
+    Genre,
+    Duration,
+    /// "Recently added" (`date_added`, set the first time a scan sees the file).
+    DateAdded,
+    /// Favorites first (ascending).
+    Favorite,
+}
+
+/// A configurable track table column (the now-playing marker is always first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum TrackColumn {
+    Favorite,
+    TrackNo,
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    Year,
+    Genre,
+    Duration,
+    DateAdded,
+}
+
+impl TrackColumn {
+    /// Every column, in the default order.
+    pub const ALL: [TrackColumn; 10] = [
+        TrackColumn::Favorite,
+        TrackColumn::TrackNo,
+        TrackColumn::Title,
+        TrackColumn::Artist,
+        TrackColumn::Album,
+        TrackColumn::AlbumArtist,
+        TrackColumn::Year,
+        TrackColumn::Genre,
+        TrackColumn::Duration,
+        TrackColumn::DateAdded,
+    ];
+
+    /// Header text.
+    pub fn label(self) -> &'static str {
+        match self {
+            TrackColumn::Favorite => "♥",
+            TrackColumn::TrackNo => "#",
+            TrackColumn::Title => "Title",
+            TrackColumn::Artist => "Artist",
+            TrackColumn::Album => "Album",
+            TrackColumn::AlbumArtist => "Album Artist",
+            TrackColumn::Year => "Year",
+            TrackColumn::Genre => "Genre",
+            TrackColumn::Duration => "Len",
+            TrackColumn::DateAdded => "Added",
+        }
+    }
+
+    /// Name in the column settings panel (the header's "♥" / "#" need spelling out).
+    pub fn settings_label(self) -> &'static str {
+        match self {
+            TrackColumn::Favorite => "Favorite",
+            TrackColumn::TrackNo => "Track #",
+            TrackColumn::Duration => "Length",
+            other => other.label(),
+        }
+    }
+
+    /// Header click sorts by this.
+    pub fn sort_column(self) -> SortColumn {
+        match self {
+            TrackColumn::Favorite => SortColumn::Favorite,
+            TrackColumn::TrackNo => SortColumn::TrackNo,
+            TrackColumn::Title => SortColumn::Title,
+            TrackColumn::Artist => SortColumn::Artist,
+            TrackColumn::Album => SortColumn::Album,
+            TrackColumn::AlbumArtist => SortColumn::AlbumArtist,
+            TrackColumn::Year => SortColumn::Year,
+            TrackColumn::Genre => SortColumn::Genre,
+            TrackColumn::Duration => SortColumn::Duration,
+            TrackColumn::DateAdded => SortColumn::DateAdded,
+        }
+    }
+
+    /// Width when `column_widths` has no entry for it.
+    pub fn default_width(self) -> f32 {
+        match self {
+            TrackColumn::Favorite => 24.0,
+            TrackColumn::TrackNo => 44.0,
+            TrackColumn::Title | TrackColumn::Album => 240.0,
+            TrackColumn::Artist => 190.0,
+            TrackColumn::AlbumArtist => 170.0,
+            TrackColumn::Year | TrackColumn::Duration => 70.0,
+            TrackColumn::Genre => 140.0,
+            TrackColumn::DateAdded => 90.0,
+        }
+    }
+
+    /// Stable string used in `config.toml`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            TrackColumn::Favorite => "favorite",
+            TrackColumn::TrackNo => "track_no",
+            TrackColumn::Title => "title",
+            TrackColumn::Artist => "artist",
+            TrackColumn::Album => "album",
+            TrackColumn::AlbumArtist => "album_artist",
+            TrackColumn::Year => "year",
+            TrackColumn::Genre => "genre",
+            TrackColumn::Duration => "duration",
+            TrackColumn::DateAdded => "date_added",
+        }
+    }
+
+    pub fn from_config_key(s: &str) -> Option<Self> {
+        TrackColumn::ALL.into_iter().find(|c| c.config_key() == s)
+    }
+}
+
+/// Track column widths are clamped to this range (config values included).
+pub(crate) const MIN_COLUMN_W: f32 = 20.0;
+pub(crate) const MAX_COLUMN_W: f32 = 600.0;
+
+/// How a track-row click changes the selection (from held modifiers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SelectMode {
+    /// Plain click: replace the selection.
+    Single,
+    /// Ctrl/Cmd+click: toggle this track in the selection.
+    AddToggle,
+    /// Shift+click: add every visible track between the last click and this one.
+    RangeExtend,
+}
+
+/// Which way a queue entry moves (one step per press).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Up,
+    Down,
+}
+
+/// What playing a track from a list (`PlayTrack`) puts in the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum QueueFill {
+    /// Leave the queue alone (it only changes when you queue things yourself).
+    #[default]
+    Manual,
+    /// The track's album, in disc/track order.
+    Album,
+    /// Every track by the same artist, by album then disc/track.
+    Artist,
+    /// Every track in the same genre, by artist, album, disc/track.
+    Genre,
+    /// The whole library, shuffled (the played track first).
+    Random,
+}
+
+impl QueueFill {
+    pub const ALL: [QueueFill; 5] = [
+        QueueFill::Manual,
+        QueueFill::Album,
+        QueueFill::Artist,
+        QueueFill::Genre,
+        QueueFill::Random,
+    ];
+}
+
+impl std::fmt::Display for QueueFill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            QueueFill::Manual => "Fill: Manual",
+            QueueFill::Album => "Fill: Album",
+            QueueFill::Artist => "Fill: Artist",
+            QueueFill::Genre => "Fill: Genre",
+            QueueFill::Random => "Fill: Random",
+        })
+    }
+}
+
+/// One undo/redo step: the rows (as they were on disk) to write back.
+#[derive(Debug, Clone)]
+pub(crate) struct UndoEntry {
+    /// Short description for the status line ("Save", "Batch save (12 files)").
+    pub label: String,
+    pub rows: Vec<TrackRow>,
 }
 
 /// Draft editable metadata (strings so the user can type anything).
@@ -90,6 +378,97 @@ pub(crate) struct InspectorDraft {
     pub encoder_settings: String,
     pub encoded_by: String,
     pub copyright: String,
+    pub trim_db: String,
+
+    // Sort-order overrides (extended)
+    pub title_sort: String,
+    pub artist_sort: String,
+    pub album_sort: String,
+    pub album_artist_sort: String,
+
+    // MusicBrainz ids (extended; single selection only). Shown read-only; a lookup
+    // fills them and "Save edits" writes them.
+    pub mb_recording_id: String,
+    pub mb_release_id: String,
+
+    // Custom TXXX frames (extended; single selection only): description -> value,
+    // plus the "add" row being typed.
+    pub user_text: BTreeMap<String, String>,
+    pub new_user_text_desc: String,
+    pub new_user_text_value: String,
+
+    // Every COMM frame, in tag order (extended; single selection only).
+    // Replaces all of the file's comment frames on save.
+    pub comments: Vec<CommentFrameDraft>,
+}
+
+/// One editable comment frame (ID3: `COMM`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommentFrameDraft {
+    /// ISO 639-2 language code ("eng"); validated on save.
+    pub lang: String,
+    pub description: String,
+    pub text: String,
+}
+
+impl Default for CommentFrameDraft {
+    fn default() -> Self {
+        Self {
+            lang: "eng".to_string(),
+            description: String::new(),
+            text: String::new(),
+        }
+    }
+}
+
+/// Fields "Find & replace" can rewrite (plain text, written with the standard frames).
+pub(crate) const FIND_REPLACE_FIELDS: [InspectorField; 7] = [
+    InspectorField::Title,
+    InspectorField::Artist,
+    InspectorField::Album,
+    InspectorField::AlbumArtist,
+    InspectorField::Composer,
+    InspectorField::Genre,
+    InspectorField::Grouping,
+];
+
+/// Batch save progress, sent after each file is written and re-read.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BatchSaveProgress {
+    pub done: usize,
+    pub total: usize,
+    pub last_id: TrackId,
+}
+
+/// Open "Find & replace" dialog: what to search, and the last preview (if any).
+#[derive(Debug, Clone)]
+pub(crate) struct FindReplaceDraft {
+    pub field: InspectorField,
+    pub find: String,
+    pub replace: String,
+    pub regex: bool,
+    /// (track, value before, value after) for every match; cleared by any edit.
+    pub preview: Option<Vec<(TrackId, String, String)>>,
+}
+
+impl Default for FindReplaceDraft {
+    fn default() -> Self {
+        Self {
+            field: InspectorField::Artist,
+            find: String::new(),
+            replace: String::new(),
+            regex: false,
+            preview: None,
+        }
+    }
+}
+
+/// Which part of a comment frame draft an edit targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommentPart {
+    Lang,
+    Description,
+    Text,
 }
 
 /// Identifies which inspector field changed.
@@ -129,6 +508,12 @@ pub(crate) enum InspectorField {
     EncoderSettings,
     EncodedBy,
     Copyright,
+    TrimDb,
+
+    TitleSort,
+    ArtistSort,
+    AlbumSort,
+    AlbumArtistSort,
 }
 
 /// App state.
@@ -142,10 +527,28 @@ pub(crate) struct Sonora {
     pub status: String,
     pub scanning: bool,
 
+    /// (files read, total) while a library scan runs; drives the sidebar progress bar.
+    pub scan_progress: Option<(usize, usize)>,
+    /// (files written, total) while a batch save runs; same bar, below the scan one.
+    pub save_progress: Option<(usize, usize)>,
+
+    /// Files the last full scan couldn't read tags from (sidebar "Errors" log).
+    pub scan_errors: Vec<ScanError>,
+    pub show_scan_errors: bool,
+
     // Roots
     pub root_input: String,
     pub roots: Vec<PathBuf>,
 
+    /// Scan exclusion patterns (persisted) + the sidebar's "add pattern" input.
+    pub exclusions: Vec<String>,
+    pub exclusion_input: String,
+
+    /// Paths the folder watcher reported since the last batch was applied,
+    /// and whether the debounce timer for that batch is already running.
+    pub folder_changes: BTreeSet<PathBuf>,
+    pub folder_changes_pending: bool,
+
     // Library (display order)
     pub tracks: Vec<TrackRow>,
 
@@ -165,6 +568,10 @@ pub(crate) struct Sonora {
     /// Cache: `TrackId` -> decoded cover image handle (for quick UI rendering).
     pub cover_cache: BTreeMap<TrackId, iced::widget::image::Handle>,
 
+    /// Cache: `TrackId` -> normalized waveform (0..=1 per bucket) for the playback bar.
+    /// Empty = decoding failed (not retried).
+    pub waveform_cache: BTreeMap<TrackId, Vec<f32>>,
+
     // Playback (core handle + UI state)
     pub playback: Option<PlaybackController>,
 
@@ -175,16 +582,83 @@ pub(crate) struct Sonora {
     pub now_playing: Option<TrackId>,
     pub is_playing: bool,
     pub position_ms: u64,
+
+    /// When `position_ms` last came from the engine (lets the view interpolate between ticks).
+    pub last_position_update: Option<Instant>,
     pub duration_ms: Option<u64>,
     pub volume: f32,
 
+    /// Volume normalization from ReplayGain tags (persisted).
+    pub replaygain_mode: ReplayGainMode,
+
+    /// Crossfade between consecutive tracks in ms, 0 = gapless (persisted).
+    pub crossfade_ms: u64,
+
+    /// Output devices found at startup (or on Refresh), by name.
+    pub audio_devices: Vec<String>,
+    /// Chosen output device; `None` = system default (persisted).
+    pub output_device: Option<String>,
+
+    /// Equalizer bands last sent to the engine (`EQ_FREQS_HZ` order).
+    pub eq_bands: Vec<EqBand>,
+
     /// While dragging the seek slider, keep a UI-only preview ratio here.
     /// On release, we commit it (send PlayerCommand::Seek).
     pub seek_preview_ratio: Option<f32>,
 
+    /// Shuffle mode: Next picks a random track instead of display order.
+    pub shuffle: bool,
+
+    /// How `PlayTrack` refills the queue (playback bar picker).
+    pub queue_fill: QueueFill,
+
+    /// Tracks left behind by shuffled Next (most recent at the back), so Prev
+    /// walks back through what was actually played.
+    pub shuffle_history: VecDeque<TrackId>,
+
+    /// Finished tracks and when they finished, oldest first (capped at
+    /// `PLAYBACK_HISTORY_MAX`; saved to `history.json` on exit).
+    pub playback_history: VecDeque<(TrackId, DateTime<Local>)>,
+
+    /// What TrackEnded does: stop, replay the same track, or advance (wrapping).
+    pub repeat_mode: RepeatMode,
+
+    /// Play queue (stable ids). When non-empty, Next/Prev follow it instead of `tracks`.
+    pub queue: Vec<TrackId>,
+
+    /// Cursor into `queue` (the entry currently playing / last played).
+    pub queue_pos: usize,
+
+    /// Track handed to the engine for gapless playback (id, queue position if queued).
+    pub gapless_next: Option<(TrackId, Option<usize>)>,
+
+    /// Time actually listened to the current track (seeks don't count); decides scrobbling.
+    pub listened_ms: u64,
+
+    /// Last.fm credentials/session (persisted) and the token awaiting browser approval.
+    pub scrobbler: ScrobblerConfig,
+    pub lastfm_auth_token: Option<String>,
+
     // Selection / navigation
     pub view_mode: ViewMode,
+    /// Smaller rows and text in the track table and album list (persisted).
+    pub compact_mode: bool,
+
+    /// Search box text. Narrows what the views render; never touches `tracks`.
+    pub filter_query: String,
+
+    /// Track table sort (view-only; `tracks` itself is never reordered by this).
+    pub sort_column: SortColumn,
+    pub sort_ascending: bool,
+
+    /// Track table columns, in display order (persisted). Hidden ones are absent.
+    pub visible_columns: Vec<TrackColumn>,
+    /// Per-column width overrides in px (persisted); missing = `default_width()`.
+    pub column_widths: BTreeMap<TrackColumn, f32>,
+
     pub selected_album: Option<AlbumKey>,
+    /// Album detail header: the "Album actions" buttons are expanded.
+    pub show_album_actions: bool,
 
     /// Multi-selection set of track ids (stable).
     pub selected_tracks: BTreeSet<TrackId>,
@@ -195,6 +669,19 @@ pub(crate) struct Sonora {
     /// For shift-click range selection (stable id).
     pub last_clicked_track: Option<TrackId>,
 
+    /// Currently held keyboard modifiers (drives `SelectMode` for row clicks).
+    pub modifiers: iced::keyboard::Modifiers,
+
+    /// Track table scroll position + size, as last reported by the list itself.
+    pub track_list_viewport: Option<iced::widget::scrollable::Viewport>,
+
+    /// Track-table cell being edited in place (double-click), and its typed text.
+    pub inline_edit: Option<(TrackId, InspectorField)>,
+    pub inline_edit_value: String,
+
+    /// Library-wide "Find & replace" dialog (`Some` = shown over the whole window).
+    pub find_replace: Option<FindReplaceDraft>,
+
     // Inspector
     pub inspector: InspectorDraft,
     pub inspector_dirty: bool,
@@ -203,11 +690,116 @@ pub(crate) struct Sonora {
     /// For each field: are selected tracks "mixed" for this value?
     pub inspector_mixed: BTreeMap<InspectorField, bool>,
 
+    /// "Copy tags" result: field values taken from one track, pasted into the draft
+    /// by "Paste tags". `None` until something is copied.
+    pub tag_clipboard: Option<BTreeMap<InspectorField, String>>,
+
+    /// Tag-edit history: pre-edit snapshots (newest last), capped at `UNDO_MAX`.
+    /// Undoing moves the inverse snapshot onto `redo_stack`; a new edit clears it.
+    /// Restored from the newest undo file at startup (see `core::undo_log`).
+    pub undo_stack: Vec<UndoEntry>,
+    pub redo_stack: Vec<UndoEntry>,
+    /// This session's undo file; rewritten whenever `undo_stack` changes.
+    pub undo_log_path: Option<PathBuf>,
+
+    /// A MusicBrainz lookup is in flight (one at a time; the service is rate-limited).
+    pub mb_lookup_pending: bool,
+
+    /// AcoustID application key (persisted); enables "Identify by audio".
+    pub acoustid_api_key: String,
+
+    /// External tag editor command (persisted; empty = not configured).
+    pub external_tag_editor: String,
+
+    /// POPM "email" that owns our star ratings (persisted; see `rating_owner`).
+    pub rating_email: String,
+
+    /// How tag writes behave: ID3 version (v2.3 for older car stereos) and whether
+    /// to back files up first (both persisted). `write_extended` is set per save
+    /// from `show_extended`.
+    pub write_options: WriteOptions,
+
+    /// Run `validate_mp3` on each file before an inspector save; corrupt files
+    /// abort the save (persisted).
+    pub validate_before_write: bool,
+
+    /// Album grouping includes the year, so same-named albums (editions, reissues)
+    /// list separately (persisted). See `AlbumKey`.
+    pub album_group_by_year: bool,
+
+    // Duplicate finder: groups of likely duplicates (ids), which groups are expanded
+    // (by group index), and tracks marked to be moved to the trash.
+    pub duplicate_groups: Vec<Vec<TrackId>>,
+    pub expanded_duplicate_groups: BTreeSet<usize>,
+    pub marked_for_deletion: BTreeSet<TrackId>,
+
+    /// Open "Missing tags" report: incomplete tracks and what they lack.
+    /// `Some` replaces the library view in the center panel; kept current on library changes.
+    pub missing_tag_report: Option<Vec<(TrackId, Vec<RequiredField>)>>,
+
+    /// Open "Check albums" report: albums with gaps or repeated track numbers.
+    /// Shown like the missing-tags report (the two replace each other).
+    pub album_check_report: Option<Vec<AlbumLint>>,
+
+    /// Tracks whose file was gone at the last "Check files"; marked "!" in the track list.
+    pub missing_track_ids: BTreeSet<TrackId>,
+
+    /// Library overview (sidebar "Statistics"); computed when opened, kept current
+    /// on library changes while `show_stats` is on.
+    pub library_stats: LibraryStats,
+
+    /// File name template for "Rename from tags" (see `core::fileops`).
+    pub rename_template: String,
+
+    // "Organize files" panel (sidebar): destination root, path template, copy vs move.
+    pub organize_dest: String,
+    pub organize_template: String,
+    pub organize_copy: bool,
+
     // UI toggles
     pub show_extended: bool,
+    pub show_queue: bool,
+    pub show_column_config: bool,
+    pub show_eq: bool,
+    pub show_organize: bool,
+    pub show_settings: bool,
+    pub show_stats: bool,
+    pub show_lyrics: bool,
 }
 
 impl Sonora {
+    /// The "Recently played" list: newest first, at most `RECENTLY_PLAYED_SHOWN`.
+    pub fn recently_played(&self) -> impl Iterator<Item = &(TrackId, DateTime<Local>)> {
+        self.playback_history
+            .iter()
+            .rev()
+            .take(RECENTLY_PLAYED_SHOWN)
+    }
+
+    /// App entry point: default state plus the startup tasks.
+    pub(crate) fn boot() -> (Self, iced::Task<Message>) {
+        (Self::default(), iced::Task::done(Message::LoadAudioDevices))
+    }
+
+    /// Best guess at the playhead right now: last engine position + time since it arrived.
+    ///
+    /// Only extrapolates while playing and not dragging the seek slider.
+    pub fn estimated_position_ms(&self) -> u64 {
+        let mut pos = self.position_ms;
+
+        if self.is_playing
+            && self.seek_preview_ratio.is_none()
+            && let Some(at) = self.last_position_update
+        {
+            pos += at.elapsed().as_millis() as u64;
+        }
+
+        match self.duration_ms {
+            Some(dur) => pos.min(dur),
+            None => pos,
+        }
+    }
+
     /// Find the current display index for a given `TrackId`.
     #[inline]
     pub fn index_of_id(&self, id: TrackId) -> Option<usize> {
@@ -228,6 +820,92 @@ impl Sonora {
         self.tracks.get_mut(i)
     }
 
+    /// Effective width of a track table column (override, else its default).
+    pub fn column_width(&self, column: TrackColumn) -> f32 {
+        self.column_widths
+            .get(&column)
+            .copied()
+            .unwrap_or_else(|| column.default_width())
+    }
+
+    /// Indices into `tracks` in track-view render order: filter first, then (stable) sort.
+    ///
+    /// Shared by the view and by order-sensitive updates (shift-click, select all).
+    pub fn visible_track_indices(&self) -> Vec<usize> {
+        let mut idxs: Vec<usize> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| matches_filter(t, &self.filter_query))
+            .filter(|(_, t)| self.view_mode != ViewMode::Favorites || t.is_favorite)
+            .map(|(i, _)| i)
+            .collect();
+
+        if self.sort_column != SortColumn::Unsorted {
+            idxs.sort_by(|&a, &b| {
+                cmp_tracks(
+                    &self.tracks[a],
+                    &self.tracks[b],
+                    self.sort_column,
+                    self.sort_ascending,
+                )
+            });
+        }
+
+        idxs
+    }
+
+    /// Drop tracks from the library and from everything that refers to them by id.
+    ///
+    /// The caller decides what happens to the inspector (selection may have changed).
+    pub fn remove_tracks(&mut self, ids: &BTreeSet<TrackId>) {
+        self.tracks
+            .retain(|t| t.id.is_none_or(|id| !ids.contains(&id)));
+        self.rebuild_library_caches();
+
+        self.selected_tracks.retain(|id| !ids.contains(id));
+        if self.selected_track.is_some_and(|id| ids.contains(&id)) {
+            self.selected_track = self.selected_tracks.iter().next().copied();
+        }
+        if self.last_clicked_track.is_some_and(|id| ids.contains(&id)) {
+            self.last_clicked_track = None;
+        }
+        self.marked_for_deletion.retain(|id| !ids.contains(id));
+        self.missing_track_ids.retain(|id| !ids.contains(id));
+        self.shuffle_history.retain(|id| !ids.contains(id));
+        self.playback_history.retain(|(id, _)| !ids.contains(id));
+
+        // Queue: drop entries, keeping the cursor on the same (surviving) entry.
+        let removed_before_cursor = self.queue[..self.queue_pos.min(self.queue.len())]
+            .iter()
+            .filter(|id| ids.contains(id))
+            .count();
+        self.queue.retain(|id| !ids.contains(id));
+        self.queue_pos = self
+            .queue_pos
+            .saturating_sub(removed_before_cursor)
+            .min(self.queue.len().saturating_sub(1));
+
+        for group in self.duplicate_groups.iter_mut() {
+            group.retain(|id| !ids.contains(id));
+        }
+        self.duplicate_groups.retain(|g| g.len() > 1);
+        self.expanded_duplicate_groups.clear();
+    }
+
+    /// Run the completeness check over the whole library (ids, not indices).
+    pub fn missing_tags(&self) -> Vec<(TrackId, Vec<RequiredField>)> {
+        find_missing_tags(&self.tracks, &RequiredField::ALL)
+            .into_iter()
+            .filter_map(|(i, missing)| self.tracks[i].id.map(|id| (id, missing)))
+            .collect()
+    }
+
+    /// Album a track is grouped under (honours `album_group_by_year`).
+    pub fn album_key(&self, t: &TrackRow) -> AlbumKey {
+        AlbumKey::for_track_by_year(t, self.album_group_by_year)
+    }
+
     /// Rebuild `track_index` and `album_groups` from `tracks`.
     ///
     /// Call this whenever `tracks` changes (scan, save, reorder, etc).
@@ -245,26 +923,23 @@ impl Sonora {
         for t in self.tracks.iter() {
             let Some(id) = t.id else { continue };
 
-            let album_artist = t
-                .album_artist
-                .clone()
-                .or_else(|| t.artist.clone())
-                .unwrap_or_else(|| "Unknown Artist".to_string());
-
-            let album = t
-                .album
-                .clone()
-                .unwrap_or_else(|| "Unknown Album".to_string());
-
             self.album_groups
-                .entry(AlbumKey {
-                    album_artist,
-                    album,
-                })
+                .entry(self.album_key(t))
                 .or_default()
                 .push(id);
         }
 
+        // Stage 3: open lint reports follow edits/rescans.
+        if self.missing_tag_report.is_some() {
+            self.missing_tag_report = Some(self.missing_tags());
+        }
+        if self.album_check_report.is_some() {
+            self.album_check_report = Some(find_incomplete_albums(&self.tracks));
+        }
+        if self.show_stats {
+            self.library_stats = stats::compute(&self.tracks);
+        }
+
         // Optional: stable intra-album order.
         // Keep "scan order" by default; the detail view will sort by disc/track/title.
         // If you want to sort group vectors by display index:
@@ -278,18 +953,75 @@ impl Default for Sonora {
     fn default() -> Self {
         let (playback_controller, playback_events) = start_playback();
 
+        // Persisted preferences (defaults on first launch).
+        let config = Config::load();
+        let volume = config.volume.clamp(0.0, 1.0);
+        playback_controller.send(PlayerCommand::SetVolume(volume));
+
+        let replaygain_mode =
+            ReplayGainMode::from_config_key(&config.replaygain).unwrap_or_default();
+        playback_controller.send(PlayerCommand::SetReplayGain(replaygain_mode));
+
+        let crossfade_ms = config.crossfade_ms.min(MAX_CROSSFADE_MS);
+        playback_controller.send(PlayerCommand::SetCrossfade(crossfade_ms));
+
+        let output_device = config.output_device.clone();
+        if let Some(name) = &output_device {
+            playback_controller.send(PlayerCommand::SetOutputDevice(name.clone()));
+        }
+
+        // Unknown keys (older/newer versions) are dropped; nothing left = defaults.
+        let mut visible_columns: Vec<TrackColumn> = Vec::new();
+        for c in config
+            .track_columns
+            .iter()
+            .filter_map(|k| TrackColumn::from_config_key(k))
+        {
+            if !visible_columns.contains(&c) {
+                visible_columns.push(c);
+            }
+        }
+        if visible_columns.is_empty() {
+            visible_columns = TrackColumn::ALL.to_vec();
+        }
+        let column_widths: BTreeMap<TrackColumn, f32> = config
+            .column_widths
+            .iter()
+            .filter_map(|(k, w)| {
+                let c = TrackColumn::from_config_key(k)?;
+                w.is_finite()
+                    .then(|| (c, w.clamp(MIN_COLUMN_W, MAX_COLUMN_W)))
+            })
+            .collect();
+
+        let status = if config.roots.is_empty() {
+            "Add a folder, then Scan.".to_string()
+        } else {
+            "Ready. Click Scan Library.".to_string()
+        };
+
         Self {
-            status: "Add a folder, then Scan.".to_string(),
+            status,
             scanning: false,
+            scan_progress: None,
+            save_progress: None,
+
+            scan_errors: Vec::new(),
+            show_scan_errors: false,
 
             root_input: String::new(),
-            roots: Vec::new(),
+            roots: config.roots,
+            exclusions: config.exclusions,
+            exclusion_input: String::new(),
+            folder_changes: BTreeSet::new(),
+            folder_changes_pending: false,
 
             tracks: Vec::new(),
 
             track_index: BTreeMap::new(),
             album_groups: BTreeMap::new(),
             cover_cache: BTreeMap::new(),
+            waveform_cache: BTreeMap::new(),
 
             playback: Some(playback_controller),
             playback_events: Some(RefCell::new(playback_events)),
@@ -297,24 +1029,90 @@ impl Default for Sonora {
             now_playing: None,
             is_playing: false,
             position_ms: 0,
+            last_position_update: None,
             duration_ms: None,
-            volume: 1.0,
+            volume,
+            replaygain_mode,
+            crossfade_ms,
+            audio_devices: Vec::new(),
+            output_device,
+            eq_bands: flat_eq_bands(),
 
             seek_preview_ratio: None,
 
-            view_mode: ViewMode::Tracks,
+            shuffle: false,
+            queue_fill: QueueFill::default(),
+            shuffle_history: VecDeque::new(),
+            playback_history: history::load().into_iter().collect(),
+            repeat_mode: RepeatMode::Off,
+            queue: Vec::new(),
+            queue_pos: 0,
+            gapless_next: None,
+            listened_ms: 0,
+            scrobbler: config.scrobbler,
+            lastfm_auth_token: None,
+
+            view_mode: ViewMode::from_config_key(&config.view_mode).unwrap_or(ViewMode::Tracks),
+            filter_query: String::new(),
+            sort_column: SortColumn::Unsorted,
+            sort_ascending: true,
+            visible_columns,
+            column_widths,
             selected_album: None,
+            show_album_actions: false,
 
             selected_tracks: BTreeSet::new(),
             selected_track: None,
             last_clicked_track: None,
+            modifiers: iced::keyboard::Modifiers::default(),
+            track_list_viewport: None,
+            inline_edit: None,
+            find_replace: None,
+            inline_edit_value: String::new(),
 
             inspector: InspectorDraft::default(),
             inspector_dirty: false,
             saving: false,
             inspector_mixed: BTreeMap::new(),
-
-            show_extended: false,
+            tag_clipboard: None,
+            undo_stack: undo_log::load()
+                .into_iter()
+                .map(|(label, rows)| UndoEntry { label, rows })
+                .collect(),
+            redo_stack: Vec::new(),
+            undo_log_path: undo_log::session_path(),
+            mb_lookup_pending: false,
+            acoustid_api_key: config.acoustid_api_key,
+            external_tag_editor: config.external_tag_editor.unwrap_or_default(),
+            rating_email: config.rating_email,
+            write_options: WriteOptions {
+                backup: config.backup_on_write,
+                tag_version: TagVersion::from_config_key(&config.tag_version).unwrap_or_default(),
+                ..WriteOptions::default()
+            },
+            validate_before_write: config.validate_before_write,
+            album_group_by_year: config.album_group_by_year,
+            compact_mode: config.compact_mode,
+            duplicate_groups: Vec::new(),
+            expanded_duplicate_groups: BTreeSet::new(),
+            marked_for_deletion: BTreeSet::new(),
+            missing_tag_report: None,
+            album_check_report: None,
+            missing_track_ids: BTreeSet::new(),
+            library_stats: LibraryStats::default(),
+            rename_template: "{track_no:02} {title}".to_string(),
+            organize_dest: String::new(),
+            organize_template: "{album_artist}/{album}/{track_no:02} - {title}".to_string(),
+            organize_copy: false,
+
+            show_extended: config.show_extended,
+            show_queue: false,
+            show_column_config: false,
+            show_stats: false,
+            show_lyrics: false,
+            show_eq: false,
+            show_organize: false,
+            show_settings: false,
         }
     }
 }
@@ -334,29 +1132,129 @@ pub(crate) enum Message {
     AddRootPressed,
     RemoveRoot(usize),
 
+    // Scan exclusions
+    ExclusionInputChanged(String),
+    AddExclusion(String),
+    RemoveExclusion(usize),
+    /// Files/folders dropped onto the window (folders => roots, files => their folder).
+    DroppedPaths(Vec<PathBuf>),
+
+    /// Library cache rows outside the remaining roots were deleted (count).
+    CachePruned(Result<usize, String>),
+
     // Scan
     ScanLibrary,
-    ScanFinished(Result<(Vec<TrackRow>, usize), String>),
+
+    /// Incremental tag-read progress while a scan is running.
+    ScanProgress {
+        scanned: usize,
+        total: usize,
+    },
+
+    ScanFinished(Result<(Vec<TrackRow>, Vec<ScanError>), String>),
+    /// Expand / collapse the scan error log.
+    ToggleScanErrors,
+    /// Put every "path: reason" line of the scan error log on the clipboard.
+    CopyScanErrors,
+    /// First-seen dates were written to newly scanned files (count that failed).
+    DateAddedWritten(usize),
+
+    /// A file/folder under a root was created, removed or renamed (folder watcher).
+    FolderChanged(PathBuf),
+    /// Debounce window elapsed: apply the collected folder changes.
+    FolderChangesSettled,
+    /// Rows read for files that appeared under the roots.
+    FolderRescanFinished(Result<Vec<TrackRow>, String>),
 
     // View + selection
     SetViewMode(ViewMode),
+    ToggleCompactMode,
     SelectAlbum(AlbumKey),
+    /// Album-list click with modifiers (Ctrl adds/removes the album's tracks).
+    SelectAlbumModified(AlbumKey, SelectMode),
+    /// Album detail "Fix album tags": select the whole album with its album-level
+    /// fields pre-filled from the primary track, ready to Save.
+    FixAlbumTags(AlbumKey),
+    FilterChanged(String),
+
+    /// Header click: same column flips direction, new column starts ascending.
+    SetSort(SortColumn),
+
+    /// Track table column settings (⚙ above the table).
+    ToggleColumnConfig,
+    SetColumnVisible(TrackColumn, bool),
+    /// Move a visible column one step left (`Up`) or right (`Down`).
+    MoveColumn(TrackColumn, Direction),
+    /// Change a column's width by this many px (clamped).
+    ResizeColumn(TrackColumn, f32),
+    ResetColumns,
 
     /// Select a track by stable id (not Vec index).
     SelectTrack(TrackId),
+    SelectTrackModified(TrackId, SelectMode),
+
+    /// Select every track currently shown (respects the search filter).
+    SelectAll,
+    ModifiersChanged(iced::keyboard::Modifiers),
+    /// Arrow keys: move the selection one row in the track table (Up / Down).
+    SelectPrevTrack,
+    SelectNextTrack,
+    TrackListScrolled(iced::widget::scrollable::Viewport),
+    /// A key press no widget captured (routed to shortcuts in `update/keyboard.rs`).
+    KeyPressed(iced::keyboard::Key, iced::keyboard::Modifiers),
 
     // Cover art
     CoverLoaded(TrackId, Option<iced::widget::image::Handle>),
 
-    // Playback controls (from UI)
-    PlaySelected,
+    // Waveform (playback bar)
+    WaveformReady(TrackId, Vec<f32>),
 
+    /// Open a file dialog to choose a cover image for a track.
+    LoadArtworkFromFile(TrackId),
+
+    /// Write (image_bytes, mime) as the track's front cover.
+    SetCoverArt(TrackId, Vec<u8>, String),
+
+    /// Cover write result (re-read row on success) + the image size, when known.
+    CoverArtSaved(TrackId, Option<(u32, u32)>, Result<Box<TrackRow>, String>),
+
+    /// Save the track's embedded cover to an image file (asks where).
+    ExtractArtwork(TrackId),
+    ExtractArtworkDone(Result<PathBuf, String>),
+
+    // Playback controls (from UI)
     /// Play a track by stable id (not Vec index).
     PlayTrack(TrackId),
 
     TogglePlayPause,
     Next,
     Prev,
+    ToggleShuffle,
+    CycleRepeat,
+    SetQueueFill(QueueFill),
+
+    // Queue
+    EnqueueTrack(TrackId),
+    EnqueueAlbum(AlbumKey),
+    /// Replace the queue with this track + everything after it in display order, then play.
+    PlayFromHere(TrackId),
+    PlayQueue,
+    ClearQueue,
+    /// Remove the entry at this queue position.
+    RemoveFromQueue(usize),
+    /// Reorder: swap the entry at this position with its neighbour (clamped at the ends).
+    MoveInQueue(usize, Direction),
+    MoveToTop(usize),
+    MoveToBottom(usize),
+    ToggleQueuePanel,
+
+    /// Pick an .m3u/.m3u8 file and load it into the queue.
+    ImportPlaylist,
+    PlaylistImported(Result<Vec<PathBuf>, String>),
+
+    /// Save the queue (else selection, else library) as an .m3u file.
+    ExportPlaylist,
+    PlaylistExported(Result<(PathBuf, usize), String>),
 
     /// Seek slider changed (preview only; does NOT command the engine)
     SeekTo(f32),
@@ -365,22 +1263,218 @@ pub(crate) enum Message {
     SeekCommit,
 
     SetVolume(f32),
+    CycleReplayGain,
+    /// Settings crossfade slider (ms).
+    SetCrossfade(u64),
+    /// Enumerate output devices (off-thread; cpal can be slow to probe).
+    LoadAudioDevices,
+    AudioDevicesLoaded(Vec<String>),
+    /// Settings device list pick (a device name or `SYSTEM_DEFAULT_DEVICE`).
+    SetOutputDevice(String),
+    SetTagVersion(TagVersion),
+    /// Copy each file to `<name>.bak` before writing its tags.
+    SetBackupOnWrite(bool),
+    /// Check MP3 frame structure before inspector saves.
+    SetValidateBeforeWrite(bool),
+    /// Split albums by year (see `album_group_by_year`).
+    SetAlbumGroupByYear(bool),
+
+    /// Show/hide the equalizer panel above the playback bar.
+    ToggleEqPanel,
+    /// Show/hide the now-playing lyrics panel below the playback bar.
+    ToggleLyricsPanel,
+    /// EQ slider moved: (band index, gain dB).
+    SetEqGain(usize, f32),
+    ResetEq,
 
     // (optional path; still supported)
+    #[allow(dead_code)]
     PlaybackEvent(PlayerEvent),
 
     // Inspector edits
     ToggleExtended(bool),
     InspectorChanged(InspectorField, String),
+    /// "✕" next to a field: blank it (= remove the tag on save, for every selected file).
+    ClearField(InspectorField),
+    /// Store these fields' values from a track in `tag_clipboard`.
+    CopyTags(TrackId, Vec<InspectorField>),
+    /// Overwrite the draft's fields with `tag_clipboard` (saved with "Save edits").
+    PasteTags,
+
+    /// Double-clicked a track-table cell: edit it in place.
+    BeginInlineEdit(TrackId, InspectorField),
+    InlineEditChanged(String),
+    /// Enter in the inline editor: write the value to the file right away.
+    CommitInlineEdit(TrackId, InspectorField, String),
+    /// Escape (or focus moved on): drop the inline edit.
+    CancelInlineEdit,
+    InlineEditSaved(Result<Vec<(TrackId, TrackRow)>, String>),
+
+    /// Set (add or overwrite) a custom TXXX entry in the draft.
+    AddUserText(String, String),
+    /// Drop a custom TXXX entry from the draft (removed from the file on save).
+    RemoveUserText(String),
+    /// The "add custom tag" inputs changed (description, value).
+    NewUserTextChanged(String, String),
+
+    /// Edit one comment frame in the draft (index, part, new value).
+    CommentDraftChanged(usize, CommentPart, String),
+    /// Append an empty ("eng", no description) comment frame to the draft.
+    AddCommentDraft,
+    /// Drop a comment frame from the draft (removed from the file on save).
+    RemoveCommentDraft(usize),
 
     // Actions
     SaveInspectorToFile,
 
-    /// Save result for a single target track id.
-    SaveFinished(TrackId, Result<TrackRow, String>),
+    /// Save result for a single target track id (with the pre-write MP3 check, if run).
+    SaveFinished(TrackId, Result<(Box<TrackRow>, Option<Mp3Stats>), String>),
 
+    /// A batch save wrote another file (status only; rows apply at the end).
+    BatchSaveProgress(BatchSaveProgress),
     /// Save result for a batch.
     SaveFinishedBatch(Result<Vec<(TrackId, TrackRow)>, String>),
 
     RevertInspector,
+
+    // Undo / redo (tag writes)
+    Undo,
+    Redo,
+    /// (was redo, entry that was applied, its inverse, write result)
+    HistoryApplied(
+        bool,
+        UndoEntry,
+        UndoEntry,
+        Result<Vec<(TrackId, TrackRow)>, String>,
+    ),
+
+    /// Re-read tags from disk for the selected files only (no full scan).
+    RescanSelected,
+    RescanSelectedFinished(Vec<(TrackId, TrackRow)>),
+
+    /// Number the selected tracks 1..n in album order (per disc) and write them.
+    AutoNumberSelected,
+    /// Album detail "Album actions" → "Renumber tracks": 1..n per disc, then save.
+    ResequenceAlbum(AlbumKey),
+    ToggleAlbumActions,
+    AutoNumberFinished(Result<Vec<(TrackId, TrackRow)>, String>),
+
+    // Find & replace (library-wide)
+    OpenFindReplace,
+    CloseFindReplace,
+    FindReplaceFieldChanged(InspectorField),
+    FindReplaceFindChanged(String),
+    FindReplaceReplaceChanged(String),
+    FindReplaceRegexToggled(bool),
+    /// List the matches (before -> after) without writing anything.
+    PreviewFindReplace,
+    /// Rewrite `field` in every track whose value matches `find`, as one batch write.
+    FindReplace {
+        field: InspectorField,
+        find: String,
+        replace: String,
+        regex: bool,
+    },
+    FindReplaceFinished(Result<Vec<(TrackId, TrackRow)>, String>),
+
+    /// Set a track's star rating (raw POPM byte) and write it to the file right away.
+    SetRating(TrackId, u8),
+    /// (track, rating before the change, write result) — the old value is restored on error.
+    RatingWritten(TrackId, Option<u8>, Result<(), String>),
+    /// Heart button: flip the favorite flag (written immediately).
+    ToggleFavorite(TrackId),
+    /// Favorite write result; carries the previous flag to revert on failure.
+    FavoriteWritten(TrackId, bool, Result<(), String>),
+    RatingEmailChanged(String),
+
+    /// Ask for confirmation, then emit `StripTags` for the selection.
+    ConfirmStripTags,
+    /// Remove all tags from these files (then re-read them).
+    StripTags(Vec<TrackId>),
+    /// Per-track outcome: the re-read row, or why stripping failed.
+    StripTagsFinished(Vec<(TrackId, Result<TrackRow, String>)>),
+
+    // MusicBrainz
+    /// Look up the selected track on MusicBrainz and fill empty draft fields.
+    LookupMusicBrainz,
+    MusicBrainzResult(TrackId, Result<Option<MbRelease>, String>),
+    /// Fingerprint the file, match it on AcoustID, then look the recording up on MusicBrainz.
+    FingerprintAndLookup(TrackId),
+    AcoustIdApiKeyChanged(String),
+
+    // Duplicates
+    /// Analyze the library for duplicates and switch to the Duplicates view.
+    FindDuplicates,
+    ToggleDuplicateGroup(usize),
+    /// Toggle a track's "move to trash" mark.
+    MarkForDeletion(TrackId),
+    /// Move every marked file to the trash staging folder.
+    DeleteMarked,
+    /// Per-track outcome: where the file went in the trash, or why it didn't.
+    DeleteMarkedFinished(Vec<(TrackId, Result<PathBuf, String>)>),
+
+    // Missing tags report
+    FindMissingTags,
+    CloseMissingTags,
+    CheckAlbums,
+    CloseAlbumCheck,
+    /// Stat every track's path in the background.
+    CheckMissingFiles,
+    MissingFilesFound(Vec<TrackId>),
+    /// Drop the tracks found missing from the library (files are already gone).
+    RemoveMissingTracks,
+    /// Album check row clicked: show that album expanded in Album View.
+    OpenAlbumIssue(AlbumKey),
+    ShowStats,
+    CloseStats,
+    /// Window close requested: save the play history, then exit.
+    AppClosing,
+
+    // File operations
+    /// Open the OS file manager on the track's folder.
+    ShowInFolder(TrackId),
+    /// Hand these files to the configured external tag editor (mp3tag, beets, ...).
+    OpenWithExternalEditor(Vec<TrackId>),
+    ExternalEditorChanged(String),
+    RenameTemplateChanged(String),
+    /// Rename selected files from a tag template: (template, dry_run).
+    RenameFromTemplate(String, bool),
+    /// Per-track outcome: destination path (== old path when unchanged) or error.
+    RenameFinished(bool, Vec<(TrackId, Result<PathBuf, String>)>),
+
+    ToggleOrganizePanel,
+    OrganizeDestChanged(String),
+    PickOrganizeDest,
+    OrganizeTemplateChanged(String),
+    OrganizeCopyToggled(bool),
+    /// Move/copy the selection (else the whole library) into the organize template.
+    OrganizeFiles,
+    /// (copy, report)
+    OrganizeFinished(bool, Result<OrgReport, String>),
+
+    // Settings / Last.fm
+    ToggleSettingsPanel,
+    LastFmApiKeyChanged(String),
+    LastFmApiSecretChanged(String),
+    /// Start the desktop auth flow: fetch a token, then open the approval page.
+    ConnectLastFm,
+    LastFmTokenReceived(Result<String, String>),
+    /// User approved in the browser: exchange the token for a session key.
+    FinishLastFmAuth,
+    /// (session key, username)
+    LastFmSessionReceived(Result<(String, String), String>),
+    DisconnectLastFm,
+    /// Submit a finished play of this track to Last.fm.
+    TrackScrobble(TrackId),
+    ScrobbleFinished(Result<(), String>),
+
+    // Export
+    /// Ask for a destination, then write the library as CSV.
+    ExportCsv,
+    ExportCsvDone(Result<PathBuf, String>),
+
+    // Config
+    /// Persist preferences to `config.toml` (fired after relevant state changes).
+    SaveConfig,
+    ConfigSaved(Result<(), String>),
 }
@@ -1,15 +1,84 @@
 //! gui/subscription.rs
-//! Poll playback events by emitting a periodic TickPlayback message.
+//! Poll playback events by emitting a periodic TickPlayback message
+//! (plus a faster repaint tick while playing, for the interpolated playhead),
+//! track keyboard modifiers (for Ctrl/Shift-click selection), forward key presses
+//! for shortcuts (mapped in `update/keyboard.rs`), files dropped onto the window,
+//! and folder-watcher events for the library roots (applied in `update/watch.rs`).
+//!
+//! Shortcuts only fire for events no widget captured (e.g. Ctrl+A inside a
+//! text field stays "select text").
 
-use iced::{Subscription, time};
+use iced::futures::channel::mpsc as async_mpsc;
+use iced::futures::{Stream, StreamExt};
+use iced::{Event, Subscription, event, keyboard, time, window};
+use std::path::PathBuf;
 use std::time::Duration;
 
 use super::state::{Message, Sonora};
+use crate::core;
 
 pub(crate) fn subscription(state: &Sonora) -> Subscription<Message> {
+    let keys = event::listen_with(|event, status, _window| match event {
+        Event::Keyboard(keyboard::Event::ModifiersChanged(m)) => Some(Message::ModifiersChanged(m)),
+        Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+            if status == event::Status::Ignored =>
+        {
+            Some(Message::KeyPressed(key, modifiers))
+        }
+        // A focused text field swallows Escape; still let it close an inline edit.
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Escape),
+            ..
+        }) => Some(Message::CancelInlineEdit),
+        // The OS delivers one event per dropped item.
+        Event::Window(window::Event::FileDropped(path)) => Some(Message::DroppedPaths(vec![path])),
+        // `exit_on_close_request` is off so the play history can be saved first.
+        Event::Window(window::Event::CloseRequested) => Some(Message::AppClosing),
+        _ => None,
+    });
+
+    // The roots are the subscription's identity: changing them restarts the watch.
+    let watch = if state.roots.is_empty() {
+        Subscription::none()
+    } else {
+        Subscription::run_with(state.roots.clone(), |roots| watch_roots(roots))
+    };
+
     if state.playback_events.is_none() {
-        return Subscription::none();
+        return Subscription::batch([keys, watch]);
     }
 
-    time::every(Duration::from_millis(200)).map(|_| Message::TickPlayback)
+    let tick = time::every(Duration::from_millis(200)).map(|_| Message::TickPlayback);
+
+    // While playing, repaint often so the interpolated playhead looks smooth.
+    let repaint = if state.is_playing && state.seek_preview_ratio.is_none() {
+        time::every(Duration::from_millis(50)).map(|_| Message::Noop)
+    } else {
+        Subscription::none()
+    };
+
+    Subscription::batch([tick, repaint, keys, watch])
+}
+
+/// Folder watcher as a message stream. The stream owns the watcher, so the watch
+/// stops when iced drops the subscription.
+fn watch_roots(roots: &[PathBuf]) -> impl Stream<Item = Message> + use<> {
+    let (tx, rx) = async_mpsc::unbounded::<PathBuf>();
+
+    // On error the sender is dropped with the callback, so the stream just ends.
+    let watcher = match core::watcher::watch_roots(roots, move |path| {
+        let _ = tx.unbounded_send(path);
+    }) {
+        Ok(w) => Some(w),
+        Err(_e) => {
+            #[cfg(debug_assertions)]
+            eprintln!("[WATCH] {_e}");
+            None
+        }
+    };
+
+    rx.map(move |path| {
+        let _keep_alive = &watcher;
+        Message::FolderChanged(path)
+    })
 }
@@ -0,0 +1,182 @@
+//! gui/update/artwork.rs
+//! Embedded artwork edits (pick image -> write APIC -> re-read row),
+//! and extraction (read APIC -> save dialog -> write image file).
+//!
+//! - Dialogs run async (rfd); file IO + tag writes run off-thread.
+//! - The cover cache entry is dropped and reloaded after a successful write.
+//! - Oversized covers are written anyway, with a status hint to shrink them.
+
+use iced::Task;
+use std::path::PathBuf;
+
+use super::super::state::{Message, Sonora};
+use super::super::util::{filename_stem, is_busy};
+use super::selection::maybe_load_cover_for_track;
+use super::util::spawn_blocking;
+use crate::core::fileops::sanitize_component;
+use crate::core::tags::{self, PICTURE_TYPE_FRONT_COVER};
+use crate::core::types::{TrackId, TrackRow};
+
+/// Covers larger than this (either side, px) get a "consider compressing" hint.
+const LARGE_COVER_PX: u32 = 1200;
+
+pub(crate) fn load_artwork_from_file(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    Task::perform(
+        async move {
+            let handle = rfd::AsyncFileDialog::new()
+                .set_title("Choose cover image")
+                .add_filter("Images", &["jpg", "jpeg", "png"])
+                .pick_file()
+                .await?;
+
+            let path: PathBuf = handle.path().to_path_buf();
+            Some(spawn_blocking(move || read_image_file(path)).await)
+        },
+        move |picked| match picked {
+            None => Message::Noop,
+            Some(Ok((bytes, mime))) => Message::SetCoverArt(id, bytes, mime),
+            Some(Err(e)) => Message::CoverArtSaved(id, None, Err(e)),
+        },
+    )
+}
+
+pub(crate) fn set_cover_art(
+    state: &mut Sonora,
+    id: TrackId,
+    bytes: Vec<u8>,
+    mime: String,
+) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let Some(row) = state.track_by_id(id) else {
+        state.status = "Set cover failed: track not found (rescan?).".to_string();
+        return Task::none();
+    };
+
+    let path = row.path.clone();
+    let size = tags::image_dimensions(&bytes);
+    let options = state.write_options;
+
+    state.saving = true;
+    state.status = "Writing cover art...".to_string();
+
+    Task::perform(
+        spawn_blocking(move || {
+            tags::write_embedded_art(&path, bytes, &mime, PICTURE_TYPE_FRONT_COVER, &options)?;
+
+            let (mut r, failed) = tags::read_track_row(path);
+            if failed {
+                return Err("Wrote cover art, but failed to re-read tags".to_string());
+            }
+            r.id = Some(id);
+            Ok(Box::new(r))
+        }),
+        move |res| Message::CoverArtSaved(id, size, res),
+    )
+}
+
+pub(crate) fn cover_art_saved(
+    state: &mut Sonora,
+    id: TrackId,
+    size: Option<(u32, u32)>,
+    result: Result<Box<TrackRow>, String>,
+) -> Task<Message> {
+    state.saving = false;
+
+    match result {
+        Ok(new_row) => {
+            if let Some(slot) = state.track_by_id_mut(id) {
+                *slot = *new_row;
+            }
+            state.status = match size {
+                Some((w, h)) if w > LARGE_COVER_PX || h > LARGE_COVER_PX => format!(
+                    "Cover art written ({w}×{h} px). Consider compressing it to \
+                     {LARGE_COVER_PX}×{LARGE_COVER_PX} or smaller to keep files lean."
+                ),
+                _ => "Cover art written to file.".to_string(),
+            };
+
+            // Force a fresh thumbnail.
+            state.cover_cache.remove(&id);
+            maybe_load_cover_for_track(state, id)
+        }
+        Err(e) => {
+            state.status = format!("Set cover failed: {e}");
+            Task::none()
+        }
+    }
+}
+
+pub(crate) fn extract_artwork(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    let Some(row) = state.track_by_id(id) else {
+        return Task::none();
+    };
+
+    let path = row.path.clone();
+    let base_name = row
+        .album
+        .clone()
+        .unwrap_or_else(|| filename_stem(&row.path));
+
+    Task::perform(
+        async move {
+            let art = spawn_blocking(move || tags::read_embedded_art(&path)).await;
+            let (bytes, mime) = match art {
+                Ok(Some(art)) => art,
+                Ok(None) => return Some(Err("No embedded artwork.".to_string())),
+                Err(e) => return Some(Err(e)),
+            };
+
+            // Trust the bytes over the frame's MIME string (taggers get it wrong).
+            let ext = match tags::sniff_image_mime(&bytes).unwrap_or(mime.as_str()) {
+                "image/png" => "png",
+                _ => "jpg",
+            };
+
+            let handle = rfd::AsyncFileDialog::new()
+                .set_title("Save artwork")
+                .set_file_name(format!("{}.{ext}", sanitize_component(&base_name)))
+                .add_filter("Image", &[ext])
+                .save_file()
+                .await?;
+
+            let dest: PathBuf = handle.path().to_path_buf();
+            Some(
+                spawn_blocking(move || {
+                    std::fs::write(&dest, bytes).map_err(|e| format!("{}: {e}", dest.display()))?;
+                    Ok(dest)
+                })
+                .await,
+            )
+        },
+        |res| match res {
+            None => Message::Noop,
+            Some(r) => Message::ExtractArtworkDone(r),
+        },
+    )
+}
+
+pub(crate) fn extract_artwork_done(
+    state: &mut Sonora,
+    result: Result<PathBuf, String>,
+) -> Task<Message> {
+    state.status = match result {
+        Ok(path) => format!("Artwork saved to {}", path.display()),
+        Err(e) => format!("Save artwork failed: {e}"),
+    };
+    Task::none()
+}
+
+/// Read an image file and identify it as JPEG/PNG by its magic bytes.
+fn read_image_file(path: PathBuf) -> Result<(Vec<u8>, String), String> {
+    let bytes = std::fs::read(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mime = tags::sniff_image_mime(&bytes)
+        .ok_or_else(|| format!("Not a JPEG or PNG image: {}", path.display()))?;
+    Ok((bytes, mime.to_string()))
+}
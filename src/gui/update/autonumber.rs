@@ -0,0 +1,152 @@
+//! gui/update/autonumber.rs
+//! Assign track numbers to the selection (e.g. a CD rip with no TRCK tags), or
+//! resequence a whole album ("Album actions" → "Renumber tracks").
+//!
+//! - Order is the album view's (disc, track, title); each disc restarts at 1,
+//!   and `track_total` becomes that disc's count. No disc number = disc 1.
+//! - Rows are built directly (the inspector draft is not involved), written as one
+//!   batch, and recorded on the undo stack like a batch save.
+//! - Resequencing only writes the tracks whose number or total actually changes.
+
+use iced::Task;
+use std::collections::BTreeSet;
+
+use super::super::state::{AlbumKey, Message, Sonora};
+use super::super::util::{album_order_key, is_busy};
+use super::save::{apply_written_rows, write_and_reread};
+use super::util::spawn_blocking;
+use crate::core::tags::WriteOptions;
+use crate::core::types::{TrackId, TrackRow};
+
+pub(crate) fn auto_number_selected(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    // Reloading the inspector afterwards would silently drop a pending draft.
+    if state.inspector_dirty {
+        state.status = "Save or cancel your edits before auto-numbering.".to_string();
+        return Task::none();
+    }
+
+    let rows: Vec<TrackRow> = state
+        .selected_tracks
+        .iter()
+        .copied()
+        .chain(state.selected_track)
+        .collect::<BTreeSet<TrackId>>()
+        .into_iter()
+        .filter_map(|id| state.track_by_id(id).cloned())
+        .collect();
+
+    if rows.is_empty() {
+        state.status = "Select the tracks to number first.".to_string();
+        return Task::none();
+    }
+
+    let rows_to_write: Vec<(TrackId, TrackRow)> = number_by_disc(rows)
+        .into_iter()
+        .filter_map(|row| row.id.map(|id| (id, row)))
+        .collect();
+
+    start_numbering(state, rows_to_write)
+}
+
+/// "Renumber tracks" for one album: 1..n per disc, writing only the tracks that change.
+pub(crate) fn resequence_album(state: &mut Sonora, key: AlbumKey) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    if state.inspector_dirty {
+        state.status = "Save or cancel your edits before renumbering.".to_string();
+        return Task::none();
+    }
+
+    let rows: Vec<TrackRow> = state
+        .album_groups
+        .get(&key)
+        .into_iter()
+        .flatten()
+        .filter_map(|&id| state.track_by_id(id).cloned())
+        .collect();
+
+    let rows_to_write: Vec<(TrackId, TrackRow)> = number_by_disc(rows)
+        .into_iter()
+        .filter_map(|row| {
+            let id = row.id?;
+            let before = state.track_by_id(id)?;
+            let changed = before.track_no != row.track_no || before.track_total != row.track_total;
+            changed.then_some((id, row))
+        })
+        .collect();
+
+    if rows_to_write.is_empty() {
+        state.status = format!("{}: track numbers are already in order.", key.album);
+        return Task::none();
+    }
+
+    start_numbering(state, rows_to_write)
+}
+
+/// Sort into album order and set `track_no` 1..n / `track_total` n per disc.
+fn number_by_disc(mut rows: Vec<TrackRow>) -> Vec<TrackRow> {
+    let disc = |r: &TrackRow| r.disc_no.unwrap_or(1);
+    rows.sort_by_cached_key(|r| (disc(r), album_order_key(r)));
+
+    // Count per disc first, then number within each disc.
+    let mut disc_counts: Vec<(u32, u32)> = Vec::new();
+    for r in &rows {
+        match disc_counts.last_mut() {
+            Some((d, n)) if *d == disc(r) => *n += 1,
+            _ => disc_counts.push((disc(r), 1)),
+        }
+    }
+
+    let mut out: Vec<TrackRow> = Vec::with_capacity(rows.len());
+    let mut rows = rows.into_iter();
+    for (_, count) in disc_counts {
+        for (no, mut row) in (1..=count).zip(rows.by_ref()) {
+            row.track_no = Some(no);
+            row.track_total = Some(count);
+            out.push(row);
+        }
+    }
+    out
+}
+
+/// Write the numbered rows as one batch (`AutoNumberFinished` applies them).
+fn start_numbering(state: &mut Sonora, rows_to_write: Vec<(TrackId, TrackRow)>) -> Task<Message> {
+    state.saving = true;
+    state.status = format!("Numbering {} tracks...", rows_to_write.len());
+
+    // Only TRCK changes; leave extended frames exactly as they are.
+    let options = WriteOptions {
+        write_extended: false,
+        ..state.write_options
+    };
+
+    Task::perform(
+        spawn_blocking(move || write_and_reread(rows_to_write, &options)),
+        Message::AutoNumberFinished,
+    )
+}
+
+pub(crate) fn auto_number_finished(
+    state: &mut Sonora,
+    result: Result<Vec<(TrackId, TrackRow)>, String>,
+) -> Task<Message> {
+    state.saving = false;
+
+    match result {
+        Ok(rows) => {
+            let n = apply_written_rows(state, rows, "Auto-number");
+            state.status = format!("Numbered {n} tracks.");
+        }
+        Err(e) => {
+            state.status = format!("Auto-number failed: {e}");
+        }
+    }
+
+    Task::none()
+}
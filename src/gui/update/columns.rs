@@ -0,0 +1,72 @@
+//! gui/update/columns.rs
+//! Track table column settings: which columns show, their order, their widths.
+//!
+//! - `visible_columns` is the display order; hiding a column removes it from the list,
+//!   showing it again appends it at the end.
+//! - At least one column stays visible (the table needs something to click).
+//! - Every change is persisted to `config.toml`.
+
+use iced::Task;
+
+use super::super::state::{Direction, MAX_COLUMN_W, MIN_COLUMN_W, Message, Sonora, TrackColumn};
+
+pub(crate) fn toggle_column_config(state: &mut Sonora) -> Task<Message> {
+    state.show_column_config = !state.show_column_config;
+    Task::none()
+}
+
+pub(crate) fn set_column_visible(
+    state: &mut Sonora,
+    column: TrackColumn,
+    visible: bool,
+) -> Task<Message> {
+    let shown = state.visible_columns.contains(&column);
+    if visible && !shown {
+        state.visible_columns.push(column);
+    } else if !visible && shown {
+        if state.visible_columns.len() == 1 {
+            state.status = "At least one column has to stay visible.".into();
+            return Task::none();
+        }
+        state.visible_columns.retain(|c| *c != column);
+    } else {
+        return Task::none();
+    }
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn move_column(
+    state: &mut Sonora,
+    column: TrackColumn,
+    direction: Direction,
+) -> Task<Message> {
+    let Some(i) = state.visible_columns.iter().position(|c| *c == column) else {
+        return Task::none();
+    };
+    let to = match direction {
+        Direction::Up => i.saturating_sub(1),
+        Direction::Down => (i + 1).min(state.visible_columns.len() - 1),
+    };
+    if to == i {
+        return Task::none();
+    }
+    state.visible_columns.swap(i, to);
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn resize_column(state: &mut Sonora, column: TrackColumn, delta: f32) -> Task<Message> {
+    let current = state.column_width(column);
+    let width = (current + delta).clamp(MIN_COLUMN_W, MAX_COLUMN_W);
+    if width == current {
+        return Task::none();
+    }
+    state.column_widths.insert(column, width);
+    Task::done(Message::SaveConfig)
+}
+
+/// Back to every column, default order and widths.
+pub(crate) fn reset_columns(state: &mut Sonora) -> Task<Message> {
+    state.visible_columns = TrackColumn::ALL.to_vec();
+    state.column_widths.clear();
+    Task::done(Message::SaveConfig)
+}
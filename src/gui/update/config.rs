@@ -0,0 +1,54 @@
+//! gui/update/config.rs
+//! Snapshot persisted preferences from state and write them off-thread.
+
+use iced::Task;
+
+use super::super::state::{Message, Sonora};
+use super::util::spawn_blocking;
+use crate::core::config::Config;
+
+/// Build the on-disk config from current state.
+pub(crate) fn config_from_state(state: &Sonora) -> Config {
+    Config {
+        roots: state.roots.clone(),
+        exclusions: state.exclusions.clone(),
+        volume: state.volume,
+        view_mode: state.view_mode.config_key().to_string(),
+        show_extended: state.show_extended,
+        replaygain: state.replaygain_mode.config_key().to_string(),
+        crossfade_ms: state.crossfade_ms,
+        output_device: state.output_device.clone(),
+        scrobbler: state.scrobbler.clone(),
+        acoustid_api_key: state.acoustid_api_key.clone(),
+        external_tag_editor: Some(state.external_tag_editor.trim().to_string())
+            .filter(|s| !s.is_empty()),
+        rating_email: state.rating_email.clone(),
+        tag_version: state.write_options.tag_version.config_key().to_string(),
+        backup_on_write: state.write_options.backup,
+        validate_before_write: state.validate_before_write,
+        album_group_by_year: state.album_group_by_year,
+        compact_mode: state.compact_mode,
+        track_columns: state
+            .visible_columns
+            .iter()
+            .map(|c| c.config_key().to_string())
+            .collect(),
+        column_widths: state
+            .column_widths
+            .iter()
+            .map(|(c, w)| (c.config_key().to_string(), *w))
+            .collect(),
+    }
+}
+
+pub(crate) fn save_config(state: &mut Sonora) -> Task<Message> {
+    let config = config_from_state(state);
+    Task::perform(spawn_blocking(move || config.save()), Message::ConfigSaved)
+}
+
+pub(crate) fn config_saved(state: &mut Sonora, result: Result<(), String>) -> Task<Message> {
+    if let Err(e) = result {
+        state.status = format!("Config save failed: {e}");
+    }
+    Task::none()
+}
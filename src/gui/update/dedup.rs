@@ -0,0 +1,145 @@
+//! gui/update/dedup.rs
+//! Duplicate finder: analyze, mark, and move marked files to the trash.
+//!
+//! - Groups are stored as `TrackId`s (indices would go stale on rescan/save).
+//! - "Delete" is a move into `core::fileops::trash_dir()`; nothing is unlinked.
+
+use iced::Task;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use super::super::state::{Message, Sonora, ViewMode};
+use super::super::util::is_busy;
+use super::inspector::load_inspector_from_selection;
+use super::util::spawn_blocking;
+use crate::core::db::LibraryDb;
+use crate::core::dedup;
+use crate::core::fileops;
+use crate::core::types::TrackId;
+
+/// Recompute `duplicate_groups` from the current library.
+pub(crate) fn refresh_duplicates(state: &mut Sonora) {
+    state.duplicate_groups = dedup::find_duplicates(&state.tracks)
+        .into_iter()
+        .map(|g| g.into_iter().filter_map(|i| state.tracks[i].id).collect())
+        .collect();
+    state.expanded_duplicate_groups.clear();
+
+    // Marks only make sense for tracks still listed as duplicates.
+    let listed: BTreeSet<TrackId> = state.duplicate_groups.iter().flatten().copied().collect();
+    state.marked_for_deletion.retain(|id| listed.contains(id));
+}
+
+pub(crate) fn find_duplicates(state: &mut Sonora) -> Task<Message> {
+    refresh_duplicates(state);
+    state.missing_tag_report = None;
+
+    let n = state.duplicate_groups.len();
+    state.status = match n {
+        0 => "No duplicates found.".to_string(),
+        1 => "Found 1 group of duplicates.".to_string(),
+        _ => format!("Found {n} groups of duplicates."),
+    };
+
+    if state.view_mode == ViewMode::Duplicates {
+        return Task::none();
+    }
+    state.view_mode = ViewMode::Duplicates;
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn toggle_duplicate_group(state: &mut Sonora, i: usize) -> Task<Message> {
+    if !state.expanded_duplicate_groups.remove(&i) {
+        state.expanded_duplicate_groups.insert(i);
+    }
+    Task::none()
+}
+
+pub(crate) fn mark_for_deletion(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    if !state.marked_for_deletion.remove(&id) {
+        state.marked_for_deletion.insert(id);
+    }
+    Task::none()
+}
+
+pub(crate) fn delete_marked(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let targets: Vec<(TrackId, PathBuf)> = state
+        .marked_for_deletion
+        .iter()
+        .filter_map(|&id| state.track_by_id(id).map(|t| (id, t.path.clone())))
+        .collect();
+
+    if targets.is_empty() {
+        state.status = "Nothing marked for deletion.".to_string();
+        return Task::none();
+    }
+
+    state.saving = true;
+    state.status = format!("Moving {} files to the trash...", targets.len());
+
+    Task::perform(
+        spawn_blocking(move || {
+            let results: Vec<(TrackId, Result<PathBuf, String>)> = targets
+                .into_iter()
+                .map(|(id, path)| (id, fileops::move_to_trash(&path)))
+                .collect();
+
+            // Forget trashed files in the cache (best-effort; a rescan would too).
+            let trashed: Vec<TrackId> = results
+                .iter()
+                .filter(|(_, r)| r.is_ok())
+                .map(|(id, _)| *id)
+                .collect();
+            if let Ok(mut db) = LibraryDb::open_default() {
+                let _ = db.delete_ids(&trashed);
+            }
+
+            results
+        }),
+        Message::DeleteMarkedFinished,
+    )
+}
+
+pub(crate) fn delete_marked_finished(
+    state: &mut Sonora,
+    results: Vec<(TrackId, Result<PathBuf, String>)>,
+) -> Task<Message> {
+    state.saving = false;
+
+    let mut trashed: BTreeSet<TrackId> = BTreeSet::new();
+    let mut first_error: Option<String> = None;
+    let mut failed = 0usize;
+
+    for (id, result) in results {
+        match result {
+            Ok(_) => {
+                trashed.insert(id);
+            }
+            Err(e) => {
+                failed += 1;
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    state.remove_tracks(&trashed);
+    refresh_duplicates(state);
+    load_inspector_from_selection(state);
+
+    let dir = fileops::trash_dir()
+        .map(|d| d.display().to_string())
+        .unwrap_or_default();
+    state.status = match first_error {
+        None => format!("Moved {} files to {dir}", trashed.len()),
+        Some(e) => format!(
+            "Moved {} files to {dir}, {failed} failed — {e}",
+            trashed.len()
+        ),
+    };
+
+    Task::none()
+}
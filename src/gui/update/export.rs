@@ -0,0 +1,60 @@
+//! gui/update/export.rs
+//! Library export (save dialog -> background write -> status).
+//!
+//! - The whole library (`state.tracks`) is exported, not just the filtered view.
+
+use iced::Task;
+use std::path::PathBuf;
+
+use super::super::state::{Message, Sonora};
+use super::util::spawn_blocking;
+use crate::core::export::write_tracks_csv;
+use crate::core::types::TrackRow;
+
+pub(crate) fn export_csv(state: &mut Sonora) -> Task<Message> {
+    if state.tracks.is_empty() {
+        state.status = "Nothing to export (scan first).".into();
+        return Task::none();
+    }
+
+    let rows: Vec<TrackRow> = state.tracks.clone();
+
+    Task::perform(
+        async move {
+            let handle = rfd::AsyncFileDialog::new()
+                .set_title("Export library as CSV")
+                .set_file_name("sonora-library.csv")
+                .add_filter("CSV", &["csv"])
+                .save_file()
+                .await?;
+
+            let path: PathBuf = handle.path().to_path_buf();
+            Some(
+                spawn_blocking(move || {
+                    write_tracks_csv(&rows, &path)?;
+                    Ok(path)
+                })
+                .await,
+            )
+        },
+        |res| match res {
+            None => Message::Noop,
+            Some(r) => Message::ExportCsvDone(r),
+        },
+    )
+}
+
+pub(crate) fn export_csv_done(
+    state: &mut Sonora,
+    result: Result<PathBuf, String>,
+) -> Task<Message> {
+    state.status = match result {
+        Ok(path) => format!(
+            "Exported {} tracks to {}",
+            state.tracks.len(),
+            path.display()
+        ),
+        Err(e) => format!("CSV export failed: {e}"),
+    };
+    Task::none()
+}
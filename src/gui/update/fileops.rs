@@ -0,0 +1,414 @@
+//! gui/update/fileops.rs
+//! File operations driven by tags (rename from template, organize into folders),
+//! plus "Show in folder" (open the OS file manager on a track's directory) and
+//! "Open in external editor" (hand the selected files to mp3tag, beets, ...).
+//!
+//! - Rename targets are the selected `TrackId`s; rows are cloned into the worker.
+//! - Organize targets the selection, else the whole library.
+//! - Dry runs go through the same checks, so the preview reports real conflicts.
+//! - Real renames are all-or-nothing (`fileops::rename_batch`): one failure cancels
+//!   the batch and undoes the renames already done.
+//! - Renamed/moved files keep their cache row (and so their `TrackId`).
+
+use iced::Task;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::super::state::{Message, Sonora};
+use super::super::util::{is_busy, split_command_line};
+use super::util::spawn_blocking;
+use crate::core::db::LibraryDb;
+use crate::core::fileops::{self, OrgReport};
+use crate::core::types::{TrackId, TrackRow};
+
+/// Platform file manager launcher (spawned, never awaited).
+#[cfg(target_os = "windows")]
+const FILE_MANAGER: &str = "explorer";
+#[cfg(target_os = "macos")]
+const FILE_MANAGER: &str = "open";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const FILE_MANAGER: &str = "xdg-open";
+
+pub(crate) fn show_in_folder(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    let Some(row) = state.track_by_id(id) else {
+        state.status = "Show in folder failed: track not found (rescan?).".to_string();
+        return Task::none();
+    };
+    let Some(dir) = row.path.parent().map(|p| p.to_path_buf()) else {
+        state.status = format!(
+            "Show in folder failed: {} has no parent",
+            row.path.display()
+        );
+        return Task::none();
+    };
+
+    // Fire-and-forget: the file manager outlives us; only a failed launch is reported.
+    if let Err(e) = std::process::Command::new(FILE_MANAGER).arg(&dir).spawn() {
+        state.status = format!(
+            "Show in folder failed: couldn't run {FILE_MANAGER} for {}: {e}",
+            dir.display()
+        );
+    }
+
+    Task::none()
+}
+
+pub(crate) fn open_with_external_editor(state: &mut Sonora, ids: Vec<TrackId>) -> Task<Message> {
+    let mut command = split_command_line(&state.external_tag_editor).into_iter();
+    let Some(program) = command.next() else {
+        state.status = "Set an external tag editor in Settings first.".to_string();
+        return Task::none();
+    };
+
+    let paths: Vec<PathBuf> = ids
+        .iter()
+        .filter_map(|&id| state.track_by_id(id).map(|t| t.path.clone()))
+        .collect();
+    if paths.is_empty() {
+        return Task::none();
+    }
+
+    // Fire-and-forget like "Show in folder"; the edits land whenever the user saves there.
+    match std::process::Command::new(&program)
+        .args(command)
+        .args(&paths)
+        .spawn()
+    {
+        Ok(_) => {
+            state.status = format!(
+                "Opened {} files in {program}. Use \"Refresh tags\" when you're done there.",
+                paths.len()
+            );
+        }
+        Err(e) => state.status = format!("Couldn't run {program}: {e}"),
+    }
+
+    Task::none()
+}
+
+pub(crate) fn external_editor_changed(state: &mut Sonora, s: String) -> Task<Message> {
+    state.external_tag_editor = s;
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn rename_template_changed(state: &mut Sonora, template: String) -> Task<Message> {
+    state.rename_template = template;
+    Task::none()
+}
+
+pub(crate) fn rename_from_template(
+    state: &mut Sonora,
+    template: String,
+    dry_run: bool,
+) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let ids: Vec<TrackId> = if !state.selected_tracks.is_empty() {
+        state.selected_tracks.iter().copied().collect()
+    } else {
+        state.selected_track.into_iter().collect()
+    };
+
+    let rows: Vec<(TrackId, TrackRow)> = ids
+        .iter()
+        .filter_map(|&id| state.track_by_id(id).map(|r| (id, r.clone())))
+        .collect();
+
+    if rows.is_empty() {
+        state.status = "Select tracks to rename first.".to_string();
+        return Task::none();
+    }
+
+    state.saving = true;
+    state.status = if dry_run {
+        format!("Previewing rename of {} files...", rows.len())
+    } else {
+        format!("Renaming {} files...", rows.len())
+    };
+
+    Task::perform(
+        spawn_blocking(move || rename_rows(rows, &template, dry_run)),
+        move |results| Message::RenameFinished(dry_run, results),
+    )
+}
+
+/// Worker: plan every rename (refusing two tracks that would land on the same name),
+/// then run them as one batch. Any planning error cancels the whole batch.
+fn rename_rows(
+    rows: Vec<(TrackId, TrackRow)>,
+    template: &str,
+    dry_run: bool,
+) -> Vec<(TrackId, Result<PathBuf, String>)> {
+    let mut planned: HashSet<PathBuf> = HashSet::new();
+
+    let mut results: Vec<(TrackId, Result<PathBuf, String>)> = rows
+        .iter()
+        .map(|(id, row)| {
+            let result = fileops::rename_from_template(row, template, true).and_then(|dest| {
+                if dest != row.path && !planned.insert(dest.clone()) {
+                    return Err(format!(
+                        "{} is the target of another selected track",
+                        dest.display()
+                    ));
+                }
+                Ok(dest)
+            });
+            (*id, result)
+        })
+        .collect();
+
+    if dry_run {
+        return results;
+    }
+
+    // Nothing touched yet: a bad plan leaves every file where it is.
+    if results.iter().any(|(_, r)| r.is_err()) {
+        for ((_, result), (_, row)) in results.iter_mut().zip(&rows) {
+            if result.is_ok() {
+                *result = Ok(row.path.clone());
+            }
+        }
+        return results;
+    }
+
+    let ops: fileops::RenameOps = results
+        .iter()
+        .zip(&rows)
+        .filter_map(|((_, result), (_, row))| match result {
+            Ok(dest) if *dest != row.path => Some((row.path.clone(), dest.clone())),
+            _ => None,
+        })
+        .collect();
+
+    let done: HashMap<PathBuf, PathBuf> = match fileops::rename_batch(ops) {
+        Ok(completed) => completed.into_iter().collect(),
+        Err((completed, e)) => {
+            // Rolled back; any rename that couldn't be undone is still on disk.
+            let stuck: HashMap<PathBuf, PathBuf> = completed
+                .into_iter()
+                .filter(|(from, to)| !from.exists() && to.exists())
+                .collect();
+            for ((_, result), (_, row)) in results.iter_mut().zip(&rows) {
+                let wanted_change = matches!(result, Ok(dest) if *dest != row.path);
+                if let Some(to) = stuck.get(&row.path) {
+                    *result = Ok(to.clone());
+                } else if wanted_change {
+                    *result = Err(e.clone());
+                }
+            }
+            stuck
+        }
+    };
+
+    // Cache update is best-effort: a rescan would still find the renamed files.
+    if let Ok(mut db) = LibraryDb::open_default() {
+        for (_, row) in &rows {
+            if let Some(to) = done.get(&row.path) {
+                let mut renamed = row.clone();
+                renamed.path = to.clone();
+                let _ = db.rename_path(&row.path, &renamed);
+            }
+        }
+    }
+
+    results
+}
+
+pub(crate) fn rename_finished(
+    state: &mut Sonora,
+    dry_run: bool,
+    results: Vec<(TrackId, Result<PathBuf, String>)>,
+) -> Task<Message> {
+    state.saving = false;
+
+    let (mut renamed, mut skipped, mut failed) = (0usize, 0usize, 0usize);
+    let mut first_error: Option<String> = None;
+    let mut example: Option<String> = None;
+
+    for (id, result) in results {
+        match result {
+            Ok(dest) => {
+                let Some(row) = state.track_by_id_mut(id) else {
+                    continue;
+                };
+                if dest == row.path {
+                    skipped += 1;
+                    continue;
+                }
+
+                renamed += 1;
+                if example.is_none() {
+                    example = dest.file_name().map(|n| n.to_string_lossy().to_string());
+                }
+                if !dry_run {
+                    row.path = dest;
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    let mut status = if dry_run {
+        format!(
+            "Rename preview: {renamed} would be renamed, {skipped} unchanged, {failed} would fail"
+        )
+    } else if failed == 0 {
+        format!("Renamed {renamed}, skipped {skipped}, failed {failed}")
+    } else if renamed == 0 {
+        format!("Rename cancelled, no files changed ({failed} failed)")
+    } else {
+        // Only when undoing the batch failed too.
+        format!("Rename failed and was only partly undone: {renamed} renamed, {failed} failed")
+    };
+    if dry_run && let Some(name) = example {
+        status.push_str(&format!(" (e.g. {name})"));
+    }
+    if let Some(e) = first_error {
+        status.push_str(&format!(" — {e}"));
+    }
+    state.status = status;
+
+    Task::none()
+}
+
+pub(crate) fn toggle_organize_panel(state: &mut Sonora) -> Task<Message> {
+    state.show_organize = !state.show_organize;
+    Task::none()
+}
+
+pub(crate) fn organize_dest_changed(state: &mut Sonora, dest: String) -> Task<Message> {
+    state.organize_dest = dest;
+    Task::none()
+}
+
+pub(crate) fn pick_organize_dest(_state: &mut Sonora) -> Task<Message> {
+    Task::perform(
+        async {
+            rfd::AsyncFileDialog::new()
+                .set_title("Organize into folder")
+                .pick_folder()
+                .await
+                .map(|h| h.path().display().to_string())
+        },
+        |picked| match picked {
+            None => Message::Noop,
+            Some(dest) => Message::OrganizeDestChanged(dest),
+        },
+    )
+}
+
+pub(crate) fn organize_template_changed(state: &mut Sonora, template: String) -> Task<Message> {
+    state.organize_template = template;
+    Task::none()
+}
+
+pub(crate) fn organize_copy_toggled(state: &mut Sonora, copy: bool) -> Task<Message> {
+    state.organize_copy = copy;
+    Task::none()
+}
+
+pub(crate) fn organize_files(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let dest = state.organize_dest.trim();
+    if dest.is_empty() {
+        state.status = "Choose a destination folder first.".to_string();
+        return Task::none();
+    }
+    let dest_root = PathBuf::from(dest);
+
+    let rows: Vec<TrackRow> = if state.selected_tracks.is_empty() {
+        state.tracks.clone()
+    } else {
+        state
+            .selected_tracks
+            .iter()
+            .filter_map(|&id| state.track_by_id(id).cloned())
+            .collect()
+    };
+
+    if rows.is_empty() {
+        state.status = "Nothing to organize (scan first).".to_string();
+        return Task::none();
+    }
+
+    let template = state.organize_template.clone();
+    let copy = state.organize_copy;
+
+    state.saving = true;
+    state.status = format!(
+        "{} {} files into {}...",
+        if copy { "Copying" } else { "Moving" },
+        rows.len(),
+        dest_root.display()
+    );
+
+    Task::perform(
+        spawn_blocking(move || {
+            let report = fileops::organize_files(&rows, &dest_root, &template, copy)?;
+
+            // Moves keep their cache row (and id); copies are new files for the next scan.
+            if !copy && let Ok(mut db) = LibraryDb::open_default() {
+                let by_path: HashMap<&PathBuf, &TrackRow> =
+                    rows.iter().map(|r| (&r.path, r)).collect();
+                for (from, to) in &report.moved {
+                    if let Some(row) = by_path.get(from) {
+                        let mut row = (*row).clone();
+                        row.path = to.clone();
+                        let _ = db.rename_path(from, &row);
+                    }
+                }
+            }
+
+            Ok(report)
+        }),
+        move |result| Message::OrganizeFinished(copy, result),
+    )
+}
+
+pub(crate) fn organize_finished(
+    state: &mut Sonora,
+    copy: bool,
+    result: Result<OrgReport, String>,
+) -> Task<Message> {
+    state.saving = false;
+
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            state.status = format!("Organize failed: {e}");
+            return Task::none();
+        }
+    };
+
+    if !copy {
+        let moved: HashMap<&PathBuf, &PathBuf> =
+            report.moved.iter().map(|(from, to)| (from, to)).collect();
+        for row in state.tracks.iter_mut() {
+            if let Some(to) = moved.get(&row.path) {
+                row.path = (*to).clone();
+            }
+        }
+    }
+
+    let mut status = format!(
+        "{} {} files ({} already in place, {} failed)",
+        if copy { "Copied" } else { "Moved" },
+        report.moved.len(),
+        report.unchanged,
+        report.failed.len()
+    );
+    if let Some((path, e)) = report.failed.first() {
+        status.push_str(&format!(" — {}: {e}", path.display()));
+    }
+    state.status = status;
+
+    Task::none()
+}
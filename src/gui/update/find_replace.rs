@@ -0,0 +1,246 @@
+//! gui/update/find_replace.rs
+//! Library-wide "Find & replace" on one text field (e.g. "feat." -> "ft." in every artist).
+//!
+//! - Matches are literal substrings, or a regex (`$1` etc. in the replacement) when toggled.
+//! - Preview lists every (before -> after) without writing; any edit clears it.
+//! - Rows are rewritten directly (no inspector draft), written as one batch, and recorded
+//!   on the undo stack like a batch save.
+
+use iced::Task;
+use regex::Regex;
+
+use super::super::state::{FindReplaceDraft, InspectorField, Message, Sonora};
+use super::super::util::{clean_optional_string, is_busy};
+use super::save::{apply_written_rows, write_and_reread};
+use super::util::spawn_blocking;
+use crate::core::tags::WriteOptions;
+use crate::core::types::{TrackId, TrackRow};
+
+/// How `find` is matched against a field value.
+enum Matcher {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn new(find: &str, regex: bool) -> Result<Matcher, String> {
+        if find.is_empty() {
+            return Err("Enter the text to find.".to_string());
+        }
+        if regex {
+            Regex::new(find)
+                .map(Matcher::Regex)
+                .map_err(|e| format!("Invalid regex: {e}"))
+        } else {
+            Ok(Matcher::Literal(find.to_string()))
+        }
+    }
+
+    /// `value` with every match replaced, or `None` if nothing matched.
+    fn apply(&self, value: &str, replace: &str) -> Option<String> {
+        match self {
+            Matcher::Literal(find) => value
+                .contains(find.as_str())
+                .then(|| value.replace(find.as_str(), replace)),
+            Matcher::Regex(re) => re
+                .is_match(value)
+                .then(|| re.replace_all(value, replace).into_owned()),
+        }
+    }
+}
+
+/// The row slot behind each field "Find & replace" offers (`FIND_REPLACE_FIELDS`).
+fn row_field(row: &mut TrackRow, field: InspectorField) -> Option<&mut Option<String>> {
+    match field {
+        InspectorField::Title => Some(&mut row.title),
+        InspectorField::Artist => Some(&mut row.artist),
+        InspectorField::Album => Some(&mut row.album),
+        InspectorField::AlbumArtist => Some(&mut row.album_artist),
+        InspectorField::Composer => Some(&mut row.composer),
+        InspectorField::Genre => Some(&mut row.genre),
+        InspectorField::Grouping => Some(&mut row.grouping),
+        _ => None,
+    }
+}
+
+/// Every track whose `field` changes: (rewritten row, value before, value after).
+fn find_matches(
+    tracks: &[TrackRow],
+    field: InspectorField,
+    matcher: &Matcher,
+    replace: &str,
+) -> Vec<(TrackRow, String, String)> {
+    let mut out = Vec::new();
+
+    for t in tracks {
+        let mut row = t.clone();
+        let Some(slot) = row_field(&mut row, field) else {
+            return Vec::new();
+        };
+        let Some(before) = slot.clone() else {
+            continue;
+        };
+        let Some(after) = matcher.apply(&before, replace) else {
+            continue;
+        };
+        // A regex can match without changing anything (e.g. replacing "x" with "x").
+        if after == before {
+            continue;
+        }
+
+        *slot = clean_optional_string(&after);
+        out.push((row, before, after));
+    }
+
+    out
+}
+
+fn draft(state: &mut Sonora) -> Option<&mut FindReplaceDraft> {
+    state.find_replace.as_mut()
+}
+
+pub(crate) fn open_find_replace(state: &mut Sonora) -> Task<Message> {
+    if state.find_replace.is_none() {
+        state.find_replace = Some(FindReplaceDraft::default());
+    }
+    Task::none()
+}
+
+pub(crate) fn close_find_replace(state: &mut Sonora) -> Task<Message> {
+    state.find_replace = None;
+    Task::none()
+}
+
+pub(crate) fn field_changed(state: &mut Sonora, field: InspectorField) -> Task<Message> {
+    if let Some(d) = draft(state) {
+        d.field = field;
+        d.preview = None;
+    }
+    Task::none()
+}
+
+pub(crate) fn find_changed(state: &mut Sonora, find: String) -> Task<Message> {
+    if let Some(d) = draft(state) {
+        d.find = find;
+        d.preview = None;
+    }
+    Task::none()
+}
+
+pub(crate) fn replace_changed(state: &mut Sonora, replace: String) -> Task<Message> {
+    if let Some(d) = draft(state) {
+        d.replace = replace;
+        d.preview = None;
+    }
+    Task::none()
+}
+
+pub(crate) fn regex_toggled(state: &mut Sonora, on: bool) -> Task<Message> {
+    if let Some(d) = draft(state) {
+        d.regex = on;
+        d.preview = None;
+    }
+    Task::none()
+}
+
+pub(crate) fn preview_find_replace(state: &mut Sonora) -> Task<Message> {
+    let Some(d) = state.find_replace.as_ref() else {
+        return Task::none();
+    };
+
+    let matcher = match Matcher::new(&d.find, d.regex) {
+        Ok(m) => m,
+        Err(e) => {
+            state.status = e;
+            return Task::none();
+        }
+    };
+
+    let preview: Vec<(TrackId, String, String)> =
+        find_matches(&state.tracks, d.field, &matcher, &d.replace)
+            .into_iter()
+            .filter_map(|(row, before, after)| row.id.map(|id| (id, before, after)))
+            .collect();
+
+    state.status = match preview.len() {
+        0 => "No matches.".to_string(),
+        1 => "1 track would change.".to_string(),
+        n => format!("{n} tracks would change."),
+    };
+    if let Some(d) = draft(state) {
+        d.preview = Some(preview);
+    }
+    Task::none()
+}
+
+pub(crate) fn find_replace(
+    state: &mut Sonora,
+    field: InspectorField,
+    find: String,
+    replace: String,
+    regex: bool,
+) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    // Reloading the inspector afterwards would silently drop a pending draft.
+    if state.inspector_dirty {
+        state.status = "Save or cancel your edits before replacing.".to_string();
+        return Task::none();
+    }
+
+    let matcher = match Matcher::new(&find, regex) {
+        Ok(m) => m,
+        Err(e) => {
+            state.status = e;
+            return Task::none();
+        }
+    };
+
+    let rows_to_write: Vec<(TrackId, TrackRow)> =
+        find_matches(&state.tracks, field, &matcher, &replace)
+            .into_iter()
+            .filter_map(|(row, _, _)| row.id.map(|id| (id, row)))
+            .collect();
+
+    if rows_to_write.is_empty() {
+        state.status = "No matches.".to_string();
+        return Task::none();
+    }
+
+    state.saving = true;
+    state.status = format!("Replacing in {} tracks...", rows_to_write.len());
+
+    // Only the standard text frames change; leave extended frames exactly as they are.
+    let options = WriteOptions {
+        write_extended: false,
+        ..state.write_options
+    };
+
+    Task::perform(
+        spawn_blocking(move || write_and_reread(rows_to_write, &options)),
+        Message::FindReplaceFinished,
+    )
+}
+
+pub(crate) fn find_replace_finished(
+    state: &mut Sonora,
+    result: Result<Vec<(TrackId, TrackRow)>, String>,
+) -> Task<Message> {
+    state.saving = false;
+
+    match result {
+        Ok(rows) => {
+            let n = apply_written_rows(state, rows, "Find & replace");
+            state.find_replace = None;
+            state.status = format!("Replaced in {n} tracks.");
+        }
+        Err(e) => {
+            // Earlier files in the batch may be written already; a rescan shows them.
+            state.status = format!("Find & replace failed: {e}");
+        }
+    }
+
+    Task::none()
+}
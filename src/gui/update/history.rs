@@ -0,0 +1,176 @@
+//! gui/update/history.rs
+//! Undo / redo for tag writes.
+//!
+//! - Each successful save pushes the rows as they were *before* the write.
+//! - Undo writes that snapshot back to disk (all fields, extended included),
+//!   re-reads the files, and pushes the state it replaced onto the redo stack.
+//! - Only tag text is covered: artwork and file moves are not undoable.
+//! - The undo stack is written to this session's undo file whenever it changes
+//!   (`core::undo_log`), so a crash doesn't lose it. The redo stack isn't kept.
+
+use iced::Task;
+
+use super::super::state::{Message, Sonora, UndoEntry};
+use super::super::util::is_busy;
+use super::inspector::load_inspector_from_selection;
+use super::util::spawn_blocking;
+use crate::core::tags::WriteOptions;
+use crate::core::types::{TrackId, TrackRow};
+use crate::core::undo_log;
+
+/// Snapshots kept per stack (oldest dropped first).
+pub(crate) const UNDO_MAX: usize = undo_log::MAX_STEPS;
+
+/// Record pre-edit rows for a write that just succeeded. Clears the redo stack.
+pub(crate) fn push_undo(state: &mut Sonora, label: String, rows: Vec<TrackRow>) {
+    if rows.is_empty() {
+        return;
+    }
+    push_capped(&mut state.undo_stack, UndoEntry { label, rows });
+    state.redo_stack.clear();
+    persist_undo(state);
+}
+
+/// Best-effort write of the undo stack to this session's undo file.
+fn persist_undo(state: &Sonora) {
+    let Some(path) = &state.undo_log_path else {
+        return;
+    };
+    let steps = state
+        .undo_stack
+        .iter()
+        .map(|e| (e.label.as_str(), e.rows.as_slice()));
+    if let Err(_e) = undo_log::save(path, steps) {
+        #[cfg(debug_assertions)]
+        eprintln!("[UNDO] save failed: {_e}");
+    }
+}
+
+fn push_capped(stack: &mut Vec<UndoEntry>, entry: UndoEntry) {
+    stack.push(entry);
+    if stack.len() > UNDO_MAX {
+        stack.remove(0);
+    }
+}
+
+pub(crate) fn undo(state: &mut Sonora) -> Task<Message> {
+    apply(state, false)
+}
+
+pub(crate) fn redo(state: &mut Sonora) -> Task<Message> {
+    apply(state, true)
+}
+
+fn apply(state: &mut Sonora, redo: bool) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let stack = if redo {
+        &mut state.redo_stack
+    } else {
+        &mut state.undo_stack
+    };
+    let Some(entry) = stack.pop() else {
+        state.status = if redo {
+            "Nothing to redo."
+        } else {
+            "Nothing to undo."
+        }
+        .to_string();
+        return Task::none();
+    };
+
+    // What we're about to overwrite becomes the opposite step.
+    let inverse = UndoEntry {
+        label: entry.label.clone(),
+        rows: entry
+            .rows
+            .iter()
+            .filter_map(|r| r.id.and_then(|id| state.track_by_id(id)).cloned())
+            .collect(),
+    };
+
+    state.saving = true;
+    state.status = format!(
+        "{} {}...",
+        if redo { "Redoing" } else { "Undoing" },
+        entry.label
+    );
+
+    let rows = entry.rows.clone();
+    let options = WriteOptions {
+        write_extended: true,
+        ..state.write_options
+    };
+    Task::perform(
+        spawn_blocking(move || write_rows(rows, &options)),
+        move |result| Message::HistoryApplied(redo, entry, inverse, result),
+    )
+}
+
+/// Write each row back (all fields) and re-read it; stops at the first failure.
+fn write_rows(
+    rows: Vec<TrackRow>,
+    options: &WriteOptions,
+) -> Result<Vec<(TrackId, TrackRow)>, String> {
+    let mut out: Vec<(TrackId, TrackRow)> = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let Some(id) = row.id else { continue };
+
+        crate::core::tags::write_track_row(&row, options)
+            .map_err(|e| format!("Write failed for {}: {e}", row.path.display()))?;
+
+        let (mut r, _failed) = crate::core::tags::read_track_row(row.path.clone());
+        r.id = Some(id);
+        out.push((id, r));
+    }
+
+    Ok(out)
+}
+
+pub(crate) fn history_applied(
+    state: &mut Sonora,
+    was_redo: bool,
+    applied: UndoEntry,
+    inverse: UndoEntry,
+    result: Result<Vec<(TrackId, TrackRow)>, String>,
+) -> Task<Message> {
+    state.saving = false;
+
+    match result {
+        Ok(rows) => {
+            for (id, row) in rows {
+                if let Some(slot) = state.track_by_id_mut(id) {
+                    *slot = row;
+                }
+            }
+            state.rebuild_library_caches();
+            load_inspector_from_selection(state);
+
+            state.status = format!(
+                "{}: {}",
+                if was_redo { "Redone" } else { "Undone" },
+                applied.label
+            );
+            if was_redo {
+                push_capped(&mut state.undo_stack, inverse);
+            } else {
+                push_capped(&mut state.redo_stack, inverse);
+            }
+            persist_undo(state);
+        }
+        Err(e) => {
+            // Some files may already be rewritten; keep the step so it can be retried.
+            state.status = format!("{} failed: {e}", if was_redo { "Redo" } else { "Undo" });
+            if was_redo {
+                state.redo_stack.push(applied);
+            } else {
+                state.undo_stack.push(applied);
+            }
+        }
+    }
+
+    Task::none()
+}
@@ -0,0 +1,125 @@
+//! gui/update/inline_edit.rs
+//! Edit a track-table cell in place (double-click the title, Enter to save, Escape to cancel).
+//!
+//! - Writes immediately (no inspector draft involved), standard fields only.
+//! - Recorded on the undo stack like any other save.
+//! - Refused while the inspector has unsaved edits, since the save reloads it.
+
+use iced::Task;
+use iced::widget::operation;
+
+use super::super::state::{INLINE_EDIT_ID, InspectorField, Message, Sonora};
+use super::super::util::{clean_optional_string, is_busy};
+use super::save::{apply_written_rows, write_and_reread};
+use super::util::spawn_blocking;
+use crate::core::tags::WriteOptions;
+use crate::core::types::{TrackId, TrackRow};
+
+/// The row slot behind each field that can be edited in place (the title, for now).
+fn row_field(row: &mut TrackRow, field: InspectorField) -> Option<&mut Option<String>> {
+    match field {
+        InspectorField::Title => Some(&mut row.title),
+        _ => None,
+    }
+}
+
+pub(crate) fn begin_inline_edit(
+    state: &mut Sonora,
+    id: TrackId,
+    field: InspectorField,
+) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+    if state.inspector_dirty {
+        state.status = "Save or cancel your edits before editing in place.".to_string();
+        return Task::none();
+    }
+
+    let Some(mut row) = state.track_by_id(id).cloned() else {
+        return Task::none();
+    };
+    let Some(slot) = row_field(&mut row, field) else {
+        return Task::none();
+    };
+
+    state.inline_edit_value = slot.clone().unwrap_or_default();
+    state.inline_edit = Some((id, field));
+
+    Task::batch([
+        operation::focus(INLINE_EDIT_ID),
+        operation::move_cursor_to_end(INLINE_EDIT_ID),
+    ])
+}
+
+pub(crate) fn inline_edit_changed(state: &mut Sonora, value: String) -> Task<Message> {
+    state.inline_edit_value = value;
+    Task::none()
+}
+
+pub(crate) fn cancel_inline_edit(state: &mut Sonora) -> Task<Message> {
+    close_inline_edit(state);
+    Task::none()
+}
+
+fn close_inline_edit(state: &mut Sonora) {
+    state.inline_edit = None;
+    state.inline_edit_value.clear();
+}
+
+pub(crate) fn commit_inline_edit(
+    state: &mut Sonora,
+    id: TrackId,
+    field: InspectorField,
+    value: String,
+) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+    close_inline_edit(state);
+
+    let Some(mut row) = state.track_by_id(id).cloned() else {
+        return Task::none();
+    };
+    let Some(slot) = row_field(&mut row, field) else {
+        return Task::none();
+    };
+
+    let new_value = clean_optional_string(&value);
+    if *slot == new_value {
+        return Task::none();
+    }
+    *slot = new_value;
+
+    state.saving = true;
+    state.status = "Writing tags to file...".to_string();
+
+    let options = WriteOptions {
+        write_extended: false,
+        ..state.write_options
+    };
+
+    Task::perform(
+        spawn_blocking(move || write_and_reread(vec![(id, row)], &options)),
+        Message::InlineEditSaved,
+    )
+}
+
+pub(crate) fn inline_edit_saved(
+    state: &mut Sonora,
+    result: Result<Vec<(TrackId, TrackRow)>, String>,
+) -> Task<Message> {
+    state.saving = false;
+
+    match result {
+        Ok(rows) => {
+            apply_written_rows(state, rows, "Inline edit");
+            state.status = "Tags written to file.".to_string();
+        }
+        Err(e) => {
+            state.status = format!("Save failed: {e}");
+        }
+    }
+
+    Task::none()
+}
@@ -7,13 +7,16 @@
 use iced::Task;
 use std::collections::BTreeMap;
 
-use super::super::state::{InspectorField, KEEP_SENTINEL, Message, Sonora};
+use super::super::state::{
+    CommentFrameDraft, CommentPart, InspectorField, KEEP_SENTINEL, Message, Sonora,
+};
 use super::super::util::filename_stem;
-use crate::core::types::TrackId;
+use crate::core::tags::is_internal_user_text;
+use crate::core::types::{TrackId, TrackRow};
 
 pub(crate) fn toggle_extended(state: &mut Sonora, v: bool) -> Task<Message> {
     state.show_extended = v;
-    Task::none()
+    Task::done(Message::SaveConfig)
 }
 
 pub(crate) fn inspector_changed(
@@ -32,6 +35,107 @@ pub(crate) fn inspector_changed(
     Task::none()
 }
 
+/// Blank a field. In batch mode this drops `<keep>` too, so the save removes the
+/// tag from every selected file ("" = `None` for `write_track_row`).
+pub(crate) fn clear_field(state: &mut Sonora, field: InspectorField) -> Task<Message> {
+    state.inspector_mixed.insert(field, false);
+    set_inspector_field(state, field, String::new());
+    state.inspector_dirty = true;
+    Task::none()
+}
+
+/// Tag clipboard: remember `fields` of track `id` (as the inspector would show them).
+pub(crate) fn copy_tags(
+    state: &mut Sonora,
+    id: TrackId,
+    fields: Vec<InspectorField>,
+) -> Task<Message> {
+    let Some(t) = state.track_by_id(id) else {
+        return Task::none();
+    };
+    let clipboard: BTreeMap<InspectorField, String> = fields
+        .into_iter()
+        .map(|field| (field, track_field_value(t, field)))
+        .collect();
+
+    let title = t.title.clone().unwrap_or_else(|| filename_stem(&t.path));
+    state.status = format!("Copied {} tag(s) from \"{title}\".", clipboard.len());
+    state.tag_clipboard = Some(clipboard);
+    Task::none()
+}
+
+/// Tag clipboard: overwrite the draft's fields with the copied values (for every
+/// selected track; nothing is written until "Save edits").
+pub(crate) fn paste_tags(state: &mut Sonora) -> Task<Message> {
+    let Some(clipboard) = state.tag_clipboard.clone().filter(|c| !c.is_empty()) else {
+        return Task::none();
+    };
+    if state.selected_track.is_none() && state.selected_tracks.is_empty() {
+        return Task::none();
+    }
+
+    let count = clipboard.len();
+    for (field, value) in clipboard {
+        state.inspector_mixed.insert(field, false);
+        set_inspector_field(state, field, value);
+    }
+    state.inspector_dirty = true;
+    state.status = format!("Pasted {count} tag(s). Review, then Save.");
+    Task::none()
+}
+
+/// A track's value for `field`, formatted like the inspector draft ("" = unset).
+fn track_field_value(t: &TrackRow, field: InspectorField) -> String {
+    fn s(v: &Option<String>) -> String {
+        v.clone().unwrap_or_default()
+    }
+    fn n<T: ToString>(v: Option<T>) -> String {
+        v.map(|n| n.to_string()).unwrap_or_default()
+    }
+
+    match field {
+        InspectorField::Title => s(&t.title),
+        InspectorField::Artist => s(&t.artist),
+        InspectorField::Album => s(&t.album),
+        InspectorField::AlbumArtist => s(&t.album_artist),
+        InspectorField::Composer => s(&t.composer),
+
+        InspectorField::TrackNo => n(t.track_no),
+        InspectorField::TrackTotal => n(t.track_total),
+        InspectorField::DiscNo => n(t.disc_no),
+        InspectorField::DiscTotal => n(t.disc_total),
+
+        InspectorField::Year => n(t.year),
+        InspectorField::Genre => s(&t.genre),
+
+        InspectorField::Grouping => s(&t.grouping),
+        InspectorField::Comment => s(&t.comment),
+        InspectorField::Lyrics => s(&t.lyrics),
+        InspectorField::Lyricist => s(&t.lyricist),
+
+        InspectorField::Date => s(&t.date),
+        InspectorField::Conductor => s(&t.conductor),
+        InspectorField::Remixer => s(&t.remixer),
+        InspectorField::Publisher => s(&t.publisher),
+        InspectorField::Subtitle => s(&t.subtitle),
+
+        InspectorField::Bpm => n(t.bpm),
+        InspectorField::Key => s(&t.key),
+        InspectorField::Mood => s(&t.mood),
+        InspectorField::Language => s(&t.language),
+        InspectorField::Isrc => s(&t.isrc),
+        InspectorField::EncoderSettings => s(&t.encoder_settings),
+        InspectorField::EncodedBy => s(&t.encoded_by),
+        InspectorField::Copyright => s(&t.copyright),
+        InspectorField::TrimDb => n(t.volume_trim_db),
+
+        InspectorField::TitleSort => s(&t.title_sort),
+        InspectorField::ArtistSort => s(&t.artist_sort),
+        InspectorField::AlbumSort => s(&t.album_sort),
+        InspectorField::AlbumArtistSort => s(&t.album_artist_sort),
+    }
+}
+
 /// Update a single inspector string field based on `InspectorField`.
 fn set_inspector_field(state: &mut Sonora, field: InspectorField, value: String) {
     match field {
@@ -70,7 +174,84 @@ fn set_inspector_field(state: &mut Sonora, field: InspectorField, value: String)
         InspectorField::EncoderSettings => state.inspector.encoder_settings = value,
         InspectorField::EncodedBy => state.inspector.encoded_by = value,
         InspectorField::Copyright => state.inspector.copyright = value,
+        InspectorField::TrimDb => state.inspector.trim_db = value,
+
+        InspectorField::TitleSort => state.inspector.title_sort = value,
+        InspectorField::ArtistSort => state.inspector.artist_sort = value,
+        InspectorField::AlbumSort => state.inspector.album_sort = value,
+        InspectorField::AlbumArtistSort => state.inspector.album_artist_sort = value,
+    }
+}
+
+pub(crate) fn add_user_text(state: &mut Sonora, desc: String, value: String) -> Task<Message> {
+    let desc = desc.trim().to_string();
+    if desc.is_empty() {
+        return Task::none();
+    }
+    if is_internal_user_text(&desc) {
+        state.status = format!("\"{desc}\" is managed by Sonora; pick another description.");
+        return Task::none();
+    }
+
+    // Submitted from the "add" row -> reset it.
+    if state.inspector.new_user_text_desc.trim() == desc {
+        state.inspector.new_user_text_desc.clear();
+        state.inspector.new_user_text_value.clear();
+    }
+
+    state.inspector.user_text.insert(desc, value);
+    state.inspector_dirty = true;
+    Task::none()
+}
+
+pub(crate) fn remove_user_text(state: &mut Sonora, desc: String) -> Task<Message> {
+    if state.inspector.user_text.remove(&desc).is_some() {
+        state.inspector_dirty = true;
+    }
+    Task::none()
+}
+
+pub(crate) fn new_user_text_changed(
+    state: &mut Sonora,
+    desc: String,
+    value: String,
+) -> Task<Message> {
+    state.inspector.new_user_text_desc = desc;
+    state.inspector.new_user_text_value = value;
+    Task::none()
+}
+
+pub(crate) fn comment_draft_changed(
+    state: &mut Sonora,
+    i: usize,
+    part: CommentPart,
+    value: String,
+) -> Task<Message> {
+    let Some(c) = state.inspector.comments.get_mut(i) else {
+        return Task::none();
+    };
+
+    match part {
+        CommentPart::Lang => c.lang = value,
+        CommentPart::Description => c.description = value,
+        CommentPart::Text => c.text = value,
+    }
+    state.inspector_dirty = true;
+    Task::none()
+}
+
+pub(crate) fn add_comment_draft(state: &mut Sonora) -> Task<Message> {
+    state.inspector.comments.push(CommentFrameDraft::default());
+    state.inspector_dirty = true;
+    Task::none()
+}
+
+pub(crate) fn remove_comment_draft(state: &mut Sonora, i: usize) -> Task<Message> {
+    if i < state.inspector.comments.len() {
+        state.inspector.comments.remove(i);
+        state.inspector_dirty = true;
     }
+    Task::none()
 }
 
 pub(crate) fn clear_inspector(state: &mut Sonora) {
@@ -205,6 +386,33 @@ pub(crate) fn load_inspector_from_selection(state: &mut Sonora) {
         .map(|&i| opt_str(&state.tracks[i].lyricist))
         .collect();
 
+    let title_sort: Vec<String> = idxs
+        .iter()
+        .map(|&i| opt_str(&state.tracks[i].title_sort))
+        .collect();
+    let artist_sort: Vec<String> = idxs
+        .iter()
+        .map(|&i| opt_str(&state.tracks[i].artist_sort))
+        .collect();
+    let album_sort: Vec<String> = idxs
+        .iter()
+        .map(|&i| opt_str(&state.tracks[i].album_sort))
+        .collect();
+    let album_artist_sort: Vec<String> = idxs
+        .iter()
+        .map(|&i| opt_str(&state.tracks[i].album_artist_sort))
+        .collect();
+
+    let trim_db: Vec<String> = idxs
+        .iter()
+        .map(|&i| {
+            state.tracks[i]
+                .volume_trim_db
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+
     // Apply + compute mixed flags
     let mut map_mixed: BTreeMap<InspectorField, bool> = BTreeMap::new();
 
@@ -302,8 +510,78 @@ pub(crate) fn load_inspector_from_selection(state: &mut Sonora) {
         lyricist,
     );
 
+    // Sort fields are aggregated like the standard ones (saving never wipes them).
+    apply_field(
+        &mut state.inspector.title_sort,
+        &mut map_mixed,
+        InspectorField::TitleSort,
+        title_sort,
+    );
+    apply_field(
+        &mut state.inspector.artist_sort,
+        &mut map_mixed,
+        InspectorField::ArtistSort,
+        artist_sort,
+    );
+    apply_field(
+        &mut state.inspector.album_sort,
+        &mut map_mixed,
+        InspectorField::AlbumSort,
+        album_sort,
+    );
+    apply_field(
+        &mut state.inspector.album_artist_sort,
+        &mut map_mixed,
+        InspectorField::AlbumArtistSort,
+        album_artist_sort,
+    );
+    apply_field(
+        &mut state.inspector.trim_db,
+        &mut map_mixed,
+        InspectorField::TrimDb,
+        trim_db,
+    );
+
     state.inspector_mixed = map_mixed;
 
+    // Custom TXXX frames are edited per file; a batch leaves each file's own.
+    state.inspector.user_text = match idxs.as_slice() {
+        [i] => state.tracks[*i]
+            .user_text
+            .iter()
+            .filter(|(k, _)| !is_internal_user_text(k))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        _ => BTreeMap::new(),
+    };
+    state.inspector.new_user_text_desc.clear();
+    state.inspector.new_user_text_value.clear();
+
+    // MusicBrainz ids identify one recording; a batch keeps each file's own.
+    let (mb_recording_id, mb_release_id) = match idxs.as_slice() {
+        [i] => (
+            opt_str(&state.tracks[*i].mb_recording_id),
+            opt_str(&state.tracks[*i].mb_release_id),
+        ),
+        _ => (String::new(), String::new()),
+    };
+    state.inspector.mb_recording_id = mb_recording_id;
+    state.inspector.mb_release_id = mb_release_id;
+
+    // Same for comment frames: the full list is only editable for one file.
+    state.inspector.comments = match idxs.as_slice() {
+        [i] => state.tracks[*i]
+            .comments
+            .iter()
+            .map(|c| CommentFrameDraft {
+                lang: c.lang.clone(),
+                description: c.description.clone(),
+                text: c.text.clone(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
     // Avoid stale extended values until you implement mixed/aggregation for them.
     state.inspector.date.clear();
     state.inspector.conductor.clear();
@@ -0,0 +1,49 @@
+//! gui/update/keyboard.rs
+//! Keyboard shortcuts.
+//!
+//! - The subscription only forwards key presses no widget captured, so typing
+//!   in a text field (Space, arrows, Ctrl+A/Z) never triggers these.
+//! - Shortcuts map to existing messages and go through the normal router.
+
+use iced::Task;
+use iced::keyboard::{Key, Modifiers, key::Named};
+
+use super::super::state::{Message, Sonora};
+
+pub(crate) fn key_pressed(state: &mut Sonora, key: Key, modifiers: Modifiers) -> Task<Message> {
+    // Escape closes an inline cell edit before it means "revert the inspector".
+    if key == Key::Named(Named::Escape) && state.inline_edit.is_some() {
+        return super::update(state, Message::CancelInlineEdit);
+    }
+
+    match shortcut(&key, modifiers) {
+        Some(message) => super::update(state, message),
+        None => Task::none(),
+    }
+}
+
+fn shortcut(key: &Key, modifiers: Modifiers) -> Option<Message> {
+    let cmd = modifiers.command();
+
+    match key.as_ref() {
+        // Playback
+        Key::Named(Named::Space) if !cmd => Some(Message::TogglePlayPause),
+        Key::Named(Named::ArrowRight) if !cmd => Some(Message::Next),
+        Key::Named(Named::ArrowLeft) if !cmd => Some(Message::Prev),
+
+        // Editing
+        // (Shift changes the reported character, so match both cases.)
+        Key::Character("s" | "S") if cmd => Some(Message::SaveInspectorToFile),
+        Key::Character("z" | "Z") if cmd && modifiers.shift() => Some(Message::Redo),
+        Key::Character("z" | "Z") if cmd => Some(Message::Undo),
+        Key::Character("y" | "Y") if cmd => Some(Message::Redo),
+        Key::Named(Named::Escape) => Some(Message::RevertInspector),
+
+        // Selection
+        Key::Character("a") if cmd => Some(Message::SelectAll),
+        Key::Named(Named::ArrowUp) if !cmd => Some(Message::SelectPrevTrack),
+        Key::Named(Named::ArrowDown) if !cmd => Some(Message::SelectNextTrack),
+
+        _ => None,
+    }
+}
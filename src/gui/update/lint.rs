@@ -0,0 +1,133 @@
+//! gui/update/lint.rs
+//! "Missing tags" and "Check albums" reports, plus the "Statistics" overview (open / close).
+//! Also "Check files": marks tracks whose file has moved or been deleted since the scan.
+//!
+//! - Reports are computed synchronously (a linear pass over in-memory rows).
+//! - The file check stats every path, so it runs on a blocking thread instead.
+//! - While open, `rebuild_library_caches` recomputes them, so fixed tracks drop out.
+//! - All three use the center panel, so opening one closes the others.
+
+use iced::Task;
+use std::collections::BTreeSet;
+
+use super::super::state::{AlbumKey, Message, Sonora, ViewMode};
+use super::super::util::is_busy;
+use super::dedup::refresh_duplicates;
+use super::inspector::load_inspector_from_selection;
+use super::selection::select_album;
+use super::util::spawn_blocking;
+use crate::core::lint::{self, find_incomplete_albums};
+use crate::core::stats;
+use crate::core::types::TrackId;
+
+pub(crate) fn find_missing_tags(state: &mut Sonora) -> Task<Message> {
+    let report = state.missing_tags();
+
+    state.status = match report.len() {
+        0 => "Every track has complete tags.".to_string(),
+        1 => "1 track is missing tags.".to_string(),
+        n => format!("{n} tracks are missing tags."),
+    };
+    state.missing_tag_report = Some(report);
+    state.album_check_report = None;
+    state.show_stats = false;
+    state.show_queue = false;
+
+    Task::none()
+}
+
+pub(crate) fn close_missing_tags(state: &mut Sonora) -> Task<Message> {
+    state.missing_tag_report = None;
+    Task::none()
+}
+
+pub(crate) fn check_albums(state: &mut Sonora) -> Task<Message> {
+    let report = find_incomplete_albums(&state.tracks);
+
+    state.status = match report.len() {
+        0 => "No gaps or repeated track numbers in any album.".to_string(),
+        1 => "1 album has numbering issues.".to_string(),
+        n => format!("{n} albums have numbering issues."),
+    };
+    state.album_check_report = Some(report);
+    state.missing_tag_report = None;
+    state.show_stats = false;
+    state.show_queue = false;
+
+    Task::none()
+}
+
+pub(crate) fn close_album_check(state: &mut Sonora) -> Task<Message> {
+    state.album_check_report = None;
+    Task::none()
+}
+
+pub(crate) fn open_album_issue(state: &mut Sonora, key: AlbumKey) -> Task<Message> {
+    state.album_check_report = None;
+
+    // `select_album` toggles; an already-open album just stays open.
+    if state.selected_album.as_ref() == Some(&key) {
+        state.view_mode = ViewMode::Albums;
+        return Task::none();
+    }
+    select_album(state, key)
+}
+
+pub(crate) fn show_stats(state: &mut Sonora) -> Task<Message> {
+    state.library_stats = stats::compute(&state.tracks);
+    state.show_stats = true;
+    state.missing_tag_report = None;
+    state.album_check_report = None;
+    state.show_queue = false;
+    Task::none()
+}
+
+pub(crate) fn close_stats(state: &mut Sonora) -> Task<Message> {
+    state.show_stats = false;
+    Task::none()
+}
+
+pub(crate) fn check_missing_files(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) || state.tracks.is_empty() {
+        return Task::none();
+    }
+
+    state.status = "Checking files...".to_string();
+    // A snapshot: rows only need `id` + `path`, and the check must not hold `state`.
+    let tracks = state.tracks.clone();
+    Task::perform(
+        spawn_blocking(move || lint::check_missing_files(&tracks)),
+        Message::MissingFilesFound,
+    )
+}
+
+pub(crate) fn missing_files_found(state: &mut Sonora, ids: Vec<TrackId>) -> Task<Message> {
+    // Ids that vanished from the library while the check ran are simply ignored.
+    state.missing_track_ids = ids
+        .into_iter()
+        .filter(|id| state.track_by_id(*id).is_some())
+        .collect();
+
+    state.status = match state.missing_track_ids.len() {
+        0 => "Every file is where it was scanned.".to_string(),
+        1 => "1 file is missing.".to_string(),
+        n => format!("{n} files are missing."),
+    };
+    Task::none()
+}
+
+pub(crate) fn remove_missing_tracks(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) || state.missing_track_ids.is_empty() {
+        return Task::none();
+    }
+
+    let ids: BTreeSet<TrackId> = std::mem::take(&mut state.missing_track_ids);
+    state.remove_tracks(&ids);
+    if state.view_mode == ViewMode::Duplicates {
+        refresh_duplicates(state);
+    }
+    load_inspector_from_selection(state);
+
+    state.status = format!("Removed {} missing files from the library.", ids.len());
+    Task::none()
+}
@@ -6,15 +6,32 @@
 
 use iced::Task;
 
-use super::state::{Message, Sonora};
+use super::state::{Direction, Message, Sonora};
 
+mod artwork;
+mod autonumber;
+mod columns;
+mod config;
+mod dedup;
+mod export;
+mod fileops;
+mod find_replace;
+mod history;
+mod inline_edit;
 mod inspector;
+mod keyboard;
+mod lint;
+mod musicbrainz;
 mod playback;
+mod queue;
 mod roots;
 mod save;
 mod scan;
+mod scrobbler;
 mod selection;
 mod util;
+mod watch;
+mod waveform;
 
 pub(crate) fn update(state: &mut Sonora, message: Message) -> Task<Message> {
     match message {
@@ -26,31 +43,102 @@ pub(crate) fn update(state: &mut Sonora, message: Message) -> Task<Message> {
         Message::RootInputChanged(s) => roots::root_input_changed(state, s),
         Message::AddRootPressed => roots::add_root_pressed(state),
         Message::RemoveRoot(i) => roots::remove_root(state, i),
+        Message::ExclusionInputChanged(s) => roots::exclusion_input_changed(state, s),
+        Message::AddExclusion(pattern) => roots::add_exclusion(state, pattern),
+        Message::RemoveExclusion(i) => roots::remove_exclusion(state, i),
+        Message::DroppedPaths(paths) => roots::dropped_paths(state, paths),
+        Message::CachePruned(result) => roots::cache_pruned(state, result),
 
         // Scan
         Message::ScanLibrary => scan::scan_library(state),
+        Message::ScanProgress { scanned, total } => scan::scan_progress(state, scanned, total),
         Message::ScanFinished(result) => scan::scan_finished(state, result),
+        Message::ToggleScanErrors => scan::toggle_scan_errors(state),
+        Message::CopyScanErrors => scan::copy_scan_errors(state),
+        Message::DateAddedWritten(failed) => scan::date_added_written(state, failed),
+        Message::FolderChanged(path) => watch::folder_changed(state, path),
+        Message::FolderChangesSettled => watch::folder_changes_settled(state),
+        Message::FolderRescanFinished(result) => watch::folder_rescan_finished(state, result),
 
         // View + selection
         Message::SetViewMode(mode) => selection::set_view_mode(state, mode),
+        Message::ToggleCompactMode => selection::toggle_compact_mode(state),
         Message::SelectAlbum(key) => selection::select_album(state, key),
+        Message::SelectAlbumModified(key, mode) => {
+            selection::select_album_modified(state, key, mode)
+        }
+        Message::FixAlbumTags(key) => selection::fix_album_tags(state, key),
+        Message::ToggleAlbumActions => selection::toggle_album_actions(state),
         Message::SelectTrack(id) => selection::select_track(state, id),
+        Message::SelectTrackModified(id, mode) => selection::select_track_modified(state, id, mode),
+        Message::ModifiersChanged(m) => selection::modifiers_changed(state, m),
+        Message::SelectPrevTrack => selection::select_adjacent_track(state, Direction::Up),
+        Message::SelectNextTrack => selection::select_adjacent_track(state, Direction::Down),
+        Message::TrackListScrolled(viewport) => selection::track_list_scrolled(state, viewport),
+        Message::SelectAll => selection::select_all(state),
+        Message::KeyPressed(key, modifiers) => keyboard::key_pressed(state, key, modifiers),
+        Message::FilterChanged(q) => selection::filter_changed(state, q),
+        Message::SetSort(col) => selection::set_sort(state, col),
+        Message::ToggleColumnConfig => columns::toggle_column_config(state),
+        Message::SetColumnVisible(c, on) => columns::set_column_visible(state, c, on),
+        Message::MoveColumn(c, dir) => columns::move_column(state, c, dir),
+        Message::ResizeColumn(c, delta) => columns::resize_column(state, c, delta),
+        Message::ResetColumns => columns::reset_columns(state),
 
         // Cover
         Message::CoverLoaded(id, handle) => selection::cover_loaded(state, id, handle),
+        Message::WaveformReady(id, peaks) => waveform::waveform_ready(state, id, peaks),
+        Message::LoadArtworkFromFile(id) => artwork::load_artwork_from_file(state, id),
+        Message::SetCoverArt(id, bytes, mime) => artwork::set_cover_art(state, id, bytes, mime),
+        Message::CoverArtSaved(id, size, result) => {
+            artwork::cover_art_saved(state, id, size, result)
+        }
+        Message::ExtractArtwork(id) => artwork::extract_artwork(state, id),
+        Message::ExtractArtworkDone(result) => artwork::extract_artwork_done(state, result),
 
         // Playback
-        Message::PlaySelected => playback::play_selected(state),
-        Message::PlayTrack(id) => playback::play_track(state, id),
+        Message::PlayTrack(id) => playback::play_track_filled(state, id),
         Message::TogglePlayPause => playback::toggle_play_pause(state),
         Message::Next => playback::next(state),
         Message::Prev => playback::prev(state),
+        Message::ToggleShuffle => playback::toggle_shuffle(state),
+        Message::CycleRepeat => playback::cycle_repeat(state),
+        Message::SetQueueFill(mode) => playback::set_queue_fill(state, mode),
+
+        // Queue
+        Message::EnqueueTrack(id) => queue::enqueue_track(state, id),
+        Message::EnqueueAlbum(key) => queue::enqueue_album(state, key),
+        Message::PlayFromHere(id) => queue::play_from_here(state, id),
+        Message::PlayQueue => queue::play_queue(state),
+        Message::ClearQueue => queue::clear_queue(state),
+        Message::RemoveFromQueue(i) => queue::remove_from_queue(state, i),
+        Message::MoveInQueue(i, direction) => queue::move_in_queue(state, i, direction),
+        Message::MoveToTop(i) => queue::move_to_top(state, i),
+        Message::MoveToBottom(i) => queue::move_to_bottom(state, i),
+        Message::ToggleQueuePanel => queue::toggle_queue_panel(state),
+        Message::ImportPlaylist => queue::import_playlist(state),
+        Message::PlaylistImported(result) => queue::playlist_imported(state, result),
+        Message::ExportPlaylist => queue::export_playlist(state),
+        Message::PlaylistExported(result) => queue::playlist_exported(state, result),
 
         // Seek: preview vs commit
         Message::SeekTo(ratio) => playback::seek_preview(state, ratio),
         Message::SeekCommit => playback::seek_commit(state),
 
         Message::SetVolume(vol) => playback::set_volume(state, vol),
+        Message::CycleReplayGain => playback::cycle_replaygain(state),
+        Message::SetCrossfade(ms) => playback::set_crossfade(state, ms),
+        Message::LoadAudioDevices => playback::load_audio_devices(state),
+        Message::AudioDevicesLoaded(names) => playback::audio_devices_loaded(state, names),
+        Message::SetOutputDevice(name) => playback::set_output_device(state, name),
+        Message::SetTagVersion(version) => save::set_tag_version(state, version),
+        Message::SetBackupOnWrite(on) => save::set_backup_on_write(state, on),
+        Message::SetValidateBeforeWrite(on) => save::set_validate_before_write(state, on),
+        Message::SetAlbumGroupByYear(on) => selection::set_album_group_by_year(state, on),
+        Message::ToggleEqPanel => playback::toggle_eq_panel(state),
+        Message::ToggleLyricsPanel => playback::toggle_lyrics_panel(state),
+        Message::SetEqGain(band, gain_db) => playback::set_eq_gain(state, band, gain_db),
+        Message::ResetEq => playback::reset_eq(state),
 
         // Playback (optional path)
         Message::PlaybackEvent(ev) => playback::handle_event(state, ev),
@@ -60,11 +148,133 @@ pub(crate) fn update(state: &mut Sonora, message: Message) -> Task<Message> {
         Message::InspectorChanged(field, value) => {
             inspector::inspector_changed(state, field, value)
         }
+        Message::ClearField(field) => inspector::clear_field(state, field),
+        Message::CopyTags(id, fields) => inspector::copy_tags(state, id, fields),
+        Message::PasteTags => inspector::paste_tags(state),
 
         // Save
         Message::SaveInspectorToFile => save::save_inspector_to_file(state),
         Message::SaveFinished(id, result) => save::save_finished(state, id, result),
+        Message::BatchSaveProgress(p) => save::batch_save_progress(state, p),
         Message::SaveFinishedBatch(result) => save::save_finished_batch(state, result),
+        Message::BeginInlineEdit(id, field) => inline_edit::begin_inline_edit(state, id, field),
+        Message::InlineEditChanged(value) => inline_edit::inline_edit_changed(state, value),
+        Message::CommitInlineEdit(id, field, value) => {
+            inline_edit::commit_inline_edit(state, id, field, value)
+        }
+        Message::CancelInlineEdit => inline_edit::cancel_inline_edit(state),
+        Message::InlineEditSaved(result) => inline_edit::inline_edit_saved(state, result),
+        Message::AddUserText(desc, value) => inspector::add_user_text(state, desc, value),
+        Message::RemoveUserText(desc) => inspector::remove_user_text(state, desc),
+        Message::CommentDraftChanged(i, part, value) => {
+            inspector::comment_draft_changed(state, i, part, value)
+        }
+        Message::AddCommentDraft => inspector::add_comment_draft(state),
+        Message::RemoveCommentDraft(i) => inspector::remove_comment_draft(state, i),
+        Message::NewUserTextChanged(desc, value) => {
+            inspector::new_user_text_changed(state, desc, value)
+        }
         Message::RevertInspector => save::revert_inspector(state),
+
+        // Undo / redo
+        Message::Undo => history::undo(state),
+        Message::Redo => history::redo(state),
+        Message::HistoryApplied(was_redo, applied, inverse, result) => {
+            history::history_applied(state, was_redo, applied, inverse, result)
+        }
+        Message::RescanSelected => scan::rescan_selected(state),
+        Message::RescanSelectedFinished(rows) => scan::rescan_selected_finished(state, rows),
+        Message::AutoNumberSelected => autonumber::auto_number_selected(state),
+        Message::ResequenceAlbum(key) => autonumber::resequence_album(state, key),
+        Message::AutoNumberFinished(result) => autonumber::auto_number_finished(state, result),
+        Message::OpenFindReplace => find_replace::open_find_replace(state),
+        Message::CloseFindReplace => find_replace::close_find_replace(state),
+        Message::FindReplaceFieldChanged(field) => find_replace::field_changed(state, field),
+        Message::FindReplaceFindChanged(s) => find_replace::find_changed(state, s),
+        Message::FindReplaceReplaceChanged(s) => find_replace::replace_changed(state, s),
+        Message::FindReplaceRegexToggled(on) => find_replace::regex_toggled(state, on),
+        Message::PreviewFindReplace => find_replace::preview_find_replace(state),
+        Message::FindReplace {
+            field,
+            find,
+            replace,
+            regex,
+        } => find_replace::find_replace(state, field, find, replace, regex),
+        Message::FindReplaceFinished(result) => find_replace::find_replace_finished(state, result),
+        Message::SetRating(id, rating) => save::set_rating(state, id, rating),
+        Message::RatingWritten(id, old, result) => save::rating_written(state, id, old, result),
+        Message::ToggleFavorite(id) => save::toggle_favorite(state, id),
+        Message::FavoriteWritten(id, old, result) => save::favorite_written(state, id, old, result),
+        Message::RatingEmailChanged(s) => save::rating_email_changed(state, s),
+        Message::ConfirmStripTags => save::confirm_strip_tags(state),
+        Message::StripTags(ids) => save::strip_tags(state, ids),
+        Message::StripTagsFinished(results) => save::strip_tags_finished(state, results),
+
+        // MusicBrainz
+        Message::LookupMusicBrainz => musicbrainz::lookup_musicbrainz(state),
+        Message::MusicBrainzResult(id, result) => {
+            musicbrainz::musicbrainz_result(state, id, result)
+        }
+        Message::FingerprintAndLookup(id) => musicbrainz::fingerprint_and_lookup(state, id),
+        Message::AcoustIdApiKeyChanged(s) => musicbrainz::acoustid_api_key_changed(state, s),
+
+        // Duplicates
+        Message::FindDuplicates => dedup::find_duplicates(state),
+        Message::ToggleDuplicateGroup(i) => dedup::toggle_duplicate_group(state, i),
+        Message::MarkForDeletion(id) => dedup::mark_for_deletion(state, id),
+        Message::DeleteMarked => dedup::delete_marked(state),
+        Message::DeleteMarkedFinished(results) => dedup::delete_marked_finished(state, results),
+
+        // Missing tags report
+        Message::FindMissingTags => lint::find_missing_tags(state),
+        Message::CloseMissingTags => lint::close_missing_tags(state),
+        Message::CheckAlbums => lint::check_albums(state),
+        Message::CloseAlbumCheck => lint::close_album_check(state),
+        Message::CheckMissingFiles => lint::check_missing_files(state),
+        Message::MissingFilesFound(ids) => lint::missing_files_found(state, ids),
+        Message::RemoveMissingTracks => lint::remove_missing_tracks(state),
+        Message::OpenAlbumIssue(key) => lint::open_album_issue(state, key),
+        Message::ShowStats => lint::show_stats(state),
+        Message::CloseStats => lint::close_stats(state),
+        Message::AppClosing => playback::app_closing(state),
+
+        // File operations
+        Message::ShowInFolder(id) => fileops::show_in_folder(state, id),
+        Message::OpenWithExternalEditor(ids) => fileops::open_with_external_editor(state, ids),
+        Message::ExternalEditorChanged(s) => fileops::external_editor_changed(state, s),
+        Message::RenameTemplateChanged(s) => fileops::rename_template_changed(state, s),
+        Message::RenameFromTemplate(template, dry_run) => {
+            fileops::rename_from_template(state, template, dry_run)
+        }
+        Message::RenameFinished(dry_run, results) => {
+            fileops::rename_finished(state, dry_run, results)
+        }
+        Message::ToggleOrganizePanel => fileops::toggle_organize_panel(state),
+        Message::OrganizeDestChanged(s) => fileops::organize_dest_changed(state, s),
+        Message::PickOrganizeDest => fileops::pick_organize_dest(state),
+        Message::OrganizeTemplateChanged(s) => fileops::organize_template_changed(state, s),
+        Message::OrganizeCopyToggled(v) => fileops::organize_copy_toggled(state, v),
+        Message::OrganizeFiles => fileops::organize_files(state),
+        Message::OrganizeFinished(copy, result) => fileops::organize_finished(state, copy, result),
+
+        // Settings / Last.fm
+        Message::ToggleSettingsPanel => scrobbler::toggle_settings_panel(state),
+        Message::LastFmApiKeyChanged(s) => scrobbler::api_key_changed(state, s),
+        Message::LastFmApiSecretChanged(s) => scrobbler::api_secret_changed(state, s),
+        Message::ConnectLastFm => scrobbler::connect(state),
+        Message::LastFmTokenReceived(result) => scrobbler::token_received(state, result),
+        Message::FinishLastFmAuth => scrobbler::finish_auth(state),
+        Message::LastFmSessionReceived(result) => scrobbler::session_received(state, result),
+        Message::DisconnectLastFm => scrobbler::disconnect(state),
+        Message::TrackScrobble(id) => scrobbler::track_scrobble(state, id),
+        Message::ScrobbleFinished(result) => scrobbler::scrobble_finished(state, result),
+
+        // Export
+        Message::ExportCsv => export::export_csv(state),
+        Message::ExportCsvDone(result) => export::export_csv_done(state, result),
+
+        // Config
+        Message::SaveConfig => config::save_config(state),
+        Message::ConfigSaved(result) => config::config_saved(state, result),
     }
 }
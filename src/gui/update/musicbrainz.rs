@@ -0,0 +1,170 @@
+//! gui/update/musicbrainz.rs
+//! MusicBrainz lookup -> inspector draft.
+//!
+//! - Single selection only; the query uses the draft title/artist (what the user sees).
+//! - "Identify by audio" skips the text search: Chromaprint fingerprint -> AcoustID
+//!   recording id -> MusicBrainz recording, landing in the same result handler.
+//! - Results fill *empty* draft fields and mark the draft dirty. Nothing is written
+//!   until the user clicks "Save edits".
+
+use iced::Task;
+
+use super::super::state::{Message, Sonora};
+use super::super::util::filename_stem;
+use super::util::spawn_blocking;
+use crate::core::acoustid::{fingerprint_file, lookup_recording_id};
+use crate::core::musicbrainz::{MbRelease, lookup_by_recording_id, lookup_by_title_artist};
+use crate::core::types::TrackId;
+
+pub(crate) fn lookup_musicbrainz(state: &mut Sonora) -> Task<Message> {
+    if state.mb_lookup_pending {
+        return Task::none();
+    }
+    if state.selected_tracks.len() > 1 {
+        state.status = "MusicBrainz lookup works on one track at a time.".into();
+        return Task::none();
+    }
+    let Some(id) = state.selected_track else {
+        state.status = "Select a track to look up.".into();
+        return Task::none();
+    };
+    let Some(i) = state.index_of_id(id) else {
+        return Task::none();
+    };
+
+    let title = match state.inspector.title.trim() {
+        "" => filename_stem(&state.tracks[i].path),
+        t => t.to_string(),
+    };
+    let artist = state.inspector.artist.trim().to_string();
+
+    state.mb_lookup_pending = true;
+    state.status = format!("Looking up \"{title}\" on MusicBrainz...");
+
+    Task::perform(
+        async move { lookup_by_title_artist(&title, &artist).await },
+        move |res| Message::MusicBrainzResult(id, res),
+    )
+}
+
+pub(crate) fn fingerprint_and_lookup(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    if state.mb_lookup_pending {
+        return Task::none();
+    }
+    if state.acoustid_api_key.trim().is_empty() {
+        state.status = "Set an AcoustID API key in Settings first.".into();
+        return Task::none();
+    }
+    let Some(row) = state.track_by_id(id) else {
+        return Task::none();
+    };
+    let Some(duration_ms) = row.duration_ms else {
+        state.status = "Can't identify: track duration is unknown.".into();
+        return Task::none();
+    };
+
+    let path = row.path.clone();
+    let api_key = state.acoustid_api_key.clone();
+
+    state.mb_lookup_pending = true;
+    state.status = "Fingerprinting audio...".into();
+
+    Task::perform(
+        async move {
+            let fingerprint = spawn_blocking(move || fingerprint_file(&path)).await?;
+            let duration_s = u64::from(duration_ms) / 1000;
+            match lookup_recording_id(&api_key, &fingerprint, duration_s).await? {
+                Some(recording_id) => lookup_by_recording_id(&recording_id).await,
+                None => Ok(None),
+            }
+        },
+        move |res| Message::MusicBrainzResult(id, res),
+    )
+}
+
+pub(crate) fn acoustid_api_key_changed(state: &mut Sonora, s: String) -> Task<Message> {
+    state.acoustid_api_key = s;
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn musicbrainz_result(
+    state: &mut Sonora,
+    id: TrackId,
+    result: Result<Option<MbRelease>, String>,
+) -> Task<Message> {
+    state.mb_lookup_pending = false;
+
+    let rel = match result {
+        Ok(Some(rel)) => rel,
+        Ok(None) => {
+            state.status = "MusicBrainz: no confident match.".into();
+            return Task::none();
+        }
+        Err(e) => {
+            state.status = format!("MusicBrainz lookup failed: {e}");
+            return Task::none();
+        }
+    };
+
+    // The selection moved on while the request was in flight; don't touch another draft.
+    if state.selected_track != Some(id) || state.selected_tracks.len() > 1 {
+        state.status = "MusicBrainz result ignored (selection changed).".into();
+        return Task::none();
+    }
+
+    let score = rel
+        .score
+        .map(|s| format!(" (score {s})"))
+        .unwrap_or_default();
+    let filled = fill_missing(state, &rel);
+    state.status = if filled == 0 {
+        format!("MusicBrainz match{score}: no missing fields to fill.")
+    } else {
+        format!("MusicBrainz match{score}: filled {filled} field(s); review and Save.")
+    };
+
+    Task::none()
+}
+
+/// Copy lookup values into empty draft fields. Returns how many fields changed.
+fn fill_missing(state: &mut Sonora, rel: &MbRelease) -> usize {
+    // An untagged file shows its filename as the draft title; that counts as missing.
+    let title_is_stem = state
+        .selected_track
+        .and_then(|id| state.track_by_id(id))
+        .is_some_and(|row| {
+            row.title.is_none() && state.inspector.title == filename_stem(&row.path)
+        });
+    if rel.title.is_some() && title_is_stem {
+        state.inspector.title.clear();
+    }
+
+    let num = |n: Option<u32>| n.map(|v| v.to_string());
+    let d = &mut state.inspector;
+    let mut filled = 0;
+
+    let mut fill = |slot: &mut String, value: Option<String>| {
+        if let Some(value) = value
+            && slot.trim().is_empty()
+        {
+            *slot = value;
+            filled += 1;
+        }
+    };
+
+    fill(&mut d.title, rel.title.clone());
+    fill(&mut d.artist, rel.artist.clone());
+    fill(&mut d.album, rel.album.clone());
+    fill(&mut d.album_artist, rel.album_artist.clone());
+    fill(&mut d.year, rel.year.map(|y| y.to_string()));
+    fill(&mut d.track_no, num(rel.track_no));
+    fill(&mut d.track_total, num(rel.track_total));
+    fill(&mut d.disc_no, num(rel.disc_no));
+    fill(&mut d.mb_recording_id, rel.recording_id.clone());
+    fill(&mut d.mb_release_id, rel.release_id.clone());
+
+    if filled > 0 {
+        state.inspector_dirty = true;
+    }
+    filled
+}
@@ -3,16 +3,49 @@
 //!
 //! - `now_playing` and selection are `TrackId`, not Vec indices.
 //! - `PlayTrack` accepts a `TrackId` and looks up the current row by id.
+//! - Shuffle: Next picks a random track; Prev walks `shuffle_history` first.
+//! - Repeat: TrackEnded consults `repeat_mode` (stop / replay / advance).
+//! - Queue: when non-empty, Next/Prev/TrackEnded defer to `queue.rs`.
+//! - Queue fill: a `PlayTrack` (click in a list, Play) refills the queue per `queue_fill`
+//!   (album / artist / genre / random); `Manual` leaves it alone. Internal
+//!   `play_track` calls (Next, queue steps, repeat) never refill.
+//! - Gapless: on `NearEnd` we pick the upcoming track early and send `SetNext`;
+//!   `AdvancedToNext` then commits it like a normal Next would.
+//! - Crossfade: same handshake; `CrossfadeStarted` commits the track as the fade begins.
+//! - Scrobbling: `listened_ms` counts real listening time; when a track finishes
+//!   (TrackEnded or a gapless advance) and it counts, we emit `TrackScrobble`.
+//! - Starting a track (directly or gaplessly) also requests its waveform.
+//! - History: every finished track is appended to `playback_history` ("Recently played").
 //!
 //! Design goals:
 //! - GUI never touches rodio/symphonia directly.
 //! - All IO / timing is driven by the engine + TickPlayback polling.
 
 use iced::Task;
-
-use super::super::state::{Message, Sonora};
-use crate::core::playback::{PlayerCommand, PlayerEvent, start_playback};
-use crate::core::types::TrackId;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::time::Instant;
+
+use super::super::state::{
+    MAX_CROSSFADE_MS, Message, PLAYBACK_HISTORY_MAX, QueueFill, SYSTEM_DEFAULT_DEVICE, Sonora,
+    flat_eq_bands,
+};
+use super::super::util::album_order_key;
+use super::queue::{queue_next, queue_next_pos, queue_prev};
+use super::util::spawn_blocking;
+use super::waveform::load_waveform;
+use crate::core::history;
+use crate::core::playback::{
+    PlayerCommand, PlayerEvent, RepeatMode, TrackGain, list_audio_devices, start_playback,
+};
+use crate::core::scrobbler::should_scrobble;
+use crate::core::types::{TrackId, TrackRow};
+
+/// Cap on remembered shuffle steps (Prev depth).
+const SHUFFLE_HISTORY_MAX: usize = 200;
+
+/// Position jumps larger than this between engine updates are seeks, not listening.
+const MAX_LISTEN_STEP_MS: u64 = 2_000;
 
 fn ensure_engine(state: &mut Sonora) {
     if state.playback.is_some() && state.playback_events.is_some() {
@@ -21,6 +54,11 @@ fn ensure_engine(state: &mut Sonora) {
 
     let (controller, events) = start_playback();
     controller.send(PlayerCommand::SetVolume(state.volume));
+    controller.send(PlayerCommand::SetReplayGain(state.replaygain_mode));
+    controller.send(PlayerCommand::SetCrossfade(state.crossfade_ms));
+    if let Some(name) = &state.output_device {
+        controller.send(PlayerCommand::SetOutputDevice(name.clone()));
+    }
 
     state.playback = Some(controller);
     state.playback_events = Some(std::cell::RefCell::new(events));
@@ -40,11 +78,12 @@ pub(crate) fn drain_events(state: &mut Sonora) -> Task<Message> {
         }
     }
 
-    for ev in drained {
-        let _ = handle_event(state, ev);
-    }
+    let tasks: Vec<Task<Message>> = drained
+        .into_iter()
+        .map(|ev| handle_event(state, ev))
+        .collect();
 
-    Task::none()
+    Task::batch(tasks)
 }
 
 pub(crate) fn play_selected(state: &mut Sonora) -> Task<Message> {
@@ -52,7 +91,91 @@ pub(crate) fn play_selected(state: &mut Sonora) -> Task<Message> {
         state.status = "No track selected.".into();
         return Task::none();
     };
-    play_track(state, id)
+    play_track_filled(state, id)
+}
+
+/// User asked to play `id`: refill the queue for `queue_fill`, then play.
+pub(crate) fn play_track_filled(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    fill_queue_for_mode(state, id);
+    let task = play_track(state, id);
+    if state.queue_fill != QueueFill::Manual {
+        state.status = format!("{} ({} in queue).", state.status, state.queue.len());
+    }
+    task
+}
+
+pub(crate) fn set_queue_fill(state: &mut Sonora, mode: QueueFill) -> Task<Message> {
+    state.queue_fill = mode;
+    Task::none()
+}
+
+/// Replace the queue with the tracks `queue_fill` picks for `id`, cursor on `id`.
+/// `Manual` (or an unknown id) leaves the queue as it is.
+pub(crate) fn fill_queue_for_mode(state: &mut Sonora, id: TrackId) {
+    let Some(t) = state.track_by_id(id) else {
+        return;
+    };
+
+    // Same tag value, ignoring case and surrounding spaces; no value matches nothing.
+    let same = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => a.trim().eq_ignore_ascii_case(b.trim()),
+        _ => false,
+    };
+
+    let mut rows: Vec<&TrackRow> = match state.queue_fill {
+        QueueFill::Manual => return,
+        QueueFill::Album => {
+            let key = state.album_key(t);
+            state
+                .album_groups
+                .get(&key)
+                .into_iter()
+                .flatten()
+                .filter_map(|&id| state.track_by_id(id))
+                .collect()
+        }
+        QueueFill::Artist => state
+            .tracks
+            .iter()
+            .filter(|r| same(&r.artist, &t.artist))
+            .collect(),
+        QueueFill::Genre => state
+            .tracks
+            .iter()
+            .filter(|r| same(&r.genre, &t.genre))
+            .collect(),
+        QueueFill::Random => state.tracks.iter().collect(),
+    };
+
+    match state.queue_fill {
+        QueueFill::Random => rows.shuffle(&mut rand::thread_rng()),
+        QueueFill::Genre => rows.sort_by_cached_key(|r| {
+            (
+                r.artist.clone().unwrap_or_default().to_lowercase(),
+                r.album.clone().unwrap_or_default().to_lowercase(),
+                album_order_key(r),
+            )
+        }),
+        _ => rows.sort_by_cached_key(|r| {
+            (
+                r.album.clone().unwrap_or_default().to_lowercase(),
+                album_order_key(r),
+            )
+        }),
+    }
+
+    let mut queue: Vec<TrackId> = rows.into_iter().filter_map(|r| r.id).collect();
+    if state.queue_fill == QueueFill::Random {
+        queue.retain(|&v| v != id);
+        queue.insert(0, id);
+    }
+    // No artist / genre tag: just this track.
+    if !queue.contains(&id) {
+        queue = vec![id];
+    }
+
+    state.queue_pos = queue.iter().position(|&v| v == id).unwrap_or(0);
+    state.queue = queue;
 }
 
 pub(crate) fn play_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
@@ -69,21 +192,29 @@ pub(crate) fn play_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
     };
 
     let path = row.path.clone();
+    let gain = TrackGain {
+        track_db: row.replaygain_track_gain,
+        album_db: row.replaygain_album_gain,
+        trim_db: row.volume_trim_db,
+    };
 
     #[cfg(debug_assertions)]
     eprintln!("[GUI] PlayTrack id={} path={}", id, path.display());
 
-    controller.send(PlayerCommand::PlayFile(path.clone()));
+    controller.send(PlayerCommand::PlayFile(path.clone(), gain));
 
     // Playback should not hijack selection.
     state.now_playing = Some(id);
+    state.gapless_next = None;
+    state.listened_ms = 0;
     state.is_playing = true;
     state.position_ms = 0;
+    state.last_position_update = None;
     state.duration_ms = None;
     state.seek_preview_ratio = None;
     state.status = format!("Playing: {}", path.display());
 
-    Task::none()
+    load_waveform(state, id)
 }
 
 pub(crate) fn toggle_play_pause(state: &mut Sonora) -> Task<Message> {
@@ -107,6 +238,10 @@ pub(crate) fn pause(state: &mut Sonora) -> Task<Message> {
     };
 
     controller.send(PlayerCommand::Pause);
+
+    // Freeze the interpolated playhead where it is.
+    state.position_ms = state.estimated_position_ms();
+    state.last_position_update = None;
     state.is_playing = false;
 
     Task::none()
@@ -130,40 +265,84 @@ pub(crate) fn resume(state: &mut Sonora) -> Task<Message> {
     Task::none()
 }
 
-pub(crate) fn stop(state: &mut Sonora) -> Task<Message> {
+pub(crate) fn toggle_shuffle(state: &mut Sonora) -> Task<Message> {
     ensure_engine(state);
 
-    let Some(controller) = &state.playback else {
-        state.status = "Stop failed: playback engine failed to initialize.".into();
-        return Task::none();
-    };
+    state.shuffle = !state.shuffle;
+    state.shuffle_history.clear();
 
-    controller.send(PlayerCommand::Stop);
+    if let Some(controller) = &state.playback {
+        controller.send(PlayerCommand::SetShuffle(state.shuffle));
+    }
 
-    state.is_playing = false;
-    state.position_ms = 0;
-    state.duration_ms = None;
-    state.seek_preview_ratio = None;
+    Task::none()
+}
+
+pub(crate) fn cycle_repeat(state: &mut Sonora) -> Task<Message> {
+    ensure_engine(state);
+
+    state.repeat_mode = state.repeat_mode.cycled();
+
+    if let Some(controller) = &state.playback {
+        controller.send(PlayerCommand::SetRepeat(state.repeat_mode));
+    }
 
     Task::none()
 }
 
+/// Pick a random track id other than `exclude` (None if nothing else is playable).
+fn random_track_id(state: &Sonora, exclude: Option<TrackId>) -> Option<TrackId> {
+    let candidates: Vec<TrackId> = state
+        .tracks
+        .iter()
+        .filter_map(|t| t.id)
+        .filter(|id| Some(*id) != exclude)
+        .collect();
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let i = rand::thread_rng().gen_range(0..candidates.len());
+    Some(candidates[i])
+}
+
 pub(crate) fn next(state: &mut Sonora) -> Task<Message> {
     if state.tracks.is_empty() {
         return Task::none();
     }
 
-    // Prefer "now playing", else selection, else first track.
-    let anchor_id = state
-        .now_playing
-        .or(state.selected_track)
-        .or_else(|| state.tracks.first().and_then(|t| t.id));
+    if !state.queue.is_empty() {
+        return queue_next(state);
+    }
 
-    let Some(cur_id) = anchor_id else {
-        state.status = "No playable track found (missing ids?).".into();
+    if state.shuffle
+        && let Some(next_id) = random_track_id(state, state.now_playing)
+    {
+        if let Some(cur) = state.now_playing {
+            state.shuffle_history.push_back(cur);
+            if state.shuffle_history.len() > SHUFFLE_HISTORY_MAX {
+                state.shuffle_history.pop_front();
+            }
+        }
+        return play_track(state, next_id);
+    }
+
+    let Some(next_id) = display_next_id(state) else {
+        state.status = "Next failed: no playable track (missing ids?).".into();
         return Task::none();
     };
 
+    play_track(state, next_id)
+}
+
+/// Next track in display order after now playing (else selection, else first), wrapping.
+fn display_next_id(state: &Sonora) -> Option<TrackId> {
+    let cur_id = state
+        .now_playing
+        .or(state.selected_track)
+        .or_else(|| state.tracks.first().and_then(|t| t.id))?;
+
     // Convert current id to index, then move by index in the current display order.
     let cur_idx = state.index_of_id(cur_id).unwrap_or(0);
     let next_idx = if cur_idx + 1 >= state.tracks.len() {
@@ -172,12 +351,95 @@ pub(crate) fn next(state: &mut Sonora) -> Task<Message> {
         cur_idx + 1
     };
 
-    let Some(next_id) = state.tracks.get(next_idx).and_then(|t| t.id) else {
-        state.status = "Next failed: track missing id.".into();
-        return Task::none();
+    state.tracks.get(next_idx).and_then(|t| t.id)
+}
+
+/// What should follow the current track when it ends (same rules as `TrackEnded`).
+///
+/// Returns the id plus its queue position when it comes from the queue.
+fn upcoming_track(state: &Sonora) -> Option<(TrackId, Option<usize>)> {
+    match state.repeat_mode {
+        RepeatMode::One => state.now_playing.map(|id| (id, None)),
+        _ if !state.queue.is_empty() => {
+            queue_next_pos(state).map(|pos| (state.queue[pos], Some(pos)))
+        }
+        RepeatMode::All if state.shuffle => {
+            random_track_id(state, state.now_playing).map(|id| (id, None))
+        }
+        RepeatMode::All => display_next_id(state).map(|id| (id, None)),
+        RepeatMode::Off => None,
+    }
+}
+
+/// Engine asked what comes next: pick it now and hand it over for gapless playback.
+fn near_end(state: &mut Sonora, _remaining_ms: u64) {
+    #[cfg(debug_assertions)]
+    eprintln!("[GUI] NearEnd with {_remaining_ms} ms left");
+
+    state.gapless_next = upcoming_track(state);
+
+    let Some((id, _)) = state.gapless_next else {
+        return;
+    };
+    let (Some(controller), Some(row)) = (&state.playback, state.track_by_id(id)) else {
+        return;
     };
 
-    play_track(state, next_id)
+    let gain = TrackGain {
+        track_db: row.replaygain_track_gain,
+        album_db: row.replaygain_album_gain,
+        trim_db: row.volume_trim_db,
+    };
+    controller.send(PlayerCommand::SetNext(row.path.clone(), gain));
+}
+
+/// The current track just finished: record it in the play history and scrobble it
+/// if enough of it was heard. Resets the listening counter either way.
+fn finished_play(state: &mut Sonora) -> Task<Message> {
+    let listened_ms = std::mem::take(&mut state.listened_ms);
+
+    if let Some(id) = state.now_playing {
+        state.playback_history.push_back((id, chrono::Local::now()));
+        if state.playback_history.len() > PLAYBACK_HISTORY_MAX {
+            state.playback_history.pop_front();
+        }
+    }
+
+    match state.now_playing {
+        Some(id)
+            if state.scrobbler.is_connected()
+                && should_scrobble(listened_ms, state.duration_ms) =>
+        {
+            Task::done(Message::TrackScrobble(id))
+        }
+        _ => Task::none(),
+    }
+}
+
+/// The prebuffered track is now playing: commit the same bookkeeping `next()` would.
+fn advanced_to_next(state: &mut Sonora, path: &std::path::Path, duration_ms: Option<u64>) {
+    if let Some((id, queue_pos)) = state.gapless_next.take() {
+        match queue_pos {
+            Some(pos) => state.queue_pos = pos,
+            None if state.shuffle && state.repeat_mode != RepeatMode::One => {
+                if let Some(cur) = state.now_playing {
+                    state.shuffle_history.push_back(cur);
+                    if state.shuffle_history.len() > SHUFFLE_HISTORY_MAX {
+                        state.shuffle_history.pop_front();
+                    }
+                }
+            }
+            None => {}
+        }
+        state.now_playing = Some(id);
+    }
+
+    state.is_playing = true;
+    state.duration_ms = duration_ms;
+    state.position_ms = 0;
+    state.last_position_update = Some(Instant::now());
+    state.seek_preview_ratio = None;
+    state.status = format!("Now playing: {}", path.display());
 }
 
 pub(crate) fn prev(state: &mut Sonora) -> Task<Message> {
@@ -185,6 +447,19 @@ pub(crate) fn prev(state: &mut Sonora) -> Task<Message> {
         return Task::none();
     }
 
+    if !state.queue.is_empty() {
+        return queue_prev(state);
+    }
+
+    // Shuffle: go back through what was played (skip ids lost to a rescan).
+    if state.shuffle {
+        while let Some(id) = state.shuffle_history.pop_back() {
+            if state.index_of_id(id).is_some() {
+                return play_track(state, id);
+            }
+        }
+    }
+
     let anchor_id = state
         .now_playing
         .or(state.selected_track)
@@ -265,6 +540,7 @@ pub(crate) fn seek_commit(state: &mut Sonora) -> Task<Message> {
 
     // Optimistic UI update; engine will confirm via Started/Position.
     state.position_ms = target_ms;
+    state.last_position_update = None;
 
     Task::none()
 }
@@ -280,6 +556,112 @@ pub(crate) fn set_volume(state: &mut Sonora, volume: f32) -> Task<Message> {
     Task::none()
 }
 
+/// Crossfade slider (ms, snapped by the view); 0 switches back to gapless.
+pub(crate) fn set_crossfade(state: &mut Sonora, ms: u64) -> Task<Message> {
+    state.crossfade_ms = ms.min(MAX_CROSSFADE_MS);
+
+    if let Some(controller) = &state.playback {
+        controller.send(PlayerCommand::SetCrossfade(state.crossfade_ms));
+    }
+
+    Task::none()
+}
+
+/// Window is closing: write the play history (best-effort), then quit.
+pub(crate) fn app_closing(state: &mut Sonora) -> Task<Message> {
+    let entries: Vec<_> = state.playback_history.iter().copied().collect();
+
+    Task::perform(spawn_blocking(move || history::save(&entries)), |r| r).then(|_result| {
+        #[cfg(debug_assertions)]
+        if let Err(e) = &_result {
+            eprintln!("[HISTORY] save failed: {e}");
+        }
+        iced::exit()
+    })
+}
+
+pub(crate) fn load_audio_devices(_state: &mut Sonora) -> Task<Message> {
+    Task::perform(
+        spawn_blocking(list_audio_devices),
+        Message::AudioDevicesLoaded,
+    )
+}
+
+pub(crate) fn audio_devices_loaded(state: &mut Sonora, names: Vec<String>) -> Task<Message> {
+    state.audio_devices = names;
+    Task::none()
+}
+
+/// Switch output device (the engine restarts the current track there) and persist it.
+pub(crate) fn set_output_device(state: &mut Sonora, name: String) -> Task<Message> {
+    let device = Some(name).filter(|n| n != SYSTEM_DEFAULT_DEVICE);
+    if device == state.output_device {
+        return Task::none();
+    }
+    state.output_device = device;
+
+    if let Some(controller) = &state.playback {
+        let name = state.output_device.clone().unwrap_or_default();
+        controller.send(PlayerCommand::SetOutputDevice(name));
+    }
+
+    state.status = format!(
+        "Output device: {}",
+        state
+            .output_device
+            .as_deref()
+            .unwrap_or(SYSTEM_DEFAULT_DEVICE)
+    );
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn toggle_eq_panel(state: &mut Sonora) -> Task<Message> {
+    state.show_eq = !state.show_eq;
+    Task::none()
+}
+
+pub(crate) fn toggle_lyrics_panel(state: &mut Sonora) -> Task<Message> {
+    state.show_lyrics = !state.show_lyrics;
+    Task::none()
+}
+
+pub(crate) fn set_eq_gain(state: &mut Sonora, band: usize, gain_db: f32) -> Task<Message> {
+    let Some(b) = state.eq_bands.get_mut(band) else {
+        return Task::none();
+    };
+    b.gain_db = gain_db.clamp(-12.0, 12.0);
+
+    send_eq(state);
+    Task::none()
+}
+
+pub(crate) fn reset_eq(state: &mut Sonora) -> Task<Message> {
+    state.eq_bands = flat_eq_bands();
+
+    send_eq(state);
+    Task::none()
+}
+
+fn send_eq(state: &mut Sonora) {
+    ensure_engine(state);
+
+    if let Some(controller) = &state.playback {
+        controller.send(PlayerCommand::SetEq(state.eq_bands.clone()));
+    }
+}
+
+pub(crate) fn cycle_replaygain(state: &mut Sonora) -> Task<Message> {
+    ensure_engine(state);
+
+    state.replaygain_mode = state.replaygain_mode.cycled();
+
+    if let Some(controller) = &state.playback {
+        controller.send(PlayerCommand::SetReplayGain(state.replaygain_mode));
+    }
+
+    Task::done(Message::SaveConfig)
+}
+
 pub(crate) fn handle_event(state: &mut Sonora, event: PlayerEvent) -> Task<Message> {
     #[cfg(debug_assertions)]
     match &event {
@@ -310,6 +692,7 @@ pub(crate) fn handle_event(state: &mut Sonora, event: PlayerEvent) -> Task<Messa
             state.is_playing = true;
             state.duration_ms = duration_ms;
             state.position_ms = start_ms;
+            state.last_position_update = Some(Instant::now());
             state.seek_preview_ratio = None;
             state.status = format!("Now playing: {}", path.display());
         }
@@ -318,20 +701,57 @@ pub(crate) fn handle_event(state: &mut Sonora, event: PlayerEvent) -> Task<Messa
         PlayerEvent::Stopped => {
             state.is_playing = false;
             state.position_ms = 0;
+            state.last_position_update = None;
             state.duration_ms = None;
             state.seek_preview_ratio = None;
         }
         PlayerEvent::Position { position_ms } => {
             // If user is dragging the seek slider, don't fight them.
+            if state.is_playing
+                && let Some(step) = position_ms.checked_sub(state.position_ms)
+                && step <= MAX_LISTEN_STEP_MS
+            {
+                state.listened_ms += step;
+            }
             if state.seek_preview_ratio.is_none() {
                 state.position_ms = position_ms;
+                state.last_position_update = Some(Instant::now());
             }
         }
         PlayerEvent::TrackEnded => {
+            let scrobble = finished_play(state);
+
             state.is_playing = false;
             state.position_ms = 0;
+            state.last_position_update = None;
             state.seek_preview_ratio = None;
+
+            let follow = match (state.repeat_mode, state.now_playing) {
+                (RepeatMode::One, Some(id)) => play_track(state, id),
+                // next() wraps past the last track / queue entry (or shuffles).
+                (RepeatMode::All, Some(_)) => next(state),
+                // A queue keeps going until its end even without repeat.
+                (RepeatMode::Off, Some(_)) if !state.queue.is_empty() => queue_next(state),
+                _ => Task::none(),
+            };
+            return Task::batch([scrobble, follow]);
+        }
+        PlayerEvent::NearEnd { remaining_ms } => near_end(state, remaining_ms),
+        PlayerEvent::AdvancedToNext { path, duration_ms }
+        | PlayerEvent::CrossfadeStarted {
+            next_path: path,
+            duration_ms,
+        } => {
+            let scrobble = finished_play(state);
+            advanced_to_next(state, &path, duration_ms);
+            let waveform = match state.now_playing {
+                Some(id) => load_waveform(state, id),
+                None => Task::none(),
+            };
+            return Task::batch([scrobble, waveform]);
         }
+        PlayerEvent::ShuffleChanged(on) => state.shuffle = on,
+        PlayerEvent::RepeatChanged(mode) => state.repeat_mode = mode,
         PlayerEvent::Error(err) => {
             state.status = format!("Playback error: {err}");
         }
@@ -0,0 +1,368 @@
+//! gui/update/queue.rs
+//! Play queue (ordered TrackIds + cursor).
+//!
+//! - `state.queue` holds ids, never indices; ids missing after a rescan are skipped.
+//! - While the queue is non-empty, Next/Prev move `queue_pos` instead of walking `tracks`.
+//! - Wrap-around at the ends only happens with `RepeatMode::All`.
+//! - Imported playlists replace the queue; entries match library rows by exact path.
+//! - Export writes the queue if there is one, else the selection, else the library.
+//! - Reordering moves one entry at a time; the cursor (and a prebuffered gapless
+//!   position) follow the entry they pointed at, not the slot.
+
+use iced::Task;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::super::state::{AlbumKey, Direction, Message, Sonora};
+use super::playback::play_track;
+use super::util::spawn_blocking;
+use crate::core::playback::RepeatMode;
+use crate::core::playlist::{parse_m3u, write_m3u};
+use crate::core::types::{TrackId, TrackRow};
+
+pub(crate) fn enqueue_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    if state.index_of_id(id).is_none() {
+        return Task::none();
+    }
+
+    state.queue.push(id);
+    state.status = format!("Queued ({} in queue).", state.queue.len());
+    Task::none()
+}
+
+pub(crate) fn enqueue_album(state: &mut Sonora, key: AlbumKey) -> Task<Message> {
+    let Some(ids) = state.album_groups.get(&key) else {
+        return Task::none();
+    };
+
+    // Same (disc, track, title) order as the album detail view.
+    let mut ids: Vec<TrackId> = ids.clone();
+    ids.sort_by_key(|id| {
+        state
+            .track_by_id(*id)
+            .map(|t| {
+                (
+                    t.disc_no.unwrap_or(0),
+                    t.track_no.unwrap_or(0),
+                    t.title.clone().unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default()
+    });
+
+    let added = ids.len();
+    state.queue.extend(ids);
+    state.status = format!(
+        "Queued {added} tracks from {} ({} in queue).",
+        key.album,
+        state.queue.len()
+    );
+    Task::none()
+}
+
+/// Queue `id` and every track after it in the track table's current order
+/// (filter + sort), replacing the queue, and start playing it.
+/// A track that isn't listed (filtered out) is queued on its own.
+pub(crate) fn play_from_here(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    let order: Vec<TrackId> = state
+        .visible_track_indices()
+        .into_iter()
+        .filter_map(|i| state.tracks[i].id)
+        .collect();
+
+    state.queue = match order.iter().position(|&v| v == id) {
+        Some(start) => order[start..].to_vec(),
+        None => vec![id],
+    };
+    state.queue_pos = 0;
+
+    let task = play_track(state, id);
+    state.status = format!("{} ({} in queue).", state.status, state.queue.len());
+    task
+}
+
+/// Start playing the queue from the top.
+pub(crate) fn play_queue(state: &mut Sonora) -> Task<Message> {
+    state.queue_pos = 0;
+    play_from(state, 0, 1)
+}
+
+pub(crate) fn clear_queue(state: &mut Sonora) -> Task<Message> {
+    state.queue.clear();
+    state.queue_pos = 0;
+    state.status = "Queue cleared.".into();
+    Task::none()
+}
+
+pub(crate) fn remove_from_queue(state: &mut Sonora, i: usize) -> Task<Message> {
+    if i >= state.queue.len() {
+        return Task::none();
+    }
+
+    state.queue.remove(i);
+
+    // Keep the cursor on the same entry.
+    if i < state.queue_pos {
+        state.queue_pos -= 1;
+    }
+    if state.queue_pos >= state.queue.len() {
+        state.queue_pos = state.queue.len().saturating_sub(1);
+    }
+
+    Task::none()
+}
+
+pub(crate) fn move_in_queue(state: &mut Sonora, i: usize, direction: Direction) -> Task<Message> {
+    let to = match direction {
+        Direction::Up => i.saturating_sub(1),
+        Direction::Down => (i + 1).min(state.queue.len().saturating_sub(1)),
+    };
+    move_queue_entry(state, i, to);
+    Task::none()
+}
+
+pub(crate) fn move_to_top(state: &mut Sonora, i: usize) -> Task<Message> {
+    move_queue_entry(state, i, 0);
+    Task::none()
+}
+
+pub(crate) fn move_to_bottom(state: &mut Sonora, i: usize) -> Task<Message> {
+    move_queue_entry(state, i, state.queue.len().saturating_sub(1));
+    Task::none()
+}
+
+/// Move the entry at `from` to `to`, shifting the ones in between by one.
+fn move_queue_entry(state: &mut Sonora, from: usize, to: usize) {
+    let len = state.queue.len();
+    if from >= len || to >= len || from == to {
+        return;
+    }
+
+    let id = state.queue.remove(from);
+    state.queue.insert(to, id);
+
+    // Where did the entry previously at `pos` end up?
+    let remap = |pos: usize| {
+        if pos == from {
+            to
+        } else if from < pos && pos <= to {
+            pos - 1
+        } else if to <= pos && pos < from {
+            pos + 1
+        } else {
+            pos
+        }
+    };
+
+    state.queue_pos = remap(state.queue_pos);
+    if let Some((_, Some(pos))) = &mut state.gapless_next {
+        *pos = remap(*pos);
+    }
+}
+
+pub(crate) fn toggle_queue_panel(state: &mut Sonora) -> Task<Message> {
+    state.show_queue = !state.show_queue;
+    Task::none()
+}
+
+pub(crate) fn import_playlist(_state: &mut Sonora) -> Task<Message> {
+    Task::perform(
+        async {
+            let handle = rfd::AsyncFileDialog::new()
+                .set_title("Import playlist")
+                .add_filter("Playlists", &["m3u", "m3u8"])
+                .pick_file()
+                .await?;
+
+            let path: PathBuf = handle.path().to_path_buf();
+            Some(spawn_blocking(move || parse_m3u(&path)).await)
+        },
+        |res| match res {
+            None => Message::Noop,
+            Some(r) => Message::PlaylistImported(r),
+        },
+    )
+}
+
+pub(crate) fn playlist_imported(
+    state: &mut Sonora,
+    result: Result<Vec<PathBuf>, String>,
+) -> Task<Message> {
+    let paths = match result {
+        Ok(paths) => paths,
+        Err(e) => {
+            state.status = format!("Playlist import failed: {e}");
+            return Task::none();
+        }
+    };
+
+    let by_path: HashMap<&PathBuf, TrackId> = state
+        .tracks
+        .iter()
+        .filter_map(|t| t.id.map(|id| (&t.path, id)))
+        .collect();
+
+    let mut matched: Vec<TrackId> = Vec::with_capacity(paths.len());
+    let mut unmatched: Vec<&PathBuf> = Vec::new();
+    for p in &paths {
+        match by_path.get(p) {
+            Some(&id) => matched.push(id),
+            None => unmatched.push(p),
+        }
+    }
+
+    state.status = match unmatched.as_slice() {
+        [] => format!("Queued {} tracks from playlist.", matched.len()),
+        [only] => format!(
+            "Queued {} tracks; not in library: {}",
+            matched.len(),
+            only.display()
+        ),
+        [first, rest @ ..] => format!(
+            "Queued {} tracks; {} not in library (e.g. {}).",
+            matched.len(),
+            rest.len() + 1,
+            first.display()
+        ),
+    };
+
+    state.queue = matched;
+    state.queue_pos = 0;
+    Task::none()
+}
+
+pub(crate) fn export_playlist(state: &mut Sonora) -> Task<Message> {
+    let rows = playlist_rows(state);
+    if rows.is_empty() {
+        state.status = "Nothing to export (queue, selection and library are empty).".into();
+        return Task::none();
+    }
+
+    Task::perform(
+        async move {
+            let handle = rfd::AsyncFileDialog::new()
+                .set_title("Export playlist")
+                .set_file_name("playlist.m3u")
+                .add_filter("Playlists", &["m3u", "m3u8"])
+                .save_file()
+                .await?;
+
+            let path: PathBuf = handle.path().to_path_buf();
+            Some(
+                spawn_blocking(move || {
+                    write_m3u(&rows, &path)?;
+                    Ok((path, rows.len()))
+                })
+                .await,
+            )
+        },
+        |res| match res {
+            None => Message::Noop,
+            Some(r) => Message::PlaylistExported(r),
+        },
+    )
+}
+
+pub(crate) fn playlist_exported(
+    state: &mut Sonora,
+    result: Result<(PathBuf, usize), String>,
+) -> Task<Message> {
+    state.status = match result {
+        Ok((path, n)) => format!("Exported {n} tracks to {}", path.display()),
+        Err(e) => format!("Playlist export failed: {e}"),
+    };
+    Task::none()
+}
+
+/// Rows to export: queue order, else selection (in view order), else the whole library.
+fn playlist_rows(state: &Sonora) -> Vec<TrackRow> {
+    let ids: Vec<TrackId> = if !state.queue.is_empty() {
+        state.queue.clone()
+    } else if !state.selected_tracks.is_empty() {
+        let mut ids: Vec<TrackId> = state
+            .visible_track_indices()
+            .into_iter()
+            .filter_map(|i| state.tracks[i].id)
+            .filter(|id| state.selected_tracks.contains(id))
+            .collect();
+        // Selected but hidden by the filter: keep them, after the visible ones.
+        let shown: HashSet<TrackId> = ids.iter().copied().collect();
+        ids.extend(
+            state
+                .selected_tracks
+                .iter()
+                .copied()
+                .filter(|id| !shown.contains(id)),
+        );
+        ids
+    } else {
+        state.tracks.iter().filter_map(|t| t.id).collect()
+    };
+
+    ids.into_iter()
+        .filter_map(|id| state.track_by_id(id).cloned())
+        .collect()
+}
+
+/// Advance the cursor (Next / TrackEnded). Caller ensures the queue is non-empty.
+pub(crate) fn queue_next(state: &mut Sonora) -> Task<Message> {
+    match queue_next_pos(state) {
+        Some(pos) => {
+            state.queue_pos = pos;
+            play_track(state, state.queue[pos])
+        }
+        None => {
+            state.status = "End of queue.".into();
+            Task::none()
+        }
+    }
+}
+
+/// First playable position after the cursor (wrapping only with `RepeatMode::All`).
+pub(crate) fn queue_next_pos(state: &Sonora) -> Option<usize> {
+    let len = state.queue.len();
+
+    for step in 1..=len {
+        let pos = state.queue_pos + step;
+        if pos >= len && state.repeat_mode != RepeatMode::All {
+            return None;
+        }
+        let pos = pos % len;
+        if state.index_of_id(state.queue[pos]).is_some() {
+            return Some(pos);
+        }
+    }
+
+    None
+}
+
+/// Step the cursor back (Prev). Caller ensures the queue is non-empty.
+pub(crate) fn queue_prev(state: &mut Sonora) -> Task<Message> {
+    if state.queue_pos == 0 && state.repeat_mode != RepeatMode::All {
+        state.status = "Start of queue.".into();
+        return Task::none();
+    }
+    let len = state.queue.len();
+    play_from(state, (state.queue_pos + len - 1) % len, -1)
+}
+
+/// Play the first still-present entry from `start`, stepping by `dir` (wrapping).
+fn play_from(state: &mut Sonora, start: usize, dir: isize) -> Task<Message> {
+    let len = state.queue.len();
+    if len == 0 {
+        state.status = "Queue is empty.".into();
+        return Task::none();
+    }
+
+    for step in 0..len {
+        let pos = (start as isize + dir * step as isize).rem_euclid(len as isize) as usize;
+        let id = state.queue[pos];
+        if state.index_of_id(id).is_some() {
+            state.queue_pos = pos;
+            return play_track(state, id);
+        }
+    }
+
+    state.status = "Queued tracks are no longer in the library (rescan?).".into();
+    Task::none()
+}
@@ -1,9 +1,14 @@
 //! gui/update/roots.rs
+//! Library root folders: typed path, drag-and-drop onto the window, removal.
+//! Scan exclusions: patterns for paths under the roots that scans skip.
 use iced::Task;
 use std::path::{Path, PathBuf};
 
 use super::super::state::Message;
 use super::super::state::Sonora;
+use super::super::util::is_busy;
+use super::util::spawn_blocking;
+use crate::core::db::LibraryDb;
 
 pub(crate) fn root_input_changed(state: &mut Sonora, s: String) -> Task<Message> {
     state.root_input = s;
@@ -11,7 +16,7 @@ pub(crate) fn root_input_changed(state: &mut Sonora, s: String) -> Task<Message>
 }
 
 pub(crate) fn add_root_pressed(state: &mut Sonora) -> Task<Message> {
-    if state.scanning || state.saving {
+    if is_busy(state) {
         return Task::none();
     }
 
@@ -38,13 +43,108 @@ pub(crate) fn add_root_pressed(state: &mut Sonora) -> Task<Message> {
     state.roots.push(p.clone());
     state.root_input.clear();
     state.status = format!("Added folder: {}", p.display());
-    Task::none()
+    Task::done(Message::SaveConfig)
+}
+
+/// Files/folders dropped onto the window: folders become roots, files add their folder.
+pub(crate) fn dropped_paths(state: &mut Sonora, paths: Vec<PathBuf>) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let mut added: Vec<PathBuf> = Vec::new();
+    let mut skipped = 0usize;
+
+    for path in paths {
+        let dir = if path.is_dir() {
+            Some(path)
+        } else if path.is_file() {
+            path.parent().map(Path::to_path_buf)
+        } else {
+            None
+        };
+
+        // Same rules as typing a path: existing folder, not already a root.
+        match dir {
+            Some(d) if d.is_dir() && !state.roots.contains(&d) => {
+                state.roots.push(d.clone());
+                added.push(d);
+            }
+            _ => skipped += 1,
+        }
+    }
+
+    state.status = match (added.as_slice(), skipped) {
+        ([], 0) => return Task::none(),
+        ([], _) => "Dropped items were already added (or aren't folders).".to_string(),
+        ([one], 0) => format!("Added folder: {}", one.display()),
+        (many, 0) => format!("Added {} folders", many.len()),
+        (many, s) => format!("Added {} folders ({s} skipped)", many.len()),
+    };
+
+    if added.is_empty() {
+        Task::none()
+    } else {
+        Task::done(Message::SaveConfig)
+    }
 }
 
 pub(crate) fn remove_root(state: &mut Sonora, i: usize) -> Task<Message> {
-    if i < state.roots.len() && !state.scanning && !state.saving {
+    if i < state.roots.len() && !is_busy(state) {
         let removed = state.roots.remove(i);
         state.status = format!("Removed folder: {}", removed.display());
+
+        // Drop cached rows that no longer belong to any root.
+        let roots = state.roots.clone();
+        let prune = Task::perform(
+            spawn_blocking(move || LibraryDb::open_default()?.prune_outside_roots(&roots)),
+            Message::CachePruned,
+        );
+        return Task::batch([Task::done(Message::SaveConfig), prune]);
+    }
+    Task::none()
+}
+
+pub(crate) fn cache_pruned(state: &mut Sonora, result: Result<usize, String>) -> Task<Message> {
+    match result {
+        Ok(_n) => {
+            #[cfg(debug_assertions)]
+            eprintln!("[GUI] pruned {_n} cached rows outside roots");
+        }
+        Err(e) => state.status = format!("Library cache prune failed: {e}"),
     }
     Task::none()
 }
+
+pub(crate) fn exclusion_input_changed(state: &mut Sonora, s: String) -> Task<Message> {
+    state.exclusion_input = s;
+    Task::none()
+}
+
+pub(crate) fn add_exclusion(state: &mut Sonora, pattern: String) -> Task<Message> {
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return Task::none();
+    }
+
+    state.exclusion_input.clear();
+    if state.exclusions.contains(&pattern) {
+        state.status = format!("Already excluded: {pattern}");
+        return Task::none();
+    }
+
+    // Takes effect on the next scan (already-loaded tracks stay put).
+    state.status = format!("Excluding {pattern} (rescan to apply)");
+    state.exclusions.push(pattern);
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn remove_exclusion(state: &mut Sonora, i: usize) -> Task<Message> {
+    if i >= state.exclusions.len() {
+        return Task::none();
+    }
+
+    let removed = state.exclusions.remove(i);
+    state.status = format!("No longer excluding {removed} (rescan to apply)");
+    Task::done(Message::SaveConfig)
+}
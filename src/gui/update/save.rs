@@ -12,17 +12,29 @@
 //! Intentional behavior:
 //! - We never mutate `state.tracks` until after a successful write + re-read.
 //! - On write failure, UI remains consistent with disk.
+//! - Every write uses `state.write_options` (ID3 version, optional `.bak` copy first).
+//!
+//! Progress:
+//! - Batch saves run on a worker thread and stream `BatchSaveProgress` after each file
+//!   (status shows "Saved n / total files…"); `SaveFinishedBatch` ends the stream.
 
 use iced::Task;
-
-use super::super::state::{KEEP_SENTINEL, Message, Sonora};
-use super::super::util::{parse_optional_i32, parse_optional_u32};
+use iced::futures::channel::mpsc as async_mpsc;
+
+use super::super::state::{BatchSaveProgress, CommentFrameDraft, KEEP_SENTINEL, Message, Sonora};
+use super::super::util::{
+    clean_optional_string, filename_stem, format_count, is_busy, parse_optional_i32,
+    parse_optional_u32,
+};
+use super::history::push_undo;
 use super::inspector::load_inspector_from_selection;
 use super::util::spawn_blocking;
-use crate::core::types::{TrackId, TrackRow};
+use crate::core::library;
+use crate::core::tags::{Mp3Stats, TagVersion, VbrMode, WriteOptions, is_internal_user_text};
+use crate::core::types::{CommentFrame, TrackId, TrackRow};
 
 pub(crate) fn save_inspector_to_file(state: &mut Sonora) -> Task<Message> {
-    if state.scanning || state.saving {
+    if is_busy(state) {
         return Task::none();
     }
 
@@ -75,7 +87,11 @@ pub(crate) fn save_inspector_to_file(state: &mut Sonora) -> Task<Message> {
         format!("Writing tags to {} files...", ids.len())
     };
 
-    let write_extended = state.show_extended;
+    let options = WriteOptions {
+        write_extended: state.show_extended,
+        ..state.write_options
+    };
+    let validate = state.validate_before_write;
 
     // Single-file path
     if rows_to_write.len() == 1 {
@@ -83,7 +99,12 @@ pub(crate) fn save_inspector_to_file(state: &mut Sonora) -> Task<Message> {
 
         return Task::perform(
             spawn_blocking(move || {
-                crate::core::tags::write_track_row(&row_to_write, write_extended).and_then(|_| {
+                let stats = if validate {
+                    validate_targets(std::slice::from_ref(&(id, row_to_write.clone())))?.pop()
+                } else {
+                    None
+                };
+                crate::core::tags::write_track_row(&row_to_write, &options).and_then(|_| {
                     let (mut r, failed) =
                         crate::core::tags::read_track_row(row_to_write.path.clone());
                     if failed {
@@ -91,7 +112,7 @@ pub(crate) fn save_inspector_to_file(state: &mut Sonora) -> Task<Message> {
                     } else {
                         // Preserve identity in the re-read row.
                         r.id = row_to_write.id;
-                        Ok(r)
+                        Ok((Box::new(r), stats))
                     }
                 })
             }),
@@ -99,45 +120,121 @@ pub(crate) fn save_inspector_to_file(state: &mut Sonora) -> Task<Message> {
         );
     }
 
-    // Batch path
-    Task::perform(
-        spawn_blocking(move || {
-            let mut out: Vec<(TrackId, TrackRow)> = Vec::new();
+    // Batch path: report each written file, then the result.
+    let (msg_tx, msg_rx) = async_mpsc::unbounded::<Message>();
+    let total = rows_to_write.len();
+    state.save_progress = Some((0, total));
 
-            for (id, row) in rows_to_write {
-                crate::core::tags::write_track_row(&row, write_extended)
-                    .map_err(|e| format!("Write failed for track {id}: {e}"))?;
+    std::thread::spawn(move || {
+        // Check every file first, so a corrupt one stops the save before any write.
+        if validate && let Err(e) = validate_targets(&rows_to_write) {
+            let _ = msg_tx.unbounded_send(Message::SaveFinishedBatch(Err(e)));
+            return;
+        }
+        let result = write_and_reread_with_progress(rows_to_write, &options, |done, last_id| {
+            let _ = msg_tx.unbounded_send(Message::BatchSaveProgress(BatchSaveProgress {
+                done,
+                total,
+                last_id,
+            }));
+        });
+        let _ = msg_tx.unbounded_send(Message::SaveFinishedBatch(result));
+    });
+
+    Task::stream(msg_rx)
+}
 
-                let (mut r, failed) = crate::core::tags::read_track_row(row.path.clone());
-                if failed {
-                    return Err(format!(
-                        "Wrote tags for track {id}, but failed to re-read them"
-                    ));
-                }
+/// `validate_mp3` every target; the first bad file names itself in the error.
+/// Returns the stats of the MP3s checked (other formats are skipped).
+/// (Blocking: run off the UI thread.)
+fn validate_targets(rows: &[(TrackId, TrackRow)]) -> Result<Vec<Mp3Stats>, String> {
+    // Frame checks only make sense for MPEG streams.
+    rows.iter()
+        .filter(|(_, r)| library::is_mp3(&r.path))
+        .map(|(_, row)| {
+            crate::core::tags::validate_mp3(&row.path).map_err(|e| {
+                format!(
+                    "{} looks corrupted, not saved: {e}",
+                    filename_stem(&row.path)
+                )
+            })
+        })
+        .collect()
+}
 
-                // Preserve identity in the re-read row.
-                r.id = row.id;
+/// Write each row, then re-read it from disk; stops at the first failure.
+/// (Blocking: run inside `spawn_blocking`.)
+pub(super) fn write_and_reread(
+    rows_to_write: Vec<(TrackId, TrackRow)>,
+    options: &WriteOptions,
+) -> Result<Vec<(TrackId, TrackRow)>, String> {
+    write_and_reread_with_progress(rows_to_write, options, |_, _| {})
+}
 
-                out.push((id, r));
-            }
+/// `write_and_reread`, calling `on_written(done, id)` after each file.
+fn write_and_reread_with_progress(
+    rows_to_write: Vec<(TrackId, TrackRow)>,
+    options: &WriteOptions,
+    mut on_written: impl FnMut(usize, TrackId),
+) -> Result<Vec<(TrackId, TrackRow)>, String> {
+    let mut out: Vec<(TrackId, TrackRow)> = Vec::new();
+
+    for (id, row) in rows_to_write {
+        crate::core::tags::write_track_row(&row, options)
+            .map_err(|e| format!("Write failed for track {id}: {e}"))?;
+
+        let (mut r, failed) = crate::core::tags::read_track_row(row.path.clone());
+        if failed {
+            return Err(format!(
+                "Wrote tags for track {id}, but failed to re-read them"
+            ));
+        }
 
-            Ok(out)
-        }),
-        Message::SaveFinishedBatch,
-    )
+        // Preserve identity in the re-read row.
+        r.id = row.id;
+
+        out.push((id, r));
+        on_written(out.len(), id);
+    }
+
+    Ok(out)
+}
+
+/// Swap re-read rows into the library, record the old ones for undo, refresh caches.
+/// Returns how many rows were replaced.
+pub(super) fn apply_written_rows(
+    state: &mut Sonora,
+    rows: Vec<(TrackId, TrackRow)>,
+    label: &str,
+) -> usize {
+    let mut before: Vec<TrackRow> = Vec::with_capacity(rows.len());
+    for (id, row) in rows {
+        if let Some(slot) = state.track_by_id_mut(id) {
+            before.push(std::mem::replace(slot, row));
+        }
+    }
+    let n = before.len();
+    push_undo(state, format!("{label} ({n} files)"), before);
+
+    // batch writes can change album grouping keys -> rebuild caches once
+    state.rebuild_library_caches();
+
+    load_inspector_from_selection(state);
+    n
 }
 
 pub(crate) fn save_finished(
     state: &mut Sonora,
     id: TrackId,
-    result: Result<TrackRow, String>,
+    result: Result<(Box<TrackRow>, Option<Mp3Stats>), String>,
 ) -> Task<Message> {
     state.saving = false;
 
     match result {
-        Ok(new_row) => {
+        Ok((new_row, stats)) => {
             if let Some(slot) = state.track_by_id_mut(id) {
-                *slot = new_row;
+                let before = std::mem::replace(slot, *new_row);
+                push_undo(state, "Save".to_string(), vec![before]);
 
                 // metadata may have changed album grouping keys -> rebuild caches
                 state.rebuild_library_caches();
@@ -151,7 +248,20 @@ pub(crate) fn save_finished(
             }
 
             state.inspector_dirty = false;
-            state.status = "Tags written to file.".to_string();
+            state.status = match stats {
+                Some(s) => {
+                    let mode = match s.bitrate_mode {
+                        VbrMode::Cbr => "CBR",
+                        VbrMode::Vbr => "VBR",
+                    };
+                    format!(
+                        "Tags written to file (MP3 checked: {} frames, {mode}, audio at byte {}).",
+                        format_count(s.frame_count as usize),
+                        s.first_frame_offset
+                    )
+                }
+                None => "Tags written to file.".to_string(),
+            };
         }
         Err(e) => {
             state.status = format!("Save failed: {e}");
@@ -161,24 +271,31 @@ pub(crate) fn save_finished(
     Task::none()
 }
 
+pub(crate) fn batch_save_progress(state: &mut Sonora, p: BatchSaveProgress) -> Task<Message> {
+    // Late progress after the result (or a rescan) would overwrite the final status.
+    if !state.saving {
+        return Task::none();
+    }
+
+    let name = state
+        .track_by_id(p.last_id)
+        .map(|t| t.title.clone().unwrap_or_else(|| filename_stem(&t.path)))
+        .unwrap_or_default();
+    state.status = format!("Saved {} / {} files… {name}", p.done, p.total);
+    state.save_progress = Some((p.done, p.total));
+    Task::none()
+}
+
 pub(crate) fn save_finished_batch(
     state: &mut Sonora,
     result: Result<Vec<(TrackId, TrackRow)>, String>,
 ) -> Task<Message> {
     state.saving = false;
+    state.save_progress = None;
 
     match result {
         Ok(rows) => {
-            for (id, row) in rows {
-                if let Some(slot) = state.track_by_id_mut(id) {
-                    *slot = row;
-                }
-            }
-
-            // batch writes can change album grouping keys -> rebuild caches once
-            state.rebuild_library_caches();
-
-            load_inspector_from_selection(state);
+            apply_written_rows(state, rows, "Batch save");
 
             state.inspector_dirty = false;
             state.status = "Batch tags written to files.".to_string();
@@ -196,6 +313,245 @@ pub(crate) fn revert_inspector(state: &mut Sonora) -> Task<Message> {
     Task::none()
 }
 
+//
+// Star rating (single field, written immediately)
+//
+
+/// POPM owner for our ratings: configured email, else the Last.fm username, else "sonora".
+fn rating_owner(state: &Sonora) -> String {
+    [&state.rating_email, &state.scrobbler.username]
+        .into_iter()
+        .map(|s| s.trim())
+        .find(|s| !s.is_empty())
+        .unwrap_or("sonora")
+        .to_string()
+}
+
+pub(crate) fn set_rating(state: &mut Sonora, id: TrackId, rating: u8) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let email = rating_owner(state);
+    let Some(row) = state.track_by_id_mut(id) else {
+        return Task::none();
+    };
+
+    // Optimistic: the stars update now, the write confirms (or reverts) later.
+    let old = row.rating.replace(rating);
+    let path = row.path.clone();
+    let options = state.write_options;
+
+    Task::perform(
+        spawn_blocking(move || crate::core::tags::write_rating(&path, &email, rating, &options)),
+        move |result| Message::RatingWritten(id, old, result),
+    )
+}
+
+pub(crate) fn rating_written(
+    state: &mut Sonora,
+    id: TrackId,
+    old: Option<u8>,
+    result: Result<(), String>,
+) -> Task<Message> {
+    if let Err(e) = result {
+        if let Some(row) = state.track_by_id_mut(id) {
+            row.rating = old;
+        }
+        state.status = format!("Rating not saved: {e}");
+    }
+    Task::none()
+}
+
+pub(crate) fn rating_email_changed(state: &mut Sonora, s: String) -> Task<Message> {
+    state.rating_email = s;
+    Task::done(Message::SaveConfig)
+}
+
+//
+// Favorite (single TXXX frame, written immediately)
+//
+
+pub(crate) fn toggle_favorite(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let Some(row) = state.track_by_id_mut(id) else {
+        return Task::none();
+    };
+
+    // Optimistic, like ratings: the heart flips now, a failed write flips it back.
+    let old = row.is_favorite;
+    row.is_favorite = !old;
+    let path = row.path.clone();
+    let options = state.write_options;
+
+    Task::perform(
+        spawn_blocking(move || crate::core::tags::write_favorite(&path, !old, &options)),
+        move |result| Message::FavoriteWritten(id, old, result),
+    )
+}
+
+pub(crate) fn favorite_written(
+    state: &mut Sonora,
+    id: TrackId,
+    old: bool,
+    result: Result<(), String>,
+) -> Task<Message> {
+    if let Err(e) = result {
+        if let Some(row) = state.track_by_id_mut(id) {
+            row.is_favorite = old;
+        }
+        state.status = format!("Favorite not saved: {e}");
+    }
+    Task::none()
+}
+
+//
+// Write settings
+//
+
+pub(crate) fn set_tag_version(state: &mut Sonora, version: TagVersion) -> Task<Message> {
+    state.write_options.tag_version = version;
+    state.status = format!("Tags will be written as {}.", version.label());
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn set_backup_on_write(state: &mut Sonora, on: bool) -> Task<Message> {
+    state.write_options.backup = on;
+    state.status = if on {
+        "Files will be backed up to .bak before each write.".to_string()
+    } else {
+        "Backups before writes turned off.".to_string()
+    };
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn set_validate_before_write(state: &mut Sonora, on: bool) -> Task<Message> {
+    state.validate_before_write = on;
+    state.status = if on {
+        "MP3 frames will be checked before each save.".to_string()
+    } else {
+        "Frame checks before saving turned off.".to_string()
+    };
+    Task::done(Message::SaveConfig)
+}
+
+//
+// Strip tags (batch, destructive: confirmed first)
+//
+
+pub(crate) fn confirm_strip_tags(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let ids: Vec<TrackId> = if !state.selected_tracks.is_empty() {
+        state.selected_tracks.iter().copied().collect()
+    } else {
+        state.selected_track.into_iter().collect()
+    };
+    if ids.is_empty() {
+        state.status = "Select a track first.".to_string();
+        return Task::none();
+    }
+
+    let description = match ids.len() {
+        1 => "Remove ALL tags (including artwork and lyrics) from the selected file?".to_string(),
+        n => format!("Remove ALL tags (including artwork and lyrics) from {n} files?"),
+    };
+
+    Task::perform(
+        async move {
+            rfd::AsyncMessageDialog::new()
+                .set_level(rfd::MessageLevel::Warning)
+                .set_title("Strip tags")
+                .set_description(description)
+                .set_buttons(rfd::MessageButtons::YesNo)
+                .show()
+                .await
+        },
+        move |answer| match answer {
+            rfd::MessageDialogResult::Yes => Message::StripTags(ids),
+            _ => Message::Noop,
+        },
+    )
+}
+
+pub(crate) fn strip_tags(state: &mut Sonora, ids: Vec<TrackId>) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let targets: Vec<(TrackId, TrackRow)> = ids
+        .into_iter()
+        .filter_map(|id| state.track_by_id(id).map(|t| (id, t.clone())))
+        .collect();
+    if targets.is_empty() {
+        return Task::none();
+    }
+
+    state.saving = true;
+    state.status = format!("Stripping tags from {} files...", targets.len());
+    let options = state.write_options;
+
+    Task::perform(
+        spawn_blocking(move || {
+            targets
+                .into_iter()
+                .map(|(id, row)| {
+                    let result = crate::core::tags::strip_tags(&row.path, &options).map(|()| {
+                        // Re-read: audio properties survive, tag fields come back empty.
+                        let (mut r, _failed) = crate::core::tags::read_track_row(row.path);
+                        r.id = row.id;
+                        r
+                    });
+                    (id, result)
+                })
+                .collect()
+        }),
+        Message::StripTagsFinished,
+    )
+}
+
+pub(crate) fn strip_tags_finished(
+    state: &mut Sonora,
+    results: Vec<(TrackId, Result<TrackRow, String>)>,
+) -> Task<Message> {
+    state.saving = false;
+
+    let mut stripped = 0usize;
+    let mut first_error: Option<String> = None;
+    let mut failed = 0usize;
+
+    for (id, result) in results {
+        match result {
+            Ok(row) => {
+                if let Some(slot) = state.track_by_id_mut(id) {
+                    *slot = row;
+                }
+                stripped += 1;
+            }
+            Err(e) => {
+                failed += 1;
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    // Album/artist keys are gone -> regroup once.
+    state.rebuild_library_caches();
+    load_inspector_from_selection(state);
+
+    state.status = match first_error {
+        None => format!("Stripped tags from {stripped} files."),
+        Some(e) => format!("Stripped tags from {stripped} files; {failed} failed (first: {e})"),
+    };
+
+    Task::none()
+}
+
 //
 // Batch-aware row builder
 //
@@ -211,6 +567,10 @@ fn build_row_from_inspector_for_id(
         .cloned()
         .ok_or_else(|| "Invalid selection (rescan?).".to_string())?;
 
+    // Cheap pre-check on the UI thread: no point spawning a write that must fail.
+    crate::core::tags::check_writable(&out.path)
+        .map_err(|e| format!("{e} (fix the file's permissions and try again)"))?;
+
     // Numeric fields: treat "<keep>" as "do not change this number"
     let mut errs: Vec<&'static str> = Vec::new();
 
@@ -241,11 +601,38 @@ fn build_row_from_inspector_for_id(
     } else {
         out.bpm
     };
+    let trim_db = if state.show_extended {
+        parse_trim_db_keep(&state.inspector.trim_db, out.volume_trim_db, &mut errs)
+    } else {
+        out.volume_trim_db
+    };
+
+    // Comment frames (single file only): the draft list replaces every COMM frame.
+    let comment_frames = (state.show_extended && !is_batch)
+        .then(|| comment_frames_from_draft(&state.inspector.comments, &mut errs));
 
     if !errs.is_empty() {
         return Err(format!("Not saved: invalid {}", errs.join(", ")));
     }
 
+    // Custom TXXX frames (single file only): internal entries stay, the rest is the draft.
+    if state.show_extended && !is_batch {
+        out.user_text.retain(|k, _| is_internal_user_text(k));
+        out.user_text.extend(
+            state
+                .inspector
+                .user_text
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+    }
+
+    // MusicBrainz ids (single file only): whatever the draft holds after a lookup.
+    if !is_batch {
+        out.mb_recording_id = clean_optional_string(&state.inspector.mb_recording_id);
+        out.mb_release_id = clean_optional_string(&state.inspector.mb_release_id);
+    }
+
     // Text fields: safety for batch mode
     let primary = primary_row;
 
@@ -299,12 +686,18 @@ fn build_row_from_inspector_for_id(
         is_batch,
         primary.and_then(|p| p.grouping.as_deref()),
     );
-    apply_opt_keep_batch(
-        &mut out.comment,
-        &state.inspector.comment,
-        is_batch,
-        primary.and_then(|p| p.comment.as_deref()),
-    );
+    if let Some(comments) = comment_frames {
+        // The writer treats `comment` as the first frame's text.
+        out.comment = comments.first().map(|c| c.text.clone());
+        out.comments = comments;
+    } else {
+        apply_opt_keep_batch(
+            &mut out.comment,
+            &state.inspector.comment,
+            is_batch,
+            primary.and_then(|p| p.comment.as_deref()),
+        );
+    }
     apply_opt_keep_batch(
         &mut out.lyrics,
         &state.inspector.lyrics,
@@ -352,6 +745,7 @@ fn build_row_from_inspector_for_id(
         );
 
         out.bpm = bpm;
+        out.volume_trim_db = trim_db;
         apply_opt_keep_batch(
             &mut out.key,
             &state.inspector.key,
@@ -394,6 +788,31 @@ fn build_row_from_inspector_for_id(
             is_batch,
             primary.and_then(|p| p.copyright.as_deref()),
         );
+
+        apply_opt_keep_batch(
+            &mut out.title_sort,
+            &state.inspector.title_sort,
+            is_batch,
+            primary.and_then(|p| p.title_sort.as_deref()),
+        );
+        apply_opt_keep_batch(
+            &mut out.artist_sort,
+            &state.inspector.artist_sort,
+            is_batch,
+            primary.and_then(|p| p.artist_sort.as_deref()),
+        );
+        apply_opt_keep_batch(
+            &mut out.album_sort,
+            &state.inspector.album_sort,
+            is_batch,
+            primary.and_then(|p| p.album_sort.as_deref()),
+        );
+        apply_opt_keep_batch(
+            &mut out.album_artist_sort,
+            &state.inspector.album_artist_sort,
+            is_batch,
+            primary.and_then(|p| p.album_artist_sort.as_deref()),
+        );
     }
 
     Ok(out)
@@ -419,16 +838,15 @@ fn apply_opt_keep_batch(
         return;
     }
 
-    if is_batch {
-        if let Some(pv) = primary_value {
-            if t == pv.trim() {
-                // User likely didn't intend to overwrite all; treat as KEEP.
-                return;
-            }
-        }
-        // If primary_value is None:
-        // - We do NOT auto-keep: user may be intentionally deleting/blanking.
-        // - So we fall through to the normal empty -> None semantics.
+    // If primary_value is None:
+    // - We do NOT auto-keep: user may be intentionally deleting/blanking.
+    // - So we fall through to the normal empty -> None semantics.
+    if is_batch
+        && let Some(pv) = primary_value
+        && t == pv.trim()
+    {
+        // User likely didn't intend to overwrite all; treat as KEEP.
+        return;
     }
 
     if t.is_empty() {
@@ -460,6 +878,79 @@ fn parse_u32_keep(
     Ok(v)
 }
 
+/// Largest trim accepted from the inspector, in either direction.
+const MAX_TRIM_DB: f32 = 24.0;
+
+/// Parse the "Trim (dB)" field: a signed number with an optional "dB" suffix.
+fn parse_trim_db_keep(
+    input: &str,
+    current: Option<f32>,
+    errs: &mut Vec<&'static str>,
+) -> Option<f32> {
+    let t = input.trim();
+    if t == KEEP_SENTINEL {
+        return current;
+    }
+    if t.is_empty() {
+        return None;
+    }
+
+    let number = t
+        .strip_suffix("dB")
+        .or_else(|| t.strip_suffix("db"))
+        .unwrap_or(t)
+        .trim();
+    match number.parse::<f32>() {
+        Ok(v) if v.is_finite() && v.abs() <= MAX_TRIM_DB => Some(v),
+        _ => {
+            errs.push("Trim (dB)");
+            None
+        }
+    }
+}
+
+/// Draft comment frames -> COMM frames to write.
+/// - Empty text drops the frame
+/// - Language must be 3 ASCII letters (stored lowercase)
+/// - (language, description) must be unique: ID3 keeps one frame per pair
+fn comment_frames_from_draft(
+    drafts: &[CommentFrameDraft],
+    errs: &mut Vec<&'static str>,
+) -> Vec<CommentFrame> {
+    let mut out: Vec<CommentFrame> = Vec::with_capacity(drafts.len());
+
+    for d in drafts.iter().filter(|d| !d.text.trim().is_empty()) {
+        let lang = d.lang.trim().to_ascii_lowercase();
+        if lang.len() != 3 || !lang.chars().all(|c| c.is_ascii_alphabetic()) {
+            push_once(errs, "Comment language (3 letters, e.g. \"eng\")");
+            continue;
+        }
+
+        let description = d.description.trim().to_string();
+        if out
+            .iter()
+            .any(|c| c.lang == lang && c.description == description)
+        {
+            push_once(errs, "Comments (duplicate language + description)");
+            continue;
+        }
+
+        out.push(CommentFrame {
+            lang,
+            description,
+            text: d.text.trim().to_string(),
+        });
+    }
+
+    out
+}
+
+fn push_once(errs: &mut Vec<&'static str>, label: &'static str) {
+    if !errs.contains(&label) {
+        errs.push(label);
+    }
+}
+
 fn parse_i32_keep(
     input: &str,
     current: Option<i32>,
@@ -2,29 +2,57 @@
 //! Scan lifecycle + async boundary + selection reset.
 //!
 //! - Use the explicit core scan pipeline boundary:
-//!   (A) core::scan_paths(roots) -> Vec<PathBuf>
-//!   (B) core::read_tracks(paths) -> (Vec<TrackRow>, failures)
+//!   (A) core::scan_paths(roots, exclusions) -> Vec<PathBuf>
+//!   (B) core::read_tracks_cached(paths, db, progress) -> (Vec<TrackRow>, Vec<ScanError>)
 //!
-//! Still no SQLite:
-//! - We assign temporary TrackId values here so the GUI can operate id-first.
-//! - Once SQLite lands, this becomes "load tracks from DB" instead.
+//! Progress:
+//! - The scan runs on a worker thread and reports through a std mpsc channel.
+//! - A relay thread forwards the latest progress as `Message::ScanProgress`,
+//!   then `Message::ScanFinished` closes the stream.
+//!
+//! Partial refresh:
+//! - `RescanSelected` re-reads only the selected files (e.g. after an external
+//!   tag editor touched them) and swaps those rows in place, keeping their ids.
+//!
+//! Errors:
+//! - Files whose tags couldn't be read still load (empty-ish rows); their paths and
+//!   reasons are kept in `scan_errors` for the sidebar's error log.
+//!
+//! Date added:
+//! - Files a scan sees for the first time (no `TXXX:SONORA_DATE_ADDED` yet) get
+//!   "now" in memory right away; just that frame is then written in the background.
+//! - Each stamped row is then upserted into the library cache, so a written file is a
+//!   cache hit next scan and a read-only one keeps its date instead of a fresh "now".
+//!
+//! Identity:
+//! - The SQLite library cache assigns stable TrackIds (its primary key).
+//! - If the cache can't be opened, we fall back to an uncached read and assign
+//!   temporary ids here so the GUI can still operate id-first.
 
 use iced::Task;
+use iced::futures::channel::mpsc as async_mpsc;
+use std::collections::BTreeSet;
 use std::path::PathBuf;
+use std::sync::mpsc;
 
-use crate::core;
+use crate::core::db::{FileStamp, LibraryDb};
+use crate::core::{self, ScanProgress};
 
-use super::super::state::{Message, Sonora, TEST_ROOT};
-use super::selection::clear_selection_and_inspector;
+use super::super::state::{Message, Sonora, TEST_ROOT, ViewMode};
+use super::super::util::{format_count, format_total_duration_ms, is_busy};
+use super::dedup::refresh_duplicates;
+use super::inspector::load_inspector_from_selection;
+use super::selection::{clear_selection_and_inspector, load_album_covers};
 use super::util::spawn_blocking;
-use crate::core::types::{TrackId, TrackRow};
+use crate::core::types::{ScanError, TrackId, TrackRow};
 
 pub(crate) fn scan_library(state: &mut Sonora) -> Task<Message> {
-    if state.scanning || state.saving {
+    if is_busy(state) {
         return Task::none();
     }
 
     state.scanning = true;
+    state.scan_progress = None;
     state.status = "Scanning...".to_string();
 
     // Selection becomes invalid once new results arrive, but keeping tracks visible
@@ -36,47 +64,122 @@ pub(crate) fn scan_library(state: &mut Sonora) -> Task<Message> {
     } else {
         state.roots.clone()
     };
+    let exclusions = state.exclusions.clone();
 
-    Task::perform(
-        spawn_blocking(move || {
+    let (msg_tx, msg_rx) = async_mpsc::unbounded::<Message>();
+
+    std::thread::spawn(move || {
+        let (progress_tx, progress_rx) = mpsc::channel::<ScanProgress>();
+
+        // Relay: coalesce bursts so the UI only sees the latest count.
+        let relay_tx = msg_tx.clone();
+        let relay = std::thread::spawn(move || {
+            while let Ok(mut p) = progress_rx.recv() {
+                while let Ok(newer) = progress_rx.try_recv() {
+                    p = newer;
+                }
+                let _ = relay_tx.unbounded_send(Message::ScanProgress {
+                    scanned: p.scanned,
+                    total: p.total,
+                });
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+        });
+
+        let result = (|| {
             // Stage A: discover paths (dedup + sorted in core)
-            let paths = core::scan_paths(&roots_to_scan)?;
-            // Stage B: read tags into TrackRows (non-fatal per-file)
-            let (rows, failures) = core::read_tracks(paths);
-            Ok((rows, failures))
-        }),
-        Message::ScanFinished,
-    )
+            let paths = core::scan_paths(&roots_to_scan, &exclusions)?;
+            // Stage B: read tags into TrackRows (non-fatal per-file),
+            // reusing cached rows for unchanged files.
+            match LibraryDb::open_default() {
+                Ok(mut db) => core::read_tracks_cached(paths, &mut db, Some(&progress_tx)),
+                Err(_e) => {
+                    #[cfg(debug_assertions)]
+                    eprintln!("[SCAN] library cache unavailable, reading uncached: {_e}");
+                    Ok(core::read_tracks(paths, Some(&progress_tx)))
+                }
+            }
+        })();
+
+        // Close the progress channel and let the relay flush before finishing.
+        drop(progress_tx);
+        let _ = relay.join();
+
+        let _ = msg_tx.unbounded_send(Message::ScanFinished(result));
+    });
+
+    Task::stream(msg_rx)
+}
+
+pub(crate) fn scan_progress(state: &mut Sonora, scanned: usize, total: usize) -> Task<Message> {
+    // Progress is only meaningful while the scan is in flight.
+    if state.scanning {
+        state.status = format!("Scanning... {scanned} / {total}");
+        state.scan_progress = Some((scanned, total));
+    }
+    Task::none()
 }
 
 pub(crate) fn scan_finished(
     state: &mut Sonora,
-    result: Result<(Vec<TrackRow>, usize), String>,
+    result: Result<(Vec<TrackRow>, Vec<ScanError>), String>,
 ) -> Task<Message> {
     state.scanning = false;
+    state.scan_progress = None;
 
     match result {
-        Ok((mut rows, tag_failures)) => {
-            // Ensure every row has a TrackId (temporary, per-scan).
+        Ok((mut rows, scan_errors)) => {
+            // Ensure every row has a TrackId (cache ids, else temporary per-scan).
             assign_temp_ids_if_missing(&mut rows);
+            let date_added_writes = stamp_date_added(&mut rows, &scan_errors);
 
-            state.status = if tag_failures == 0 {
-                format!("Loaded {} tracks", rows.len())
+            // "~" = some lengths are unknown, so the total is a lower bound.
+            let total_ms: u64 = rows.iter().map(|t| t.duration_ms.unwrap_or(0) as u64).sum();
+            let approx = if rows.iter().any(|t| t.duration_ms.is_none()) {
+                "~"
             } else {
-                format!(
-                    "Loaded {} tracks ({} tag read failures)",
-                    rows.len(),
-                    tag_failures
-                )
+                ""
             };
+            let summary = format!(
+                "Loaded {} tracks — {approx}{} total",
+                format_count(rows.len()),
+                format_total_duration_ms(total_ms)
+            );
+
+            state.status = if scan_errors.is_empty() {
+                summary
+            } else {
+                format!("{summary} ({} tag read failures)", scan_errors.len())
+            };
+            if scan_errors.is_empty() {
+                state.show_scan_errors = false;
+            }
+            state.scan_errors = scan_errors;
 
             state.tracks = rows;
+            // The scan only sees files that exist, so earlier "missing" marks are void.
+            state.missing_track_ids.clear();
 
             // Rebuild id->index and album grouping caches for the new library.
             state.rebuild_library_caches();
 
+            if state.view_mode == ViewMode::Duplicates {
+                refresh_duplicates(state);
+            }
+
             // New library = old ids/selection are invalid.
             clear_selection_and_inspector(state);
+
+            let covers = if state.view_mode == ViewMode::ArtGrid {
+                load_album_covers(state)
+            } else {
+                Task::none()
+            };
+
+            if !date_added_writes.is_empty() {
+                return Task::batch([covers, write_date_added(state, date_added_writes)]);
+            }
+            return covers;
         }
         Err(e) => {
             // Keep previous tracks; just report error.
@@ -88,13 +191,151 @@ pub(crate) fn scan_finished(
     Task::none()
 }
 
-fn assign_temp_ids_if_missing(rows: &mut [TrackRow]) {
+pub(crate) fn toggle_scan_errors(state: &mut Sonora) -> Task<Message> {
+    state.show_scan_errors = !state.show_scan_errors;
+    Task::none()
+}
+
+pub(crate) fn copy_scan_errors(state: &mut Sonora) -> Task<Message> {
+    let log: String = state
+        .scan_errors
+        .iter()
+        .map(|e| format!("{}: {}\n", e.path.display(), e.reason))
+        .collect();
+
+    state.status = format!(
+        "Copied {} scan errors to the clipboard.",
+        state.scan_errors.len()
+    );
+    iced::clipboard::write(log)
+}
+
+/// Give rows without a first-seen date "now"; returns those rows (with their tag read
+/// error, if any) for `write_date_added`.
+fn stamp_date_added(
+    rows: &mut [TrackRow],
+    scan_errors: &[ScanError],
+) -> Vec<(TrackRow, Option<String>)> {
+    let now = chrono::Local::now().to_rfc3339();
+
+    rows.iter_mut()
+        .filter(|r| r.date_added.is_none())
+        .map(|r| {
+            r.date_added = Some(now.clone());
+            let error = scan_errors
+                .iter()
+                .find(|e| e.path == r.path)
+                .map(|e| e.reason.clone());
+            (r.clone(), error)
+        })
+        .collect()
+}
+
+fn write_date_added(state: &mut Sonora, rows: Vec<(TrackRow, Option<String>)>) -> Task<Message> {
+    // Holds the busy guard so no tag edit races these writes.
+    state.saving = true;
+
+    Task::perform(
+        spawn_blocking(move || {
+            let mut failed = 0;
+            let mut store: Vec<(TrackRow, FileStamp, Option<String>)> = Vec::new();
+            for (row, error) in rows {
+                let date = row.date_added.as_deref().unwrap_or_default();
+                if core::tags::write_date_added(&row.path, date).is_err() {
+                    failed += 1;
+                }
+                // Written: the new stamp keeps the row a cache hit. Not written: the
+                // unchanged stamp does, and the cached row carries the date instead.
+                if let Some(stamp) = FileStamp::of(&row.path) {
+                    store.push((row, stamp, error));
+                }
+            }
+            if let Ok(mut db) = LibraryDb::open_default() {
+                let _ = db.upsert_many(&store);
+            }
+            failed
+        }),
+        Message::DateAddedWritten,
+    )
+}
+
+pub(crate) fn date_added_written(state: &mut Sonora, failed: usize) -> Task<Message> {
+    state.saving = false;
+
+    // Unwritten files keep their date through the library cache.
+    if failed > 0 {
+        state.status = format!("{} (date added not saved to {failed} files)", state.status);
+    }
+    Task::none()
+}
+
+pub(crate) fn rescan_selected(state: &mut Sonora) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    let targets: Vec<(TrackId, PathBuf)> = state
+        .selected_tracks
+        .iter()
+        .copied()
+        .chain(state.selected_track)
+        .collect::<BTreeSet<TrackId>>()
+        .into_iter()
+        .filter_map(|id| state.track_by_id(id).map(|t| (id, t.path.clone())))
+        .collect();
+
+    if targets.is_empty() {
+        state.status = "Select tracks to refresh.".to_string();
+        return Task::none();
+    }
+
+    state.saving = true;
+    state.status = format!("Refreshing tags for {} files...", targets.len());
+
+    Task::perform(
+        spawn_blocking(move || {
+            targets
+                .into_iter()
+                .map(|(id, path)| {
+                    let (mut row, _failed) = core::tags::read_track_row(path);
+                    row.id = Some(id);
+                    (id, row)
+                })
+                .collect()
+        }),
+        Message::RescanSelectedFinished,
+    )
+}
+
+pub(crate) fn rescan_selected_finished(
+    state: &mut Sonora,
+    rows: Vec<(TrackId, TrackRow)>,
+) -> Task<Message> {
+    state.saving = false;
+
+    let n = rows.len();
+    for (id, row) in rows {
+        if let Some(slot) = state.track_by_id_mut(id) {
+            *slot = row;
+        }
+    }
+
+    // Album/artist keys may have changed -> regroup once.
+    state.rebuild_library_caches();
+    load_inspector_from_selection(state);
+
+    state.status = format!("Refreshed tags for {n} files.");
+    Task::none()
+}
+
+pub(super) fn assign_temp_ids_if_missing(rows: &mut [TrackRow]) {
     // Deterministic and stable within a scan result.
-    // Not stable across rescans (that’s what SQLite will fix).
+    // Not stable across rescans (only cache-assigned ids are).
+    // Start above the largest cache id so temp ids never collide with real ones.
     // TrackId is currently a *type alias* (not a newtype),
     // so assign by casting, not `TrackId(n)`.
 
-    let mut next: u64 = 1;
+    let mut next: u64 = rows.iter().filter_map(|r| r.id).max().unwrap_or(0).max(0) as u64 + 1;
 
     for r in rows.iter_mut() {
         if r.id.is_none() {
@@ -0,0 +1,157 @@
+//! gui/update/scrobbler.rs
+//! Last.fm: settings panel, desktop auth flow, and scrobbling finished plays.
+//!
+//! - All HTTP runs on worker threads (`spawn_blocking`); results come back as messages.
+//! - Auth: Connect -> token + browser approval page -> Finish -> session key (persisted).
+//! - Playback decides *whether* a play counts (see `playback::finished_play`);
+//!   this module only builds and submits the scrobble.
+
+use iced::Task;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::super::state::{Message, Sonora};
+use super::util::spawn_blocking;
+use crate::core::scrobbler::{Scrobble, auth_url, get_session, get_token, scrobble};
+use crate::core::types::TrackId;
+
+pub(crate) fn toggle_settings_panel(state: &mut Sonora) -> Task<Message> {
+    state.show_settings = !state.show_settings;
+    Task::none()
+}
+
+pub(crate) fn api_key_changed(state: &mut Sonora, s: String) -> Task<Message> {
+    // A session belongs to the API key that created it.
+    if s != state.scrobbler.api_key {
+        state.scrobbler.session_key.clear();
+        state.scrobbler.username.clear();
+        state.lastfm_auth_token = None;
+    }
+    state.scrobbler.api_key = s;
+    Task::none()
+}
+
+pub(crate) fn api_secret_changed(state: &mut Sonora, s: String) -> Task<Message> {
+    state.scrobbler.api_secret = s;
+    Task::none()
+}
+
+pub(crate) fn connect(state: &mut Sonora) -> Task<Message> {
+    if !state.scrobbler.has_api_credentials() {
+        state.status = "Enter a Last.fm API key and secret first.".into();
+        return Task::none();
+    }
+
+    state.status = "Contacting Last.fm...".into();
+    let cfg = state.scrobbler.clone();
+    Task::perform(
+        spawn_blocking(move || get_token(&cfg)),
+        Message::LastFmTokenReceived,
+    )
+}
+
+pub(crate) fn token_received(state: &mut Sonora, result: Result<String, String>) -> Task<Message> {
+    let token = match result {
+        Ok(token) => token,
+        Err(e) => {
+            state.status = format!("Last.fm connect failed: {e}");
+            return Task::none();
+        }
+    };
+
+    let url = auth_url(state.scrobbler.api_key.trim(), &token);
+    state.status = match open::that(&url) {
+        Ok(()) => "Approve Sonora in your browser, then click \"Finish connecting\".".into(),
+        Err(_) => format!("Open {url} to approve Sonora, then click \"Finish connecting\"."),
+    };
+    state.lastfm_auth_token = Some(token);
+
+    Task::none()
+}
+
+pub(crate) fn finish_auth(state: &mut Sonora) -> Task<Message> {
+    let Some(token) = state.lastfm_auth_token.clone() else {
+        return Task::none();
+    };
+
+    state.status = "Finishing Last.fm connection...".into();
+    let cfg = state.scrobbler.clone();
+    Task::perform(
+        spawn_blocking(move || get_session(&cfg, &token)),
+        Message::LastFmSessionReceived,
+    )
+}
+
+pub(crate) fn session_received(
+    state: &mut Sonora,
+    result: Result<(String, String), String>,
+) -> Task<Message> {
+    match result {
+        Ok((key, username)) => {
+            state.scrobbler.session_key = key;
+            state.scrobbler.username = username;
+            state.lastfm_auth_token = None;
+            state.status = "Connected to Last.fm; finished tracks will be scrobbled.".into();
+            Task::done(Message::SaveConfig)
+        }
+        Err(e) => {
+            // Keep the token: the user may just not have approved yet.
+            state.status = format!("Last.fm connect failed: {e}");
+            Task::none()
+        }
+    }
+}
+
+pub(crate) fn disconnect(state: &mut Sonora) -> Task<Message> {
+    state.scrobbler.session_key.clear();
+    state.scrobbler.username.clear();
+    state.lastfm_auth_token = None;
+    state.status = "Disconnected from Last.fm.".into();
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn track_scrobble(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    if !state.scrobbler.is_connected() {
+        return Task::none();
+    }
+    let Some(row) = state.track_by_id(id) else {
+        return Task::none();
+    };
+
+    // Last.fm needs both; untagged files are skipped rather than guessed at.
+    let (Some(artist), Some(title)) = (row.artist.clone(), row.title.clone()) else {
+        state.status = "Not scrobbled: track has no artist/title tags.".into();
+        return Task::none();
+    };
+
+    // The play started roughly one track-length ago.
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let duration_ms = row.duration_ms.map(u64::from);
+    let started = now.saturating_sub(duration_ms.unwrap_or(0) / 1000);
+
+    let s = Scrobble {
+        artist,
+        title,
+        album: row.album.clone(),
+        album_artist: row.album_artist.clone(),
+        track_no: row.track_no,
+        duration_ms,
+        timestamp: started,
+    };
+    let cfg = state.scrobbler.clone();
+
+    Task::perform(
+        spawn_blocking(move || scrobble(&cfg, &s)),
+        Message::ScrobbleFinished,
+    )
+}
+
+pub(crate) fn scrobble_finished(state: &mut Sonora, result: Result<(), String>) -> Task<Message> {
+    // Success is silent; the status line belongs to playback.
+    if let Err(e) = result {
+        state.status = format!("Scrobble failed: {e}");
+    }
+    Task::none()
+}
@@ -5,18 +5,39 @@
 //! - All selection is keyed by `TrackId` (stable), not `Vec` indices.
 //! - Album expansion uses cached `state.album_groups` (no per-click O(n) scan).
 //!
+//! Keyboard: Up/Down step through the track table in display order, scrolling the
+//! new row into view (row geometry is fixed, so its offset is computed, not measured).
+//!
 //! Cover art cache is keyed by `TrackId`.
+//! - The art grid loads its covers lazily: only once it's shown (or rescanned while
+//!   shown), on one worker thread that streams `CoverLoaded` per album.
 
 use iced::Task;
+use iced::futures::channel::mpsc as async_mpsc;
+use iced::widget::operation::{self, AbsoluteOffset};
+use iced::widget::scrollable::Viewport;
 use std::path::{Path, PathBuf};
 
-use super::super::state::{AlbumKey, Message, Sonora, ViewMode};
+use super::super::state::{
+    AlbumKey, Direction, InspectorField, Message, SelectMode, Sonora, SortColumn, TRACK_LIST_ID,
+    ViewMode,
+};
+use super::super::util::{is_busy, matches_filter};
+use super::super::view::constants::{
+    TRACK_LIST_SPACING, TRACK_LIST_SPACING_COMPACT, TRACK_ROW_H, TRACK_ROW_H_COMPACT,
+};
+use super::dedup::refresh_duplicates;
 use super::inspector::{clear_inspector, load_inspector_from_selection};
 use super::util::spawn_blocking;
 use crate::core::types::TrackId;
 
 pub(crate) fn set_view_mode(state: &mut Sonora, mode: ViewMode) -> Task<Message> {
     state.view_mode = mode;
+    state.missing_tag_report = None;
+
+    if mode == ViewMode::Duplicates {
+        refresh_duplicates(state);
+    }
 
     state.selected_track = None;
     state.selected_tracks.clear();
@@ -24,6 +45,32 @@ pub(crate) fn set_view_mode(state: &mut Sonora, mode: ViewMode) -> Task<Message>
     state.selected_album = None;
 
     clear_inspector(state);
+    let save = Task::done(Message::SaveConfig);
+    if mode == ViewMode::ArtGrid {
+        return Task::batch([save, load_album_covers(state)]);
+    }
+    save
+}
+
+/// Compact rows on/off (track table + album list; the scroll math follows the row height).
+pub(crate) fn toggle_compact_mode(state: &mut Sonora) -> Task<Message> {
+    state.compact_mode = !state.compact_mode;
+    Task::done(Message::SaveConfig)
+}
+
+/// Search box edits. Selection is kept even if the filter hides it.
+pub(crate) fn filter_changed(state: &mut Sonora, query: String) -> Task<Message> {
+    state.filter_query = query;
+    Task::none()
+}
+
+pub(crate) fn set_sort(state: &mut Sonora, column: SortColumn) -> Task<Message> {
+    if state.sort_column == column {
+        state.sort_ascending = !state.sort_ascending;
+    } else {
+        state.sort_column = column;
+        state.sort_ascending = true;
+    }
     Task::none()
 }
 
@@ -64,7 +111,130 @@ pub(crate) fn select_album(state: &mut Sonora, key: AlbumKey) -> Task<Message> {
     maybe_load_cover_for_track(state, primary_id)
 }
 
+/// Album grouping with or without the year. Regroups right away; the open album's
+/// key changes shape, so it is closed (the track selection stays).
+pub(crate) fn set_album_group_by_year(state: &mut Sonora, on: bool) -> Task<Message> {
+    state.album_group_by_year = on;
+    state.selected_album = None;
+    state.rebuild_library_caches();
+    Task::done(Message::SaveConfig)
+}
+
+pub(crate) fn toggle_album_actions(state: &mut Sonora) -> Task<Message> {
+    state.show_album_actions = !state.show_album_actions;
+    Task::none()
+}
+
+/// "Fix album tags": select every track of the album, then show the primary track's
+/// album, album artist, year and genre instead of `<keep>`, so Save applies them to all.
+pub(crate) fn fix_album_tags(state: &mut Sonora, key: AlbumKey) -> Task<Message> {
+    if is_busy(state) {
+        return Task::none();
+    }
+
+    // Always start from the full album (`select_album` would collapse an open one).
+    state.selected_album = None;
+    let task = select_album(state, key);
+
+    let Some(primary) = state.selected_track.and_then(|id| state.track_by_id(id)) else {
+        return task;
+    };
+    let album = primary.album.clone().unwrap_or_default();
+    let album_artist = primary.album_artist.clone().unwrap_or_default();
+    let year = primary.year.map(|y| y.to_string()).unwrap_or_default();
+    let genre = primary.genre.clone().unwrap_or_default();
+
+    let fields = [
+        InspectorField::Album,
+        InspectorField::AlbumArtist,
+        InspectorField::Year,
+        InspectorField::Genre,
+    ];
+    let was_mixed = fields
+        .iter()
+        .any(|f| state.inspector_mixed.get(f).copied().unwrap_or(false));
+
+    state.inspector.album = album;
+    state.inspector.album_artist = album_artist;
+    state.inspector.year = year;
+    state.inspector.genre = genre;
+    for f in fields {
+        state.inspector_mixed.insert(f, false);
+    }
+
+    state.status = if was_mixed {
+        "Album tags filled in from the first track. Review, then Save.".to_string()
+    } else {
+        "Album tags already match across this album.".to_string()
+    };
+    state.inspector_dirty = was_mixed;
+    task
+}
+
+/// Album-list click with modifiers.
+/// - AddToggle: union the album's tracks into the selection (or remove them if the
+///   whole album is already selected); the clicked album becomes `selected_album`
+///   so its detail pane shows.
+/// - Anything else behaves like a plain click.
+pub(crate) fn select_album_modified(
+    state: &mut Sonora,
+    key: AlbumKey,
+    mode: SelectMode,
+) -> Task<Message> {
+    if mode != SelectMode::AddToggle {
+        return select_album(state, key);
+    }
+
+    if state.view_mode != ViewMode::Albums {
+        state.view_mode = ViewMode::Albums;
+    }
+
+    let ids: Vec<TrackId> = state.album_groups.get(&key).cloned().unwrap_or_default();
+    if ids.is_empty() {
+        return Task::none();
+    }
+
+    // A lone primary (plain track click) counts as part of the set being extended.
+    if let Some(id) = state.selected_track {
+        state.selected_tracks.insert(id);
+    }
+
+    if ids.iter().all(|id| state.selected_tracks.contains(id)) {
+        for id in &ids {
+            state.selected_tracks.remove(id);
+        }
+        if state.selected_album.as_ref() == Some(&key) {
+            state.selected_album = None;
+        }
+    } else {
+        state.selected_tracks.extend(ids.iter().copied());
+        state.selected_album = Some(key);
+    }
+
+    // Keep the primary if it's still selected; else pick a stable one.
+    if state
+        .selected_track
+        .is_none_or(|id| !state.selected_tracks.contains(&id))
+    {
+        state.selected_track = state.selected_tracks.iter().next().copied();
+    }
+    state.last_clicked_track = state.selected_track;
+
+    let Some(primary) = state.selected_track else {
+        clear_selection_and_inspector(state);
+        return Task::none();
+    };
+
+    load_inspector_from_selection(state);
+    maybe_load_cover_for_track(state, primary)
+}
+
 pub(crate) fn select_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
+    // The inspector shows (and the write targets) the current selection; keep it put.
+    if is_busy(state) {
+        return Task::none();
+    }
+
     // If the id doesn't exist in the current list, ignore.
     let Some(idx) = state.index_of_id(id) else {
         return Task::none();
@@ -98,6 +268,191 @@ pub(crate) fn select_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
     maybe_load_cover_for_track(state, id)
 }
 
+/// Track-view click with modifiers (Ctrl toggles, Shift extends a range).
+pub(crate) fn select_track_modified(
+    state: &mut Sonora,
+    id: TrackId,
+    mode: SelectMode,
+) -> Task<Message> {
+    if is_busy(state) || state.index_of_id(id).is_none() {
+        return Task::none();
+    }
+
+    match mode {
+        SelectMode::Single => return select_track(state, id),
+        SelectMode::AddToggle => {
+            state.selected_album = None;
+
+            if state.selected_tracks.remove(&id) {
+                // Removed the anchor? Fall back to another selected track.
+                if state.selected_track == Some(id) {
+                    state.selected_track = state.selected_tracks.iter().next().copied();
+                }
+            } else {
+                state.selected_tracks.insert(id);
+                state.selected_track = Some(id);
+            }
+            state.last_clicked_track = Some(id);
+        }
+        SelectMode::RangeExtend => {
+            let Some(anchor) = state.last_clicked_track.or(state.selected_track) else {
+                return select_track(state, id);
+            };
+
+            // Walk what the user sees (filtered + sorted), not raw `tracks` order.
+            let visible: Vec<TrackId> = state
+                .visible_track_indices()
+                .into_iter()
+                .filter_map(|i| state.tracks[i].id)
+                .collect();
+
+            let (Some(a), Some(b)) = (
+                visible.iter().position(|&v| v == anchor),
+                visible.iter().position(|&v| v == id),
+            ) else {
+                return select_track(state, id);
+            };
+
+            let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+            state.selected_album = None;
+            state
+                .selected_tracks
+                .extend(visible[lo..=hi].iter().copied());
+            state.selected_track = Some(id);
+            // `last_clicked_track` stays put so repeated shift-clicks pivot on the anchor.
+        }
+    }
+
+    let Some(primary) = state.selected_track else {
+        clear_selection_and_inspector(state);
+        return Task::none();
+    };
+
+    load_inspector_from_selection(state);
+    maybe_load_cover_for_track(state, primary)
+}
+
+pub(crate) fn select_all(state: &mut Sonora) -> Task<Message> {
+    // Album view with an open album: "all" means that album's visible tracks.
+    let ids: Vec<TrackId> = match (&state.view_mode, &state.selected_album) {
+        (ViewMode::Albums, Some(key)) => state
+            .album_groups
+            .get(key)
+            .map(|ids| {
+                ids.iter()
+                    .copied()
+                    .filter(|id| {
+                        state
+                            .track_by_id(*id)
+                            .is_some_and(|t| matches_filter(t, &state.filter_query))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        // Repeat plays collapse in the selection set; the newest one is primary.
+        (ViewMode::RecentlyPlayed, _) => state.recently_played().map(|(id, _)| *id).collect(),
+        _ => state
+            .visible_track_indices()
+            .into_iter()
+            .filter_map(|i| state.tracks[i].id)
+            .collect(),
+    };
+
+    let Some(&first) = ids.first() else {
+        return Task::none();
+    };
+
+    state.selected_tracks = ids.into_iter().collect();
+    state.selected_track = Some(first);
+    state.last_clicked_track = Some(first);
+    state.status = format!("Selected {} tracks.", state.selected_tracks.len());
+
+    load_inspector_from_selection(state);
+    maybe_load_cover_for_track(state, first)
+}
+
+pub(crate) fn modifiers_changed(
+    state: &mut Sonora,
+    modifiers: iced::keyboard::Modifiers,
+) -> Task<Message> {
+    state.modifiers = modifiers;
+    Task::none()
+}
+
+pub(crate) fn track_list_scrolled(state: &mut Sonora, viewport: Viewport) -> Task<Message> {
+    state.track_list_viewport = Some(viewport);
+    Task::none()
+}
+
+/// Up/Down in the track table: select the neighbouring row (clamped at the ends) and
+/// scroll just enough to show it. Nothing selected yet starts at the first / last row.
+pub(crate) fn select_adjacent_track(state: &mut Sonora, direction: Direction) -> Task<Message> {
+    let table_view = matches!(state.view_mode, ViewMode::Tracks | ViewMode::Favorites);
+    if !table_view || is_busy(state) {
+        return Task::none();
+    }
+
+    let order = state.visible_track_indices();
+    let Some(last) = order.len().checked_sub(1) else {
+        return Task::none();
+    };
+
+    let current = state
+        .selected_track
+        .and_then(|id| state.index_of_id(id))
+        .and_then(|idx| order.iter().position(|&i| i == idx));
+    let pos = match (current, direction) {
+        (Some(p), Direction::Up) => p.saturating_sub(1),
+        (Some(p), Direction::Down) => (p + 1).min(last),
+        (None, Direction::Up) => last,
+        (None, Direction::Down) => 0,
+    };
+
+    let Some(id) = state.tracks[order[pos]].id else {
+        return Task::none();
+    };
+    if current == Some(pos) && state.selected_tracks.len() <= 1 {
+        return Task::none();
+    }
+
+    let select = select_track(state, id);
+    Task::batch([select, scroll_track_into_view(state, pos)])
+}
+
+/// Scroll the track table so row `pos` (display order) is fully visible.
+fn scroll_track_into_view(state: &Sonora, pos: usize) -> Task<Message> {
+    // No viewport yet = the rows never overflowed, so everything is visible.
+    let Some(viewport) = state.track_list_viewport else {
+        return Task::none();
+    };
+
+    let (row_h, spacing) = if state.compact_mode {
+        (TRACK_ROW_H_COMPACT, TRACK_LIST_SPACING_COMPACT)
+    } else {
+        (TRACK_ROW_H, TRACK_LIST_SPACING)
+    };
+    let top = pos as f32 * (row_h + spacing);
+    let bottom = top + row_h;
+    let offset = viewport.absolute_offset().y;
+    let height = viewport.bounds().height;
+
+    let y = if top < offset {
+        top
+    } else if bottom > offset + height {
+        bottom - height
+    } else {
+        return Task::none();
+    };
+
+    operation::scroll_to(
+        TRACK_LIST_ID,
+        AbsoluteOffset {
+            x: None,
+            y: Some(y),
+        },
+    )
+}
+
 pub(crate) fn cover_loaded(
     state: &mut Sonora,
     id: TrackId,
@@ -114,26 +469,10 @@ pub(crate) fn cover_loaded(
 // Helpers
 
 fn album_key_for_index(state: &Sonora, idx: usize) -> AlbumKey {
-    let t = &state.tracks[idx];
-
-    let album_artist = t
-        .album_artist
-        .clone()
-        .or_else(|| t.artist.clone())
-        .unwrap_or_else(|| "Unknown Artist".to_string());
-
-    let album = t
-        .album
-        .clone()
-        .unwrap_or_else(|| "Unknown Album".to_string());
-
-    AlbumKey {
-        album_artist,
-        album,
-    }
+    state.album_key(&state.tracks[idx])
 }
 
-fn maybe_load_cover_for_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
+pub(crate) fn maybe_load_cover_for_track(state: &mut Sonora, id: TrackId) -> Task<Message> {
     // If we already have it, bail.
     if state.cover_cache.contains_key(&id) {
         return Task::none();
@@ -152,6 +491,34 @@ fn maybe_load_cover_for_track(state: &mut Sonora, id: TrackId) -> Task<Message>
     )
 }
 
+/// Load covers for every album's first track (the art grid's tiles) that isn't cached yet.
+pub(crate) fn load_album_covers(state: &Sonora) -> Task<Message> {
+    let todo: Vec<(TrackId, PathBuf)> = state
+        .album_groups
+        .values()
+        .filter_map(|ids| ids.first())
+        .filter(|id| !state.cover_cache.contains_key(id))
+        .filter_map(|&id| state.track_by_id(id).map(|t| (id, t.path.clone())))
+        .collect();
+
+    if todo.is_empty() {
+        return Task::none();
+    }
+
+    // One thread for the whole batch (not one per album); tiles fill in as covers arrive.
+    let (tx, rx) = async_mpsc::unbounded::<Message>();
+    std::thread::spawn(move || {
+        for (id, path) in todo {
+            let handle = load_cover_handle_from_path(&path);
+            if tx.unbounded_send(Message::CoverLoaded(id, handle)).is_err() {
+                break;
+            }
+        }
+    });
+
+    Task::stream(rx)
+}
+
 fn load_cover_handle_from_path(path: &Path) -> Option<iced::widget::image::Handle> {
     let (bytes, _mime) = crate::core::tags::read_embedded_art(path).ok()??;
     Some(iced::widget::image::Handle::from_bytes(bytes))
@@ -0,0 +1,149 @@
+//! gui/update/watch.rs
+//! Apply folder-watcher events (see `core::watcher`) without a full rescan.
+//!
+//! - Events are collected for `FOLDER_DEBOUNCE` first, so copying an album in
+//!   becomes one update instead of dozens.
+//! - Tracks whose file vanished (or whose folder was moved away) are dropped, from the
+//!   library cache too.
+//! - New `.mp3`/`.ogg`/`.flac` files and new folders are read through the library cache and appended
+//!   (scan exclusions apply here too).
+//! - Paths already in the library are ignored: our own tag writes (temp file +
+//!   rename) report as renames too.
+//! - Selection keeps whatever survived; the inspector reloads from it.
+
+use iced::Task;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::db::LibraryDb;
+use crate::core::types::{TrackId, TrackRow};
+use crate::core::{self, library};
+
+use super::super::state::{Message, Sonora, ViewMode};
+use super::super::util::is_busy;
+use super::dedup::refresh_duplicates;
+use super::inspector::load_inspector_from_selection;
+use super::scan::assign_temp_ids_if_missing;
+use super::util::spawn_blocking;
+
+const FOLDER_DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub(crate) fn folder_changed(state: &mut Sonora, path: PathBuf) -> Task<Message> {
+    state.folder_changes.insert(path);
+    if state.folder_changes_pending {
+        return Task::none();
+    }
+    state.folder_changes_pending = true;
+    settle_later()
+}
+
+fn settle_later() -> Task<Message> {
+    Task::perform(
+        spawn_blocking(|| std::thread::sleep(FOLDER_DEBOUNCE)),
+        |_| Message::FolderChangesSettled,
+    )
+}
+
+pub(crate) fn folder_changes_settled(state: &mut Sonora) -> Task<Message> {
+    // Don't race a scan or a write; look again after another window.
+    if is_busy(state) {
+        return settle_later();
+    }
+    state.folder_changes_pending = false;
+    let changed = std::mem::take(&mut state.folder_changes);
+
+    // Vanished: a changed path is the file itself or one of its folders.
+    let removed: BTreeSet<TrackId> = state
+        .tracks
+        .iter()
+        .filter(|t| changed.iter().any(|c| t.path.starts_with(c)) && !t.path.exists())
+        .filter_map(|t| t.id)
+        .collect();
+
+    let known: HashSet<&Path> = state.tracks.iter().map(|t| t.path.as_path()).collect();
+    let exclusions = library::Exclusions::new(&state.exclusions);
+    let added: Vec<PathBuf> = changed
+        .into_iter()
+        .filter(|p| !known.contains(p.as_path()))
+        .filter(|p| p.is_dir() || (p.is_file() && library::is_audio_file(p)))
+        .filter(|p| !exclusions.is_excluded(p))
+        .collect();
+
+    if !removed.is_empty() {
+        state.remove_tracks(&removed);
+        // Best-effort: a stale row would only come back on the next full scan.
+        if let Ok(mut db) = LibraryDb::open_default() {
+            let ids: Vec<TrackId> = removed.iter().copied().collect();
+            let _ = db.delete_ids(&ids);
+        }
+        if state.view_mode == ViewMode::Duplicates {
+            refresh_duplicates(state);
+        }
+        load_inspector_from_selection(state);
+        state.status = format!("Removed {} missing files from the library.", removed.len());
+    }
+
+    if added.is_empty() {
+        return Task::none();
+    }
+
+    state.scanning = true;
+    state.status = "Reading new files...".to_string();
+
+    let exclusions = state.exclusions.clone();
+    Task::perform(
+        spawn_blocking(move || {
+            let mut paths: Vec<PathBuf> = Vec::new();
+            for p in added {
+                if p.is_dir() {
+                    paths.extend(library::scan_audio_files(&p, &exclusions)?);
+                } else {
+                    paths.push(p);
+                }
+            }
+            paths.sort();
+            paths.dedup();
+
+            let (rows, _failures) = match LibraryDb::open_default() {
+                Ok(mut db) => core::read_tracks_cached(paths, &mut db, None)?,
+                Err(_) => core::read_tracks(paths, None),
+            };
+            Ok(rows)
+        }),
+        Message::FolderRescanFinished,
+    )
+}
+
+pub(crate) fn folder_rescan_finished(
+    state: &mut Sonora,
+    result: Result<Vec<TrackRow>, String>,
+) -> Task<Message> {
+    state.scanning = false;
+
+    match result {
+        Ok(rows) => {
+            // A scan may have picked some of these up meanwhile.
+            let known: HashSet<&Path> = state.tracks.iter().map(|t| t.path.as_path()).collect();
+            let fresh: Vec<TrackRow> = rows
+                .into_iter()
+                .filter(|r| !known.contains(r.path.as_path()))
+                .collect();
+            let n = fresh.len();
+
+            state.tracks.extend(fresh);
+            assign_temp_ids_if_missing(&mut state.tracks);
+            state.rebuild_library_caches();
+            if state.view_mode == ViewMode::Duplicates {
+                refresh_duplicates(state);
+            }
+
+            state.status = format!("Added {n} new files to the library.");
+        }
+        Err(e) => {
+            state.status = format!("Folder update failed: {e}");
+        }
+    }
+
+    Task::none()
+}
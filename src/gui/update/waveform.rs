@@ -0,0 +1,40 @@
+//! gui/update/waveform.rs
+//! Waveform thumbnails for the playback bar.
+//!
+//! - Computed off-thread when a track starts playing (full decode; see `core::waveform`).
+//! - Cached per `TrackId`; a failed decode caches an empty Vec so it isn't retried.
+
+use iced::Task;
+
+use super::super::state::{Message, Sonora};
+use super::util::spawn_blocking;
+use crate::core::types::TrackId;
+use crate::core::waveform::compute_waveform;
+
+/// Bars drawn across the playback bar.
+const WAVEFORM_BUCKETS: usize = 160;
+
+pub(crate) fn load_waveform(state: &Sonora, id: TrackId) -> Task<Message> {
+    if state.waveform_cache.contains_key(&id) {
+        return Task::none();
+    }
+    let Some(path) = state.track_by_id(id).map(|t| t.path.clone()) else {
+        return Task::none();
+    };
+
+    Task::perform(
+        spawn_blocking(move || {
+            compute_waveform(&path, WAVEFORM_BUCKETS).unwrap_or_else(|_e| {
+                #[cfg(debug_assertions)]
+                eprintln!("[WAVEFORM] {}: {_e}", path.display());
+                Vec::new()
+            })
+        }),
+        move |peaks| Message::WaveformReady(id, peaks),
+    )
+}
+
+pub(crate) fn waveform_ready(state: &mut Sonora, id: TrackId, peaks: Vec<f32>) -> Task<Message> {
+    state.waveform_cache.insert(id, peaks);
+    Task::none()
+}
@@ -6,10 +6,50 @@
 #![allow(dead_code)]
 
 use std::borrow::Cow;
-use std::path::Path;
+use std::cmp::Ordering;
+use std::path::{Component, Path};
 
+use super::state::{Sonora, SortColumn};
 use crate::core::types::TrackRow;
 
+/// A scan or a file write is running: controls that start another one (or that
+/// edit/select what's being written) are disabled, and their handlers ignore input.
+pub(crate) fn is_busy(state: &Sonora) -> bool {
+    state.scanning || state.saving
+}
+
+/// Split a configured command into program + arguments.
+/// Whitespace separates; double quotes group (so Windows paths with spaces work).
+pub(crate) fn split_command_line(s: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+
+    for c in s.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    out.push(std::mem::take(&mut cur));
+                    in_word = false;
+                }
+            }
+            c => {
+                cur.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        out.push(cur);
+    }
+    out
+}
+
 /// Gets filename without extension, used as a fallback title.
 /// Ex: 'song.mp3' -> 'song'
 pub(crate) fn filename_stem(path: &Path) -> String {
@@ -19,6 +59,40 @@ pub(crate) fn filename_stem(path: &Path) -> String {
         .to_string()
 }
 
+/// Shorten a long path to its root + last two components, `…` in between.
+/// Ex: `C:\Users\me\Music\Classical\Beethoven` -> `C:\…\Classical\Beethoven`
+/// - Paths that already fit are returned as-is.
+/// - If even that is too long, keeps the tail of the last component (`…thoven`).
+pub(crate) fn truncate_path(p: &Path, max_chars: usize) -> String {
+    let full = p.display().to_string();
+    if full.chars().count() <= max_chars {
+        return full;
+    }
+
+    let sep = std::path::MAIN_SEPARATOR;
+    let mut root = String::new();
+    let mut names: Vec<String> = Vec::new();
+    for c in p.components() {
+        match c {
+            Component::Prefix(prefix) => root.push_str(&prefix.as_os_str().to_string_lossy()),
+            Component::RootDir => root.push(sep),
+            other => names.push(other.as_os_str().to_string_lossy().into_owned()),
+        }
+    }
+
+    let tail = &names[names.len().saturating_sub(2)..];
+    let short = format!("{root}…{sep}{}", tail.join(&sep.to_string()));
+    if short.chars().count() <= max_chars {
+        return short;
+    }
+
+    // One very long folder name: keep its end, that's the part that differs.
+    let last = names.last().map(String::as_str).unwrap_or(&full);
+    let keep = max_chars.saturating_sub(1);
+    let skip = last.chars().count().saturating_sub(keep);
+    format!("…{}", last.chars().skip(skip).collect::<String>())
+}
+
 /// Format TrackRow into a compact one-line label for Track View.
 pub(crate) fn format_track_one_line(t: &TrackRow) -> String {
     let title: Cow<'_, str> = match t.title.as_deref() {
@@ -46,6 +120,116 @@ pub(crate) fn format_track_one_line(t: &TrackRow) -> String {
     format!("#{track_no} — {artist} — {title} ({album})")
 }
 
+/// Does a track match the search box?
+/// - empty/blank query matches everything
+/// - case-insensitive substring match on title, artist, album, album artist, genre
+pub(crate) fn matches_filter(t: &TrackRow, q: &str) -> bool {
+    let q = q.trim();
+    if q.is_empty() {
+        return true;
+    }
+    let q = q.to_lowercase();
+
+    [&t.title, &t.artist, &t.album, &t.album_artist, &t.genre]
+        .into_iter()
+        .flatten()
+        .any(|s| s.to_lowercase().contains(&q))
+}
+
+/// Case-insensitive compare without allocating.
+fn cmp_ci(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Compare optional values; blanks always sort last (in either direction).
+fn cmp_opt<T>(
+    a: Option<T>,
+    b: Option<T>,
+    ascending: bool,
+    cmp: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let o = cmp(&a, &b);
+            if ascending { o } else { o.reverse() }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Track table ordering for a header sort column.
+pub(crate) fn cmp_tracks(
+    a: &TrackRow,
+    b: &TrackRow,
+    column: SortColumn,
+    ascending: bool,
+) -> Ordering {
+    let s = |a: &Option<String>, b: &Option<String>| {
+        cmp_opt(a.as_deref(), b.as_deref(), ascending, |a, b| cmp_ci(a, b))
+    };
+
+    match column {
+        SortColumn::Unsorted => Ordering::Equal,
+        SortColumn::TrackNo => cmp_opt(a.track_no, b.track_no, ascending, Ord::cmp),
+        SortColumn::Title => s(&a.title, &b.title),
+        SortColumn::Artist => s(&a.artist, &b.artist),
+        SortColumn::Album => s(&a.album, &b.album),
+        SortColumn::AlbumArtist => s(&a.album_artist, &b.album_artist),
+        SortColumn::Year => cmp_opt(a.year, b.year, ascending, Ord::cmp),
+        SortColumn::Genre => s(&a.genre, &b.genre),
+        SortColumn::Duration => cmp_opt(a.duration_ms, b.duration_ms, ascending, Ord::cmp),
+        SortColumn::DateAdded => s(&a.date_added, &b.date_added),
+        SortColumn::Favorite => {
+            let o = b.is_favorite.cmp(&a.is_favorite);
+            if ascending { o } else { o.reverse() }
+        }
+    }
+}
+
+/// Library-length style total: "8d 14h 3m", zero parts skipped ("2h", "45m"; "0m" if empty).
+pub(crate) fn format_total_duration_ms(ms: u64) -> String {
+    let min = ms / 60_000;
+    let (d, h, m) = (min / (24 * 60), (min / 60) % 24, min % 60);
+
+    let parts: Vec<String> = [(d, "d"), (h, "h"), (m, "m")]
+        .into_iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect();
+
+    if parts.is_empty() {
+        "0m".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// 2847 -> "2,847".
+pub(crate) fn format_count(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Album running order: (disc, track, title), missing numbers first.
+pub(crate) fn album_order_key(t: &TrackRow) -> (u32, u32, String) {
+    (
+        t.disc_no.unwrap_or(0),
+        t.track_no.unwrap_or(0),
+        t.title.clone().unwrap_or_default(),
+    )
+}
+
 /// Turn a string into Option<String>.
 /// - empty string -> None
 /// - non-empty -> Some(trimmed_string)
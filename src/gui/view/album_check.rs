@@ -0,0 +1,75 @@
+//! gui/view/album_check.rs
+//! "Check albums" report (center panel): albums whose track numbers skip or repeat.
+//!
+//! - Multi-disc albums get one row per problematic disc.
+//! - Clicking a row opens that album in Album View.
+
+use iced::widget::{Column, button, column, container, mouse_area, row, scrollable, text};
+use iced::{Alignment, Length};
+
+use super::super::state::Message;
+use super::constants::{ROW_TEXT, TRACK_LIST_SPACING};
+use crate::core::lint::AlbumLint;
+
+pub(crate) fn build_album_check_center(report: &[AlbumLint]) -> Column<'_, Message> {
+    let header = row![
+        text(format!("Check albums ({} issues)", report.len()))
+            .size(18)
+            .width(Length::Fill),
+        button(text("Close").size(12)).on_press(Message::CloseAlbumCheck),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    if report.is_empty() {
+        return column![
+            header,
+            text("No gaps or repeated track numbers in any album.").size(12)
+        ]
+        .spacing(12);
+    }
+
+    let mut list: Column<'_, Message> = column![].spacing(TRACK_LIST_SPACING);
+
+    for lint in report {
+        let album = match lint.disc_no {
+            Some(d) => format!("{} (disc {d})", lint.key.album),
+            None => lint.key.album.clone(),
+        };
+
+        let mut problems: Vec<String> = Vec::new();
+        if !lint.gaps.is_empty() {
+            problems.push(format!("missing {}", join_nos(&lint.gaps)));
+        }
+        if !lint.duplicate_nos.is_empty() {
+            problems.push(format!("repeated {}", join_nos(&lint.duplicate_nos)));
+        }
+
+        let line = row![
+            text(album).size(ROW_TEXT).width(Length::FillPortion(3)),
+            text(&lint.key.album_artist)
+                .size(12)
+                .width(Length::FillPortion(2)),
+            text(problems.join("; "))
+                .size(12)
+                .width(Length::FillPortion(3)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        list = list.push(
+            mouse_area(container(line).padding([2, 8]))
+                .on_press(Message::OpenAlbumIssue(lint.key.clone())),
+        );
+    }
+
+    column![header, scrollable(list).height(Length::Fill)].spacing(12)
+}
+
+/// "#4, #7" style list of track numbers.
+fn join_nos(nos: &[u32]) -> String {
+    nos.iter()
+        .map(|n| format!("#{n}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
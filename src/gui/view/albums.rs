@@ -4,14 +4,18 @@
 //! - Album grouping is cached in `state.album_groups` (AlbumKey -> Vec<TrackId>).
 //! - Cover cache is keyed by `TrackId`.
 //! - Track row click emits `Message::SelectTrack(track_id)`.
+//! - Multi-disc albums get a "Disc n of m" header before each disc in the detail list.
 
-use iced::widget::{Column, column, container, mouse_area, row, scrollable, text};
-use iced::{Alignment, Length};
+use std::collections::BTreeSet;
 
-use super::super::state::{AlbumKey, Message, Sonora};
-use super::super::util::filename_stem;
+use iced::widget::{Column, button, column, container, mouse_area, row, scrollable, text};
+use iced::{Alignment, Font, Length, font};
+
+use super::super::state::{AlbumKey, Message, SelectMode, Sonora};
+use super::super::util::{album_order_key, filename_stem, is_busy, matches_filter};
 use super::constants::{
-    ALBUM_LIST_H, ALBUM_LIST_SPACING, ALBUM_ROW_COVER, ALBUM_ROW_H, COVER_BIG, ROW_TEXT,
+    ALBUM_DETAIL_PORTION, ALBUM_LIST_PORTION, ALBUM_LIST_SPACING, ALBUM_ROW_COVER,
+    ALBUM_ROW_COVER_COMPACT, ALBUM_ROW_H, ALBUM_ROW_H_COMPACT, COVER_BIG, HEADER_TEXT, ROW_TEXT,
     TRACK_LIST_SPACING, TRACK_ROW_H, TRACK_ROW_HPAD, TRACK_ROW_VPAD,
 };
 use super::widgets::{cover_thumb, fmt_duration};
@@ -20,13 +24,7 @@ use crate::core::types::TrackId;
 pub(crate) fn build_albums_center(state: &Sonora) -> Column<'_, Message> {
     let selected_key: Option<AlbumKey> = state.selected_album.clone();
 
-    // For list display: (key, track_count, representative_track_id)
-    let albums: Vec<(AlbumKey, usize, TrackId)> = state
-        .album_groups
-        .iter()
-        .filter_map(|(k, v)| v.first().copied().map(|rep| (k.clone(), v.len(), rep)))
-        .collect();
-
+    let albums = visible_albums(state);
     let list = build_album_list(state, selected_key.clone(), albums);
 
     let selected_payload: Option<(AlbumKey, Vec<TrackId>)> = state
@@ -38,12 +36,59 @@ pub(crate) fn build_albums_center(state: &Sonora) -> Column<'_, Message> {
 
     column![
         text("Albums").size(18),
-        list.height(Length::Fixed(ALBUM_LIST_H)),
-        detail.height(Length::Fill),
+        list.height(Length::FillPortion(ALBUM_LIST_PORTION)),
+        detail.height(Length::FillPortion(ALBUM_DETAIL_PORTION)),
     ]
     .spacing(12)
 }
 
+/// Albums to list, in display order: (key, track_count, representative_track_id).
+/// Albums with no track matching the filter are hidden; counts reflect matches.
+pub(super) fn visible_albums(state: &Sonora) -> Vec<(AlbumKey, usize, TrackId)> {
+    let mut albums: Vec<(AlbumKey, usize, TrackId)> = state
+        .album_groups
+        .iter()
+        .filter_map(|(k, v)| {
+            let matching: Vec<TrackId> = v
+                .iter()
+                .copied()
+                .filter(|id| is_visible(state, *id))
+                .collect();
+            matching
+                .first()
+                .copied()
+                .map(|rep| (k.clone(), matching.len(), rep))
+        })
+        .collect();
+
+    // Artists order by their sort name (see `AlbumKey`); within an artist,
+    // albums order by their sort tag (TSOA) when present.
+    albums.sort_by_cached_key(|(k, _, rep)| {
+        let album = state
+            .track_by_id(*rep)
+            .and_then(|t| t.album_sort.clone())
+            .unwrap_or_else(|| k.album.clone());
+        (k.album_artist_sort.clone(), album)
+    });
+
+    albums
+}
+
+/// Album name, with "(year)" when albums are grouped by year.
+pub(super) fn album_title(key: &AlbumKey) -> String {
+    match key.year {
+        Some(year) => format!("{} ({year})", key.album),
+        None => key.album.clone(),
+    }
+}
+
+/// Track passes the search filter (unknown ids are hidden).
+fn is_visible(state: &Sonora, id: TrackId) -> bool {
+    state
+        .track_by_id(id)
+        .is_some_and(|t| matches_filter(t, &state.filter_query))
+}
+
 fn build_album_list(
     state: &Sonora,
     selected: Option<AlbumKey>,
@@ -51,36 +96,64 @@ fn build_album_list(
 ) -> iced::widget::Scrollable<'static, Message> {
     let mut col: Column<'static, Message> = column![].spacing(ALBUM_LIST_SPACING);
 
+    // Compact mode: shorter rows, smaller cover and text (two lines still fit).
+    let (row_h, cover_size, vpad, title_size, artist_size, line_gap) = if state.compact_mode {
+        (
+            ALBUM_ROW_H_COMPACT,
+            ALBUM_ROW_COVER_COMPACT,
+            3.0,
+            12.0,
+            11.0,
+            0.0,
+        )
+    } else {
+        (ALBUM_ROW_H, ALBUM_ROW_COVER, 6.0, 14.0, 12.0, 2.0)
+    };
+
     for (key, count, rep_id) in albums {
-        let is_selected = selected.as_ref() == Some(&key);
+        // The open album, or one fully added with Ctrl+click.
+        let is_selected = selected.as_ref() == Some(&key)
+            || state.album_groups.get(&key).is_some_and(|ids| {
+                !ids.is_empty() && ids.iter().all(|id| state.selected_tracks.contains(id))
+            });
 
         let title_line = if is_selected {
-            format!("● {}", key.album)
+            format!("● {}", album_title(&key))
         } else {
-            key.album.clone()
+            album_title(&key)
         };
         let artist_line = key.album_artist.clone();
         let count_line = format!("{count} tracks");
 
-        let cover = cover_thumb(state.cover_cache.get(&rep_id), ALBUM_ROW_COVER);
+        let cover = cover_thumb(state.cover_cache.get(&rep_id), cover_size);
 
         let row_cells = row![
             cover,
-            column![text(title_line).size(14), text(artist_line).size(12)]
-                .spacing(2)
-                .width(Length::Fill),
+            column![
+                text(title_line).size(title_size),
+                text(artist_line).size(artist_size)
+            ]
+            .spacing(line_gap)
+            .width(Length::Fill),
             text(count_line).size(12).width(Length::Fixed(90.0)),
         ]
         .spacing(12)
         .align_y(Alignment::Center);
 
+        // Ctrl/Cmd+click adds the album to the selection instead of replacing it.
+        let msg = if state.modifiers.command() {
+            Message::SelectAlbumModified(key, SelectMode::AddToggle)
+        } else {
+            Message::SelectAlbum(key)
+        };
+
         let row_widget = mouse_area(
             container(row_cells)
-                .padding([6, 8])
-                .height(Length::Fixed(ALBUM_ROW_H))
+                .padding([vpad, 8.0])
+                .height(Length::Fixed(row_h))
                 .width(Length::Fill),
         )
-        .on_press(Message::SelectAlbum(key));
+        .on_press(msg);
 
         col = col.push(row_widget);
     }
@@ -103,28 +176,20 @@ fn build_album_detail(
     // Resolve ids -> indices defensively (avoid panics if the list changed).
     let mut idxs: Vec<usize> = track_ids
         .into_iter()
+        .filter(|id| is_visible(state, *id))
         .filter_map(|id| state.index_of_id(id))
         .collect();
 
+    if idxs.is_empty() && !state.filter_query.trim().is_empty() {
+        return container(text("No tracks in this album match the search.")).padding(12);
+    }
+
     if idxs.is_empty() {
         return container(text("Album tracks are out of range (rescan?).")).padding(12);
     }
 
     // Sort by (disc, track, title) for a sane album ordering.
-    idxs.sort_by(|&a, &b| {
-        let ta = &state.tracks[a];
-        let tb = &state.tracks[b];
-        (
-            ta.disc_no.unwrap_or(0),
-            ta.track_no.unwrap_or(0),
-            ta.title.clone().unwrap_or_default(),
-        )
-            .cmp(&(
-                tb.disc_no.unwrap_or(0),
-                tb.track_no.unwrap_or(0),
-                tb.title.clone().unwrap_or_default(),
-            ))
-    });
+    idxs.sort_by_cached_key(|&i| album_order_key(&state.tracks[i]));
 
     let first_idx = idxs[0];
     let first = &state.tracks[first_idx];
@@ -142,13 +207,44 @@ fn build_album_detail(
         .map(|h| cover_thumb(Some(h), COVER_BIG))
         .unwrap_or_else(|| cover_thumb(None, COVER_BIG));
 
+    // Tag fixes for the whole album (both go through the undo stack).
+    let album_actions: Column<'_, Message> = if state.show_album_actions {
+        let idle = !is_busy(state);
+        column![
+            row![
+                button(text("Fix album tags").size(12))
+                    .on_press_maybe(idle.then(|| Message::FixAlbumTags(key.clone()))),
+                button(text("Renumber tracks").size(12))
+                    .on_press_maybe(idle.then(|| Message::ResequenceAlbum(key.clone()))),
+            ]
+            .spacing(8)
+        ]
+    } else {
+        column![]
+    };
+
     let header = row![
         big_cover,
         column![
-            text(key.album.clone()).size(26),
+            text(album_title(&key)).size(26),
             text(key.album_artist.clone()).size(18),
             text(format!("{genre} • {year}")).size(14),
             text(format!("{} songs", idxs.len())).size(12),
+            row![
+                button(text("Add album to queue").size(12))
+                    .on_press(Message::EnqueueAlbum(key.clone())),
+                button(
+                    text(if state.show_album_actions {
+                        "Album actions ▴"
+                    } else {
+                        "Album actions ▾"
+                    })
+                    .size(12)
+                )
+                .on_press(Message::ToggleAlbumActions),
+            ]
+            .spacing(8),
+            album_actions,
         ]
         .spacing(6)
         .width(Length::Fill),
@@ -158,10 +254,35 @@ fn build_album_detail(
 
     let mut list = column![].spacing(TRACK_LIST_SPACING);
 
+    // Multi-disc: a "Disc n of m" header starts each disc (idxs are already disc-ordered).
+    let discs: BTreeSet<Option<u32>> = idxs.iter().map(|&i| state.tracks[i].disc_no).collect();
+    let disc_count = discs.iter().flatten().count() as u32;
+    let disc_total = idxs
+        .iter()
+        .filter_map(|&i| state.tracks[i].disc_total)
+        .max()
+        .unwrap_or(disc_count);
+    let mut current_disc: Option<Option<u32>> = None;
+
     for &i in &idxs {
         let t = &state.tracks[i];
         let Some(id) = t.id else { continue };
 
+        if discs.len() > 1 && current_disc != Some(t.disc_no) {
+            current_disc = Some(t.disc_no);
+            let label = match t.disc_no {
+                Some(n) => format!("Disc {n} of {disc_total}"),
+                None => "No disc number".to_string(),
+            };
+            list = list.push(
+                container(text(label).size(HEADER_TEXT).font(Font {
+                    weight: font::Weight::Bold,
+                    ..Font::DEFAULT
+                }))
+                .padding([6.0, TRACK_ROW_HPAD]),
+            );
+        }
+
         let n = t
             .track_no
             .map(|n| n.to_string())
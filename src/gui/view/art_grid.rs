@@ -0,0 +1,51 @@
+//! gui/view/art_grid.rs
+//! Art grid ("cover wall"): every visible album as a cover tile.
+//!
+//! - Same albums, order and search filter as the album list.
+//! - Tiles wrap to the panel width; the grid scrolls vertically.
+//! - Covers come from `state.cover_cache`, keyed by each album's first track id
+//!   (the one `load_album_covers` fetches); missing ones show the placeholder.
+//! - Clicking a tile opens that album in Album View.
+
+use iced::widget::{Column, column, mouse_area, row, scrollable, text};
+use iced::{Alignment, Length};
+
+use super::super::state::{Message, Sonora};
+use super::albums::{album_title, visible_albums};
+use super::constants::{ART_GRID_SPACING, ART_TILE};
+use super::widgets::cover_thumb;
+
+pub(crate) fn build_art_grid_center(state: &Sonora) -> Column<'_, Message> {
+    let albums = visible_albums(state);
+    let count = albums.len();
+
+    let tiles = albums.into_iter().map(|(key, _count, _rep)| {
+        let cover_id = state.album_groups.get(&key).and_then(|ids| ids.first());
+        let cover = cover_thumb(cover_id.and_then(|id| state.cover_cache.get(id)), ART_TILE);
+
+        let tile = column![
+            cover,
+            text(album_title(&key))
+                .size(13)
+                .width(Length::Fixed(ART_TILE)),
+            text(key.album_artist.clone())
+                .size(11)
+                .width(Length::Fixed(ART_TILE)),
+        ]
+        .spacing(4)
+        .align_x(Alignment::Start);
+
+        mouse_area(tile).on_press(Message::SelectAlbum(key)).into()
+    });
+
+    let grid = row(tiles)
+        .spacing(ART_GRID_SPACING)
+        .wrap()
+        .vertical_spacing(ART_GRID_SPACING);
+
+    column![
+        text(format!("Albums ({count})")).size(18),
+        scrollable(grid).height(Length::Fill).width(Length::Fill),
+    ]
+    .spacing(12)
+}
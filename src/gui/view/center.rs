@@ -1,17 +1,99 @@
 //! gui/view/center.rs
-//! Center panel router (tracks vs albums).
+//! Center panel router (tracks / albums / art grid / duplicates / recently played; the
+//! queue, a lint report (missing tags, album check) or the statistics overview replace
+//! them while open).
+//!
+//! With no tracks loaded, the library views show a "get started" placeholder instead
+//! (or a scanning notice while the first scan runs).
 
-use iced::widget::container;
+use iced::widget::{Column, button, column, container, row, text, text_input};
+use iced::{Alignment, Length};
 
 use super::super::state::{Message, Sonora, ViewMode};
+use super::album_check::build_album_check_center;
 use super::albums::build_albums_center;
+use super::art_grid::build_art_grid_center;
+use super::constants::EMPTY_STATE_ICON;
+use super::duplicates::build_duplicates_center;
+use super::missing_tags::build_missing_tags_center;
+use super::queue::build_queue_panel;
+use super::recent::build_recently_played_center;
+use super::stats::build_stats_center;
 use super::tracks::build_tracks_center;
 
 pub(crate) fn build_center_panel(state: &Sonora) -> iced::widget::Container<'_, Message> {
+    // The queue replaces the library views (search doesn't apply to it).
+    if state.show_queue {
+        return build_queue_panel(state);
+    }
+
+    // An open "Missing tags" report also covers the library views.
+    if let Some(report) = &state.missing_tag_report {
+        return container(build_missing_tags_center(state, report)).padding(12);
+    }
+    if let Some(report) = &state.album_check_report {
+        return container(build_album_check_center(report)).padding(12);
+    }
+    if state.show_stats {
+        return container(build_stats_center(&state.library_stats)).padding(12);
+    }
+
+    // Library views have nothing to list yet (the others explain their own emptiness).
+    let library_view = matches!(
+        state.view_mode,
+        ViewMode::Tracks | ViewMode::Albums | ViewMode::ArtGrid | ViewMode::Favorites
+    );
+    if library_view && state.tracks.is_empty() {
+        return container(build_empty_state(state))
+            .center(Length::Fill)
+            .padding(12);
+    }
+
     let inner: iced::Element<'_, Message> = match state.view_mode {
-        ViewMode::Tracks => build_tracks_center(state).into(),
+        ViewMode::Tracks | ViewMode::Favorites => build_tracks_center(state).into(),
         ViewMode::Albums => build_albums_center(state).into(),
+        ViewMode::ArtGrid => build_art_grid_center(state).into(),
+        // Duplicate groups are library-wide; the search box doesn't apply.
+        ViewMode::Duplicates => {
+            return container(build_duplicates_center(state)).padding(12);
+        }
+        ViewMode::RecentlyPlayed => {
+            return container(build_recently_played_center(state)).padding(12);
+        }
+    };
+
+    let search = text_input("Search title, artist, album, genre…", &state.filter_query)
+        .on_input(Message::FilterChanged)
+        .width(Length::Fill);
+
+    let clear_btn = if state.filter_query.is_empty() {
+        button("×")
+    } else {
+        button("×").on_press(Message::FilterChanged(String::new()))
+    };
+
+    let search_row = row![search, clear_btn].spacing(8);
+
+    container(column![search_row, inner].spacing(12)).padding(12)
+}
+
+/// Centered "♪ / heading / hint" placeholder for an empty library.
+fn build_empty_state(state: &Sonora) -> Column<'_, Message> {
+    let (heading, hint) = if state.scanning {
+        // The status line carries the live "n / total" count.
+        ("Scanning…", state.status.as_str())
+    } else {
+        (
+            "No tracks loaded",
+            "Add a folder and click Scan Library to get started",
+        )
     };
 
-    container(inner).padding(12)
+    column![
+        text("♪").size(EMPTY_STATE_ICON),
+        text(heading).size(20),
+        text(hint).size(13),
+    ]
+    .spacing(8)
+    .align_x(Alignment::Center)
 }
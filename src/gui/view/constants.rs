@@ -8,10 +8,19 @@ pub(crate) const WINDOW_W: f32 = 960.0;
 pub(crate) const WINDOW_H: f32 = 720.0;
 
 // Layout
-pub(crate) const PLAYBACK_H: f32 = 76.0;
+pub(crate) const PLAYBACK_H: f32 = 104.0;
+pub(crate) const LYRICS_H: f32 = 160.0;
 pub(crate) const SIDEBAR_W: f32 = 260.0;
+/// Roots list: path characters per line (~1.6 chars of 12 px text per `ROW_TEXT` of
+/// sidebar width, leaving room for the remove button). Longer paths get `truncate_path`.
+pub(crate) const ROOT_PATH_MAX_CHARS: usize = (SIDEBAR_W / ROW_TEXT * 1.6) as usize;
 pub(crate) const EDITOR_W: f32 = 380.0;
 pub(crate) const LABEL_W: f32 = 110.0;
+pub(crate) const CLEAR_BTN_W: f32 = 26.0;
+/// Scan / batch save progress bars (sidebar, under the scan button).
+pub(crate) const PROGRESS_BAR_H: f32 = 8.0;
+/// Scan error log (sidebar): scrolls on its own past this height.
+pub(crate) const SCAN_ERRORS_H: f32 = 200.0;
 
 // Text
 pub(crate) const HEADER_TEXT: f32 = 14.0;
@@ -22,12 +31,43 @@ pub(crate) const TRACK_ROW_H: f32 = 26.0;
 pub(crate) const TRACK_ROW_VPAD: f32 = 2.0;
 pub(crate) const TRACK_ROW_HPAD: f32 = 8.0;
 pub(crate) const TRACK_LIST_SPACING: f32 = 1.0;
+/// Alpha of the primary-color tint behind the now-playing row.
+pub(crate) const NOW_PLAYING_TINT: f32 = 0.18;
+// Compact mode (`state.compact_mode`): denser track rows for big libraries.
+pub(crate) const TRACK_ROW_H_COMPACT: f32 = 18.0;
+pub(crate) const TRACK_ROW_VPAD_COMPACT: f32 = 1.0;
+pub(crate) const ROW_TEXT_COMPACT: f32 = 12.0;
+pub(crate) const TRACK_LIST_SPACING_COMPACT: f32 = 0.0;
+/// Column settings panel: px per −/+ click.
+pub(crate) const COLUMN_WIDTH_STEP: f32 = 10.0;
 
 // Album list
-pub(crate) const ALBUM_LIST_H: f32 = 260.0;
+// Lists fill the height left over by the fixed chrome (playback bar, sidebar, inspector);
+// the album view splits it list : detail by these portions.
+pub(crate) const ALBUM_LIST_PORTION: u16 = 2;
+pub(crate) const ALBUM_DETAIL_PORTION: u16 = 3;
 pub(crate) const ALBUM_ROW_H: f32 = 56.0;
 pub(crate) const ALBUM_ROW_COVER: f32 = 44.0;
 pub(crate) const ALBUM_LIST_SPACING: f32 = 1.0;
+pub(crate) const ALBUM_ROW_H_COMPACT: f32 = 36.0;
+pub(crate) const ALBUM_ROW_COVER_COMPACT: f32 = 28.0;
+
+// Empty library placeholder (center panel)
+pub(crate) const EMPTY_STATE_ICON: f32 = 64.0;
+
+// Find & replace dialog
+pub(crate) const FIND_REPLACE_W: f32 = 560.0;
+pub(crate) const FIND_REPLACE_PREVIEW_H: f32 = 240.0;
+/// Preview rows drawn at most (the count line still covers every match).
+pub(crate) const FIND_REPLACE_PREVIEW_MAX: usize = 200;
+
+// Art grid (cover wall)
+pub(crate) const ART_TILE: f32 = 180.0;
+pub(crate) const ART_GRID_SPACING: f32 = 16.0;
+
+// Waveform (playback bar)
+pub(crate) const WAVEFORM_H: f32 = 22.0;
+pub(crate) const WAVEFORM_BAR_SPACING: f32 = 1.0;
 
 // Artwork
 pub(crate) const COVER_BIG: f32 = 220.0;
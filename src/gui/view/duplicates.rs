@@ -0,0 +1,105 @@
+//! gui/view/duplicates.rs
+//! Duplicates view (center panel): groups of likely duplicate tracks.
+//!
+//! - Each group is a collapsible header (title — artist, count).
+//! - Expanded rows show path + length so the user can tell copies apart;
+//!   the checkbox marks a file to be moved to the trash.
+//! - Clicking a row selects the track (inspector), like the track table.
+
+use iced::widget::{
+    Column, button, checkbox, column, container, mouse_area, row, scrollable, text,
+};
+use iced::{Alignment, Length};
+
+use super::super::state::{Message, Sonora};
+use super::super::util::{filename_stem, is_busy};
+use super::constants::{ROW_TEXT, TRACK_LIST_SPACING};
+use super::widgets::fmt_duration;
+
+pub(crate) fn build_duplicates_center(state: &Sonora) -> Column<'_, Message> {
+    let marked = state.marked_for_deletion.len();
+    let delete_btn = if marked > 0 && !is_busy(state) {
+        button(text(format!("Delete marked ({marked})")).size(12)).on_press(Message::DeleteMarked)
+    } else {
+        button(text(format!("Delete marked ({marked})")).size(12))
+    };
+
+    let header = row![
+        text(format!(
+            "Duplicates ({} groups)",
+            state.duplicate_groups.len()
+        ))
+        .size(18)
+        .width(Length::Fill),
+        button(text("Re-scan for duplicates").size(12)).on_press(Message::FindDuplicates),
+        delete_btn,
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    if state.duplicate_groups.is_empty() {
+        return column![header, text("No duplicates found.").size(12)].spacing(12);
+    }
+
+    let mut list: Column<'_, Message> = column![].spacing(TRACK_LIST_SPACING);
+
+    for (gi, group) in state.duplicate_groups.iter().enumerate() {
+        let expanded = state.expanded_duplicate_groups.contains(&gi);
+
+        let label = match group.first().and_then(|&id| state.track_by_id(id)) {
+            Some(t) => format!(
+                "{} {} — {} ({})",
+                if expanded { "▾" } else { "▸" },
+                t.title.clone().unwrap_or_else(|| filename_stem(&t.path)),
+                t.artist.clone().unwrap_or_else(|| "Unknown".into()),
+                group.len()
+            ),
+            None => continue,
+        };
+
+        list = list.push(
+            button(text(label).size(ROW_TEXT))
+                .on_press(Message::ToggleDuplicateGroup(gi))
+                .width(Length::Fill),
+        );
+
+        if !expanded {
+            continue;
+        }
+
+        for &id in group {
+            let Some(t) = state.track_by_id(id) else {
+                continue;
+            };
+
+            let marker = if state.selected_tracks.contains(&id) {
+                "●"
+            } else {
+                ""
+            };
+
+            let line = row![
+                checkbox(state.marked_for_deletion.contains(&id))
+                    .on_toggle(move |_| Message::MarkForDeletion(id)),
+                text(marker).size(ROW_TEXT).width(Length::Fixed(16.0)),
+                column![
+                    text(t.album.clone().unwrap_or_else(|| "Unknown Album".into())).size(ROW_TEXT),
+                    text(t.path.display().to_string()).size(12),
+                ]
+                .spacing(2)
+                .width(Length::Fill),
+                text(fmt_duration(t.duration_ms))
+                    .size(12)
+                    .width(Length::Fixed(44.0)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            list = list.push(
+                mouse_area(container(line).padding([2, 24])).on_press(Message::SelectTrack(id)),
+            );
+        }
+    }
+
+    column![header, scrollable(list).height(Length::Fill)].spacing(12)
+}
@@ -0,0 +1,120 @@
+//! gui/view/find_replace.rs
+//! "Find & replace" dialog, drawn over the whole window while open.
+//!
+//! - Field chooser, find / replace inputs, regex toggle.
+//! - "Preview" lists the matches (before -> after); "Replace all" writes them.
+
+use iced::widget::{
+    Column, button, center, checkbox, column, container, opaque, row, scrollable, text, text_input,
+};
+use iced::{Color, Element, Length, Theme};
+
+use super::super::state::{FIND_REPLACE_FIELDS, FindReplaceDraft, InspectorField, Message, Sonora};
+use super::super::util::is_busy;
+use super::constants::{FIND_REPLACE_PREVIEW_H, FIND_REPLACE_PREVIEW_MAX, FIND_REPLACE_W};
+
+fn field_label(field: InspectorField) -> &'static str {
+    match field {
+        InspectorField::Title => "Title",
+        InspectorField::Artist => "Artist",
+        InspectorField::Album => "Album",
+        InspectorField::AlbumArtist => "Album artist",
+        InspectorField::Composer => "Composer",
+        InspectorField::Genre => "Genre",
+        InspectorField::Grouping => "Grouping",
+        _ => "",
+    }
+}
+
+/// The dialog on a dimmed backdrop that swallows clicks to the window below.
+pub(crate) fn find_replace_overlay<'a>(
+    state: &'a Sonora,
+    draft: &'a FindReplaceDraft,
+) -> Element<'a, Message> {
+    let dialog = container(build_dialog(state, draft))
+        .width(Length::Fixed(FIND_REPLACE_W))
+        .padding(16)
+        .style(container::rounded_box);
+
+    opaque(center(dialog).style(|_theme: &Theme| {
+        container::background(Color {
+            a: 0.5,
+            ..Color::BLACK
+        })
+    }))
+}
+
+fn build_dialog<'a>(state: &'a Sonora, draft: &'a FindReplaceDraft) -> Column<'a, Message> {
+    let busy = is_busy(state);
+
+    let mut fields = row![].spacing(6);
+    for field in FIND_REPLACE_FIELDS {
+        let label = field_label(field);
+        fields = fields.push(if field == draft.field {
+            button(text(format!("✓ {label}")).size(12))
+        } else {
+            button(text(label).size(12)).on_press(Message::FindReplaceFieldChanged(field))
+        });
+    }
+
+    let find_input = text_input("Find", &draft.find)
+        .on_input(Message::FindReplaceFindChanged)
+        .on_submit(Message::PreviewFindReplace)
+        .width(Length::Fill);
+    let replace_input = text_input("Replace with", &draft.replace)
+        .on_input(Message::FindReplaceReplaceChanged)
+        .on_submit(Message::PreviewFindReplace)
+        .width(Length::Fill);
+
+    let can_run = !busy && !draft.find.is_empty();
+    let preview_btn =
+        button("Preview").on_press_maybe(can_run.then_some(Message::PreviewFindReplace));
+    let replace_btn = button("Replace all").on_press_maybe(can_run.then(|| Message::FindReplace {
+        field: draft.field,
+        find: draft.find.clone(),
+        replace: draft.replace.clone(),
+        regex: draft.regex,
+    }));
+    let close_btn = button("Close").on_press(Message::CloseFindReplace);
+
+    let mut col = column![
+        text("Find & replace").size(18),
+        fields.wrap().vertical_spacing(6),
+        find_input,
+        replace_input,
+        checkbox(draft.regex)
+            .label("Regular expression ($1 etc. in the replacement)")
+            .on_toggle(Message::FindReplaceRegexToggled),
+        row![preview_btn, replace_btn, close_btn].spacing(8),
+    ]
+    .spacing(10);
+
+    if let Some(preview) = &draft.preview {
+        let mut list = column![].spacing(4);
+        for (id, before, after) in preview.iter().take(FIND_REPLACE_PREVIEW_MAX) {
+            let title = state
+                .track_by_id(*id)
+                .and_then(|t| t.title.clone())
+                .unwrap_or_default();
+            list = list.push(column![
+                text(title).size(11),
+                text(format!("{before}  →  {after}")).size(13),
+            ]);
+        }
+        if preview.len() > FIND_REPLACE_PREVIEW_MAX {
+            list = list.push(
+                text(format!(
+                    "…and {} more",
+                    preview.len() - FIND_REPLACE_PREVIEW_MAX
+                ))
+                .size(12),
+            );
+        }
+
+        col = col
+            .push(text(format!("{} matching tracks", preview.len())).size(12))
+            .push(scrollable(list).height(Length::Fixed(FIND_REPLACE_PREVIEW_H)));
+    }
+
+    col
+}
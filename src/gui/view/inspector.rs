@@ -4,25 +4,29 @@
 //! - Selection is TrackId-based.
 //! - We resolve id -> TrackRow on demand for display.
 
-use iced::Alignment;
-use iced::Length;
 use iced::widget::Row;
 use iced::widget::{
-    Column, button, checkbox, column, container, row, scrollable, text, text_input,
+    Column, button, checkbox, column, container, row, scrollable, space, text, text_input,
 };
+use iced::{Alignment, Element, Length};
 
-use super::super::state::{InspectorField as Field, Message, Sonora};
-use super::widgets::fmt_duration;
+use super::super::state::{
+    CommentPart, InspectorField as Field, Message, Sonora, TAG_CLIPBOARD_FIELDS,
+};
+use super::super::util::is_busy;
+use super::widgets::{fmt_channels, fmt_duration, fmt_tag_format};
 
-use super::constants::LABEL_W;
+use super::constants::{CLEAR_BTN_W, LABEL_W};
 use crate::core::types::TrackId;
 
 /// Field row that appends " (mixed)" to the label when mixed.
+/// The input is read-only while `enabled` is false (a scan or write is running).
 fn field_row_mixed<'a>(
     label: &'a str,
+    field: Field,
     value: &'a str,
     mixed: bool,
-    on_input: impl Fn(String) -> Message + 'a,
+    enabled: bool,
 ) -> Row<'a, Message> {
     let label = if mixed {
         format!("{label} (mixed)")
@@ -32,23 +36,163 @@ fn field_row_mixed<'a>(
 
     row![
         text(label).width(Length::Fixed(LABEL_W)),
-        text_input("", value).on_input(on_input).width(Length::Fill),
+        text_input("", value)
+            .on_input_maybe(enabled.then_some(move |s| Message::InspectorChanged(field, s)))
+            .width(Length::Fill),
+        clear_btn(field, value, enabled),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center)
+}
+
+/// Label + value that can be selected/copied but not edited (ids filled by lookups).
+fn read_only_row<'a>(label: &'a str, value: &'a str) -> Row<'a, Message> {
+    row![
+        text(label).width(Length::Fixed(LABEL_W)),
+        text_input("-", value).width(Length::Fill),
+        space().width(Length::Fixed(CLEAR_BTN_W)),
     ]
     .spacing(8)
     .align_y(Alignment::Center)
 }
 
+/// "✕" that empties one draft field (removes the tag on save, from every selected
+/// file in batch mode). Blank fields get a same-width gap so the inputs stay aligned.
+fn clear_btn<'a>(field: Field, value: &str, enabled: bool) -> Element<'a, Message> {
+    if value.is_empty() {
+        return space().width(Length::Fixed(CLEAR_BTN_W)).into();
+    }
+
+    button(text("✕").size(12))
+        .padding([2, 6])
+        .width(Length::Fixed(CLEAR_BTN_W))
+        .style(button::text)
+        .on_press_maybe(enabled.then_some(Message::ClearField(field)))
+        .into()
+}
+
+/// "Comments" (COMM): language / description / text per frame, plus "Add comment".
+/// Only for a single selected file; the list replaces all of its comment frames on save.
+fn comment_frames(state: &Sonora) -> Column<'_, Message> {
+    let header = text("Comments (COMM)").size(14);
+
+    if state.selected_tracks.len() > 1 {
+        return column![
+            header,
+            text("Select a single track to edit every comment frame.").size(12)
+        ]
+        .spacing(6);
+    }
+
+    let enabled = !is_busy(state);
+    let mut col = column![header].spacing(6);
+
+    for (i, c) in state.inspector.comments.iter().enumerate() {
+        col = col.push(
+            row![
+                text_input("eng", &c.lang)
+                    .on_input_maybe(enabled.then_some(move |s| Message::CommentDraftChanged(
+                        i,
+                        CommentPart::Lang,
+                        s
+                    )))
+                    .width(Length::Fixed(48.0)),
+                text_input("Description", &c.description)
+                    .on_input_maybe(enabled.then_some(move |s| {
+                        Message::CommentDraftChanged(i, CommentPart::Description, s)
+                    }))
+                    .width(Length::Fixed(LABEL_W)),
+                text_input("Text", &c.text)
+                    .on_input_maybe(enabled.then_some(move |s| Message::CommentDraftChanged(
+                        i,
+                        CommentPart::Text,
+                        s
+                    )))
+                    .width(Length::Fill),
+                button(text("×").size(12))
+                    .on_press_maybe(enabled.then_some(Message::RemoveCommentDraft(i))),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    col.push(
+        button(text("Add comment").size(12))
+            .on_press_maybe(enabled.then_some(Message::AddCommentDraft)),
+    )
+}
+
+/// "Custom tags" (TXXX): one editable row per description, plus an "add" row.
+/// Only for a single selected file; batches keep each file's own frames.
+fn custom_tags(state: &Sonora) -> Column<'_, Message> {
+    let header = text("Custom tags (TXXX)").size(14);
+
+    if state.selected_tracks.len() > 1 {
+        return column![
+            header,
+            text("Select a single track to edit custom tags.").size(12)
+        ]
+        .spacing(6);
+    }
+
+    let busy = is_busy(state);
+    let mut col = column![header].spacing(6);
+
+    for (desc, value) in &state.inspector.user_text {
+        let key = desc.clone();
+        let edit = move |v| Message::AddUserText(key.clone(), v);
+        col = col.push(
+            row![
+                text(desc).width(Length::Fixed(LABEL_W)),
+                text_input("", value)
+                    .on_input_maybe((!busy).then_some(edit))
+                    .width(Length::Fill),
+                button(text("✕").size(12))
+                    .on_press_maybe((!busy).then(|| Message::RemoveUserText(desc.clone()))),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    let new_desc = &state.inspector.new_user_text_desc;
+    let new_value = &state.inspector.new_user_text_value;
+    let add_btn = if busy || new_desc.trim().is_empty() {
+        button(text("Add").size(12))
+    } else {
+        button(text("Add").size(12))
+            .on_press(Message::AddUserText(new_desc.clone(), new_value.clone()))
+    };
+
+    col.push(
+        row![
+            text_input("Description", new_desc)
+                .on_input_maybe(
+                    (!busy).then_some(move |d| Message::NewUserTextChanged(d, new_value.clone()))
+                )
+                .width(Length::Fixed(LABEL_W)),
+            text_input("Value", new_value)
+                .on_input_maybe(
+                    (!busy).then_some(move |v| Message::NewUserTextChanged(new_desc.clone(), v))
+                )
+                .width(Length::Fill),
+            add_btn,
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    )
+}
+
 /// Numeric pair row with " (mixed)" on the label if either side is mixed.
 fn num_pair_row_mixed<'a>(
     label: &'a str,
-    left: &'a str,
-    left_mixed: bool,
-    left_on: impl Fn(String) -> Message + 'a,
-    right: &'a str,
-    right_mixed: bool,
-    right_on: impl Fn(String) -> Message + 'a,
+    mixed: bool,
+    enabled: bool,
+    (left_field, left): (Field, &'a str),
+    (right_field, right): (Field, &'a str),
 ) -> Row<'a, Message> {
-    let label = if left_mixed || right_mixed {
+    let label = if mixed {
         format!("{label} (mixed)")
     } else {
         label.to_string()
@@ -57,17 +201,44 @@ fn num_pair_row_mixed<'a>(
     row![
         text(label).width(Length::Fixed(LABEL_W)),
         text_input("", left)
-            .on_input(left_on)
+            .on_input_maybe(enabled.then_some(move |s| Message::InspectorChanged(left_field, s)))
             .width(Length::Fixed(70.0)),
+        clear_btn(left_field, left, enabled),
         text("/"),
         text_input("", right)
-            .on_input(right_on)
+            .on_input_maybe(enabled.then_some(move |s| Message::InspectorChanged(right_field, s)))
             .width(Length::Fixed(70.0)),
+        clear_btn(right_field, right, enabled),
     ]
     .spacing(6)
     .align_y(Alignment::Center)
 }
 
+/// POPM byte per star (common 1–5 star encoding: 51, 102, 153, 204, 255).
+const POPM_PER_STAR: u8 = 51;
+
+/// Five clickable stars for one track. Clicking the current rating clears it.
+fn rating_row<'a>(id: TrackId, rating: Option<u8>, enabled: bool) -> Row<'a, Message> {
+    let stars = rating.map_or(0, |r| (r.saturating_add(POPM_PER_STAR / 2)) / POPM_PER_STAR);
+
+    let mut out = row![text("Rating").width(Length::Fixed(LABEL_W))]
+        .spacing(2)
+        .align_y(Alignment::Center);
+
+    for n in 1..=5u8 {
+        let glyph = if n <= stars { "★" } else { "☆" };
+        let target = if n == stars { 0 } else { n * POPM_PER_STAR };
+        let star = button(text(glyph).size(16)).padding([0, 4]);
+        out = out.push(if enabled {
+            star.on_press(Message::SetRating(id, target))
+        } else {
+            star
+        });
+    }
+
+    out
+}
+
 fn is_mixed(state: &Sonora, field: Field) -> bool {
     state.inspector_mixed.get(&field).copied().unwrap_or(false)
 }
@@ -107,18 +278,124 @@ pub(crate) fn build_inspector_panel(state: &Sonora) -> iced::widget::Container<'
         1
     };
 
+    // Like the rating stars, the heart acts on the primary track only.
+    let heart = if t.is_favorite { "♥" } else { "♡" };
+    let favorite_btn = if is_busy(state) {
+        button(text(heart).size(16)).padding([0, 4])
+    } else {
+        button(text(heart).size(16))
+            .padding([0, 4])
+            .on_press(Message::ToggleFavorite(id))
+    };
+
+    // Cover writes target one file; disable the action for multi-select.
+    let cover_btn = if sel_count == 1 && !is_busy(state) {
+        button(text("Load artwork from file…").size(12)).on_press(Message::LoadArtworkFromFile(id))
+    } else {
+        button(text("Load artwork from file…").size(12))
+    };
+
+    let extract_btn = if t.artwork_count > 0 {
+        button(text("Save artwork…").size(12)).on_press(Message::ExtractArtwork(id))
+    } else {
+        button(text("Save artwork…").size(12))
+    };
+
+    let queue_btn = button(text("Add to queue").size(12)).on_press(Message::EnqueueTrack(id));
+    let play_here_btn = button(text("Play from here").size(12)).on_press(Message::PlayFromHere(id));
+    let folder_btn = button(text("Show in folder").size(12)).on_press(Message::ShowInFolder(id));
+
+    // Hands the whole selection to the external editor from Settings.
+    let external_ids: Vec<TrackId> = if state.selected_tracks.is_empty() {
+        vec![id]
+    } else {
+        state.selected_tracks.iter().copied().collect()
+    };
+    let external_btn = if state.external_tag_editor.trim().is_empty() {
+        button(text("Open in editor").size(12))
+    } else {
+        button(text("Open in editor").size(12))
+            .on_press(Message::OpenWithExternalEditor(external_ids))
+    };
+
+    // Tag clipboard: copy album-level tags from this track, paste them into the draft.
+    let copy_btn = button(text("Copy tags").size(12))
+        .on_press(Message::CopyTags(id, TAG_CLIPBOARD_FIELDS.to_vec()));
+    let can_paste = !is_busy(state) && state.tag_clipboard.as_ref().is_some_and(|c| !c.is_empty());
+    let paste_btn =
+        button(text("Paste tags").size(12)).on_press_maybe(can_paste.then_some(Message::PasteTags));
+
+    // Destructive; the handler asks for confirmation before touching files.
+    let strip_btn = if is_busy(state) {
+        button(text("Strip tags…").size(12))
+    } else {
+        button(text("Strip tags…").size(12)).on_press(Message::ConfirmStripTags)
+    };
+
+    // Online lookup fills empty draft fields; single track, one request at a time.
+    let mb_btn = if sel_count == 1 && !state.mb_lookup_pending {
+        button(text("Lookup MusicBrainz").size(12)).on_press(Message::LookupMusicBrainz)
+    } else {
+        button(text("Lookup MusicBrainz").size(12))
+    };
+    let identify_btn = if sel_count == 1
+        && !state.mb_lookup_pending
+        && !state.acoustid_api_key.trim().is_empty()
+    {
+        button(text("Identify by audio").size(12)).on_press(Message::FingerprintAndLookup(id))
+    } else {
+        button(text("Identify by audio").size(12))
+    };
+
+    // Rename selected files from tags (preview first, then apply).
+    let busy = is_busy(state);
+    let template = state.rename_template.clone();
+    let (preview_btn, rename_btn) = if busy || template.trim().is_empty() {
+        (
+            button(text("Preview").size(12)),
+            button(text("Rename files").size(12)),
+        )
+    } else {
+        (
+            button(text("Preview").size(12))
+                .on_press(Message::RenameFromTemplate(template.clone(), true)),
+            button(text("Rename files").size(12))
+                .on_press(Message::RenameFromTemplate(template, false)),
+        )
+    };
+    let rename_row = row![
+        text("Rename").width(Length::Fixed(LABEL_W)),
+        text_input("{track_no:02} {title}", &state.rename_template)
+            .on_input_maybe((!busy).then_some(Message::RenameTemplateChanged))
+            .width(Length::Fill),
+        preview_btn,
+        rename_btn,
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    // Hidden when a multi-selection mixes tag formats.
+    let formats_agree = state
+        .selected_tracks
+        .iter()
+        .filter_map(|&id| state.track_by_id(id))
+        .all(|other| other.tag_format == t.tag_format);
+    let tags_line: Column<'_, Message> = if formats_agree {
+        column![text(format!("Tags: {}", fmt_tag_format(t.tag_format))).size(12)]
+    } else {
+        column![]
+    };
+
     let top = column![
         text("Metadata editor").size(18),
         text(format!("Selected: {sel_count}")).size(12),
+        tags_line,
         text("File path").size(12),
         text(path_line).size(12),
         text(format!(
-            "Artwork: {} | Len: {} | Rating: {} | Plays: {} | Compilation: {}",
+            "Artwork: {} | Len: {} | Plays: {} | Compilation: {}",
             t.artwork_count,
             fmt_duration(t.duration_ms),
-            t.rating
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| "-".into()),
             t.play_count
                 .map(|v| v.to_string())
                 .unwrap_or_else(|| "-".into()),
@@ -127,97 +404,152 @@ pub(crate) fn build_inspector_panel(state: &Sonora) -> iced::widget::Container<'
                 .unwrap_or_else(|| "-".into()),
         ))
         .size(12),
+        text(format!(
+            "Sample rate: {} | Bit rate: {} | Channels: {}",
+            t.sample_rate
+                .map(|v| format!("{v} Hz"))
+                .unwrap_or_else(|| "-".into()),
+            t.bit_rate
+                .map(|v| format!("{v} kbps"))
+                .unwrap_or_else(|| "-".into()),
+            fmt_channels(t.channels),
+        ))
+        .size(12),
+        row![rating_row(id, t.rating, !is_busy(state)), favorite_btn]
+            .spacing(12)
+            .align_y(Alignment::Center),
+        row![cover_btn, extract_btn].spacing(8),
+        row![play_here_btn, queue_btn].spacing(8),
+        row![folder_btn, strip_btn].spacing(8),
+        row![copy_btn, paste_btn].spacing(8),
+        external_btn,
+        row![mb_btn, identify_btn].spacing(8),
+        rename_row,
     ]
     .spacing(6);
 
+    // Draft fields are read-only while a scan or write is running.
+    let editable = !busy;
+
+    // One file + "Show more tags": every COMM frame is edited below instead.
+    let edit_comment_frames = state.show_extended && state.selected_tracks.len() <= 1;
+    let comment_row: Row<'_, Message> = if edit_comment_frames {
+        row![]
+    } else {
+        field_row_mixed(
+            "Comment",
+            Field::Comment,
+            &state.inspector.comment,
+            is_mixed(state, Field::Comment),
+            editable,
+        )
+    };
+
     let core: Column<'_, Message> = column![
         field_row_mixed(
             "Title",
+            Field::Title,
             &state.inspector.title,
             is_mixed(state, Field::Title),
-            |s| Message::InspectorChanged(Field::Title, s)
+            editable
         ),
         field_row_mixed(
             "Artist",
+            Field::Artist,
             &state.inspector.artist,
             is_mixed(state, Field::Artist),
-            |s| Message::InspectorChanged(Field::Artist, s)
+            editable
         ),
         field_row_mixed(
             "Album",
+            Field::Album,
             &state.inspector.album,
             is_mixed(state, Field::Album),
-            |s| Message::InspectorChanged(Field::Album, s)
+            editable
         ),
         field_row_mixed(
             "Album Artist",
+            Field::AlbumArtist,
             &state.inspector.album_artist,
             is_mixed(state, Field::AlbumArtist),
-            |s| Message::InspectorChanged(Field::AlbumArtist, s)
+            editable
         ),
         field_row_mixed(
             "Composer",
+            Field::Composer,
             &state.inspector.composer,
             is_mixed(state, Field::Composer),
-            |s| Message::InspectorChanged(Field::Composer, s)
+            editable
         ),
         num_pair_row_mixed(
             "Track",
-            &state.inspector.track_no,
-            is_mixed(state, Field::TrackNo),
-            |s| Message::InspectorChanged(Field::TrackNo, s),
-            &state.inspector.track_total,
-            is_mixed(state, Field::TrackTotal),
-            |s| Message::InspectorChanged(Field::TrackTotal, s),
+            is_mixed(state, Field::TrackNo) || is_mixed(state, Field::TrackTotal),
+            editable,
+            (Field::TrackNo, &state.inspector.track_no),
+            (Field::TrackTotal, &state.inspector.track_total),
         ),
         num_pair_row_mixed(
             "Disc",
-            &state.inspector.disc_no,
-            is_mixed(state, Field::DiscNo),
-            |s| Message::InspectorChanged(Field::DiscNo, s),
-            &state.inspector.disc_total,
-            is_mixed(state, Field::DiscTotal),
-            |s| Message::InspectorChanged(Field::DiscTotal, s),
+            is_mixed(state, Field::DiscNo) || is_mixed(state, Field::DiscTotal),
+            editable,
+            (Field::DiscNo, &state.inspector.disc_no),
+            (Field::DiscTotal, &state.inspector.disc_total),
         ),
         field_row_mixed(
             "Year",
+            Field::Year,
             &state.inspector.year,
             is_mixed(state, Field::Year),
-            |s| Message::InspectorChanged(Field::Year, s)
+            editable
         ),
         field_row_mixed(
             "Genre",
+            Field::Genre,
             &state.inspector.genre,
             is_mixed(state, Field::Genre),
-            |s| Message::InspectorChanged(Field::Genre, s)
+            editable
         ),
         field_row_mixed(
             "Grouping",
+            Field::Grouping,
             &state.inspector.grouping,
             is_mixed(state, Field::Grouping),
-            |s| Message::InspectorChanged(Field::Grouping, s)
-        ),
-        field_row_mixed(
-            "Comment",
-            &state.inspector.comment,
-            is_mixed(state, Field::Comment),
-            |s| Message::InspectorChanged(Field::Comment, s)
+            editable
         ),
+        comment_row,
         field_row_mixed(
             "Lyrics",
+            Field::Lyrics,
             &state.inspector.lyrics,
             is_mixed(state, Field::Lyrics),
-            |s| Message::InspectorChanged(Field::Lyrics, s)
+            editable
         ),
         field_row_mixed(
             "Lyricist",
+            Field::Lyricist,
             &state.inspector.lyricist,
             is_mixed(state, Field::Lyricist),
-            |s| Message::InspectorChanged(Field::Lyricist, s)
+            editable
         ),
     ]
     .spacing(8);
 
+    // Every COMM frame of the primary track (read-only; "Comment" above edits the first).
+    let comments: Column<'_, Message> = if t.comments.is_empty() || edit_comment_frames {
+        column![]
+    } else {
+        let mut col = column![text(format!("Comments ({})", t.comments.len())).size(14)].spacing(4);
+        for c in &t.comments {
+            let header = if c.description.is_empty() {
+                format!("[{}]", c.lang)
+            } else {
+                format!("[{}] {}", c.lang, c.description)
+            };
+            col = col.push(text(header).size(12)).push(text(&c.text).size(12));
+        }
+        col
+    };
+
     let toggle = checkbox(state.show_extended)
         .label("Show more tags")
         .on_toggle(Message::ToggleExtended);
@@ -226,102 +558,174 @@ pub(crate) fn build_inspector_panel(state: &Sonora) -> iced::widget::Container<'
         column![
             field_row_mixed(
                 "Date",
+                Field::Date,
                 &state.inspector.date,
                 is_mixed(state, Field::Date),
-                |s| Message::InspectorChanged(Field::Date, s)
+                editable
             ),
             field_row_mixed(
                 "Conductor",
+                Field::Conductor,
                 &state.inspector.conductor,
                 is_mixed(state, Field::Conductor),
-                |s| Message::InspectorChanged(Field::Conductor, s)
+                editable
             ),
             field_row_mixed(
                 "Remixer",
+                Field::Remixer,
                 &state.inspector.remixer,
                 is_mixed(state, Field::Remixer),
-                |s| Message::InspectorChanged(Field::Remixer, s)
+                editable
             ),
             field_row_mixed(
                 "Publisher",
+                Field::Publisher,
                 &state.inspector.publisher,
                 is_mixed(state, Field::Publisher),
-                |s| Message::InspectorChanged(Field::Publisher, s)
+                editable
             ),
             field_row_mixed(
                 "Subtitle",
+                Field::Subtitle,
                 &state.inspector.subtitle,
                 is_mixed(state, Field::Subtitle),
-                |s| Message::InspectorChanged(Field::Subtitle, s)
+                editable
             ),
             field_row_mixed(
                 "BPM",
+                Field::Bpm,
                 &state.inspector.bpm,
                 is_mixed(state, Field::Bpm),
-                |s| Message::InspectorChanged(Field::Bpm, s)
+                editable
             ),
             field_row_mixed(
                 "Key",
+                Field::Key,
                 &state.inspector.key,
                 is_mixed(state, Field::Key),
-                |s| Message::InspectorChanged(Field::Key, s)
+                editable
             ),
             field_row_mixed(
                 "Mood",
+                Field::Mood,
                 &state.inspector.mood,
                 is_mixed(state, Field::Mood),
-                |s| Message::InspectorChanged(Field::Mood, s)
+                editable
             ),
             field_row_mixed(
                 "Language",
+                Field::Language,
                 &state.inspector.language,
                 is_mixed(state, Field::Language),
-                |s| Message::InspectorChanged(Field::Language, s)
+                editable
             ),
             field_row_mixed(
                 "ISRC",
+                Field::Isrc,
                 &state.inspector.isrc,
                 is_mixed(state, Field::Isrc),
-                |s| Message::InspectorChanged(Field::Isrc, s)
+                editable
             ),
             field_row_mixed(
                 "Encoder",
+                Field::EncoderSettings,
                 &state.inspector.encoder_settings,
                 is_mixed(state, Field::EncoderSettings),
-                |s| Message::InspectorChanged(Field::EncoderSettings, s)
+                editable
             ),
             field_row_mixed(
                 "Encoded by",
+                Field::EncodedBy,
                 &state.inspector.encoded_by,
                 is_mixed(state, Field::EncodedBy),
-                |s| Message::InspectorChanged(Field::EncodedBy, s)
+                editable
             ),
             field_row_mixed(
                 "Copyright",
+                Field::Copyright,
                 &state.inspector.copyright,
                 is_mixed(state, Field::Copyright),
-                |s| Message::InspectorChanged(Field::Copyright, s)
+                editable
+            ),
+            field_row_mixed(
+                "Trim (dB)",
+                Field::TrimDb,
+                &state.inspector.trim_db,
+                is_mixed(state, Field::TrimDb),
+                editable
+            ),
+            // Written as TSOT/TSOP/TSOA/TSO2 (ID3) or *SORT keys (Vorbis comments).
+            text("Sort fields").size(14),
+            field_row_mixed(
+                "Title sort",
+                Field::TitleSort,
+                &state.inspector.title_sort,
+                is_mixed(state, Field::TitleSort),
+                editable
+            ),
+            field_row_mixed(
+                "Artist sort",
+                Field::ArtistSort,
+                &state.inspector.artist_sort,
+                is_mixed(state, Field::ArtistSort),
+                editable
+            ),
+            field_row_mixed(
+                "Album sort",
+                Field::AlbumSort,
+                &state.inspector.album_sort,
+                is_mixed(state, Field::AlbumSort),
+                editable
             ),
+            field_row_mixed(
+                "Album artist sort",
+                Field::AlbumArtistSort,
+                &state.inspector.album_artist_sort,
+                is_mixed(state, Field::AlbumArtistSort),
+                editable
+            ),
+            read_only_row("MB recording", &state.inspector.mb_recording_id),
+            read_only_row("MB release", &state.inspector.mb_release_id),
+            comment_frames(state),
+            custom_tags(state),
         ]
         .spacing(8)
     } else {
         column![]
     };
 
-    let save_btn = if state.scanning || !state.inspector_dirty {
+    // Mirrors the sidebar's "Scanning...": the label says why the button is off.
+    let save_btn = if state.saving {
+        button("Saving…")
+    } else if busy || !state.inspector_dirty {
         button("Save edits")
     } else {
         button("Save edits").on_press(Message::SaveInspectorToFile)
     };
 
-    let revert_btn = if state.scanning {
+    let revert_btn = if busy {
         button("Cancel edits")
     } else {
         button("Cancel edits").on_press(Message::RevertInspector)
     };
 
-    let buttons = row![save_btn, revert_btn].spacing(8);
+    // Re-read the selected files from disk (after editing them in another tool).
+    let refresh_btn = if is_busy(state) {
+        button("Refresh tags")
+    } else {
+        button("Refresh tags").on_press(Message::RescanSelected)
+    };
+
+    // Number the selection 1..n in album order (per disc).
+    let number_btn = if is_busy(state) {
+        button("Auto-number")
+    } else {
+        button("Auto-number").on_press(Message::AutoNumberSelected)
+    };
+
+    let buttons = row![save_btn, revert_btn, refresh_btn, number_btn].spacing(8);
 
-    let editor = scrollable(column![top, core, toggle, extended].spacing(12)).height(Length::Fill);
+    let editor =
+        scrollable(column![top, core, comments, toggle, extended].spacing(12)).height(Length::Fill);
     container(column![editor, buttons].spacing(12)).padding(12)
 }
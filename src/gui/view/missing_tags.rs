@@ -0,0 +1,83 @@
+//! gui/view/missing_tags.rs
+//! "Missing tags" report (center panel), grouped by missing field.
+//!
+//! - A track missing several fields is listed under each of them.
+//! - Clicking a row selects the track so it can be fixed in the inspector.
+
+use iced::widget::{Column, button, column, container, mouse_area, row, scrollable, text};
+use iced::{Alignment, Length};
+
+use super::super::state::{Message, Sonora};
+use super::super::util::filename_stem;
+use super::constants::{ROW_TEXT, TRACK_LIST_SPACING};
+use crate::core::lint::RequiredField;
+use crate::core::types::TrackId;
+
+pub(crate) fn build_missing_tags_center<'a>(
+    state: &'a Sonora,
+    report: &'a [(TrackId, Vec<RequiredField>)],
+) -> Column<'a, Message> {
+    let header = row![
+        text(format!("Missing tags ({} tracks)", report.len()))
+            .size(18)
+            .width(Length::Fill),
+        button(text("Close").size(12)).on_press(Message::CloseMissingTags),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    if report.is_empty() {
+        return column![header, text("Every track has complete tags.").size(12)].spacing(12);
+    }
+
+    let mut list: Column<'_, Message> = column![].spacing(TRACK_LIST_SPACING);
+
+    for field in RequiredField::ALL {
+        let ids: Vec<_> = report
+            .iter()
+            .filter(|(_, missing)| missing.contains(&field))
+            .map(|(id, _)| *id)
+            .collect();
+        if ids.is_empty() {
+            continue;
+        }
+
+        list = list.push(
+            container(text(format!("Missing {} ({})", field.label(), ids.len())).size(16))
+                .padding([8, 0]),
+        );
+
+        for id in ids {
+            let Some(t) = state.track_by_id(id) else {
+                continue;
+            };
+
+            let marker = if state.selected_tracks.contains(&id) {
+                "●"
+            } else {
+                ""
+            };
+
+            let line = row![
+                text(marker).size(ROW_TEXT).width(Length::Fixed(16.0)),
+                text(t.title.clone().unwrap_or_else(|| filename_stem(&t.path)))
+                    .size(ROW_TEXT)
+                    .width(Length::FillPortion(3)),
+                text(t.artist.clone().unwrap_or_default())
+                    .size(12)
+                    .width(Length::FillPortion(2)),
+                text(t.album.clone().unwrap_or_default())
+                    .size(12)
+                    .width(Length::FillPortion(2)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            list = list.push(
+                mouse_area(container(line).padding([2, 8])).on_press(Message::SelectTrack(id)),
+            );
+        }
+    }
+
+    column![header, scrollable(list).height(Length::Fill)].spacing(12)
+}
@@ -1,22 +1,60 @@
 //! gui/view/mod.rs
 //! GUI renderer (reads state, produces widgets; no mutation).
 
+mod album_check;
 mod albums;
+mod art_grid;
 mod center;
 pub(crate) mod constants;
+mod duplicates;
+mod find_replace;
 mod inspector;
+mod missing_tags;
+mod queue;
+mod recent;
 mod sidebar;
+mod stats;
 mod tracks;
 mod widgets;
 
-use iced::Length;
-use iced::widget::{Column, column, row};
+use iced::widget::{Row, column, container, row, scrollable, stack, text};
+use iced::{Element, Length};
 
 use super::state::{Message, Sonora};
-use constants::{EDITOR_W, PLAYBACK_H, SIDEBAR_W};
+use super::util::filename_stem;
+use constants::{EDITOR_W, LYRICS_H, PLAYBACK_H, SIDEBAR_W};
 
-pub(crate) fn view(state: &Sonora) -> Column<'_, Message> {
-    let playback = widgets::playback_bar(state).height(Length::Fixed(PLAYBACK_H));
+/// Window title: "♪ {title} — {artist} | Sonora" while playing, "⏸ {title} — Sonora"
+/// while paused, plain "Sonora" otherwise.
+pub(crate) fn title(state: &Sonora) -> String {
+    let Some(t) = state.now_playing.and_then(|id| state.track_by_id(id)) else {
+        return "Sonora".to_string();
+    };
+    let name = t.title.clone().unwrap_or_else(|| filename_stem(&t.path));
+
+    // Stop and TrackEnded rewind to 0; a pause keeps its place in a loaded track.
+    let paused = !state.is_playing && state.duration_ms.is_some() && state.position_ms > 0;
+
+    if state.is_playing {
+        match &t.artist {
+            Some(artist) => format!("♪ {name} — {artist} | Sonora"),
+            None => format!("♪ {name} | Sonora"),
+        }
+    } else if paused {
+        format!("⏸ {name} — Sonora")
+    } else {
+        "Sonora".to_string()
+    }
+}
+
+pub(crate) fn view(state: &Sonora) -> Element<'_, Message> {
+    // The EQ panel opens above the bar, so let it grow while shown.
+    let playback_h = if state.show_eq {
+        Length::Shrink
+    } else {
+        Length::Fixed(PLAYBACK_H)
+    };
+    let playback = widgets::playback_bar(state).height(playback_h);
 
     let sidebar = sidebar::build_sidebar(state).width(Length::Fixed(SIDEBAR_W));
     let main = center::build_center_panel(state).width(Length::Fill);
@@ -24,12 +62,41 @@ pub(crate) fn view(state: &Sonora) -> Column<'_, Message> {
     // Only show the inspector when something is selected
     let has_selection = state.selected_track.is_some() || !state.selected_tracks.is_empty();
 
-    let body = if has_selection {
-        let editor = inspector::build_inspector_panel(state).width(Length::Fixed(EDITOR_W));
-        row![sidebar, main, editor].spacing(12).height(Length::Fill)
-    } else {
-        row![sidebar, main].spacing(12).height(Length::Fill)
-    };
+    let mut body: Row<'_, Message> = row![sidebar, main].spacing(12).height(Length::Fill);
+
+    if has_selection {
+        body = body.push(inspector::build_inspector_panel(state).width(Length::Fixed(EDITOR_W)));
+    }
+
+    // Lyrics of the playing track, between the bar and the body: synced (SYLT) lines
+    // follow the playhead, else the plain text (USLT) scrolls.
+    let playing = state.now_playing.and_then(|id| state.track_by_id(id));
+
+    let mut page = column![playback].spacing(12).padding(12);
+    if state.show_lyrics
+        && let Some(t) = playing
+    {
+        let lyrics: Option<iced::Element<'_, Message>> = if !t.synced_lyrics.is_empty() {
+            Some(widgets::synced_lyrics(&t.synced_lyrics, state.estimated_position_ms()).into())
+        } else {
+            t.lyrics
+                .as_deref()
+                .map(|l| scrollable(text(l).size(13)).width(Length::Fill).into())
+        };
+
+        if let Some(lyrics) = lyrics {
+            page = page.push(
+                container(lyrics)
+                    .height(Length::Fixed(LYRICS_H))
+                    .padding([0, 12]),
+            );
+        }
+    }
+    let page = page.push(body);
 
-    column![playback, body].spacing(12).padding(12)
+    // Dialogs sit on top of everything (and block it) while open.
+    match &state.find_replace {
+        Some(draft) => stack![page, find_replace::find_replace_overlay(state, draft)].into(),
+        None => page.into(),
+    }
 }
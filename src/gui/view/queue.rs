@@ -0,0 +1,113 @@
+//! gui/view/queue.rs
+//! Queue panel (ordered play queue, toggled from the playback bar).
+//!
+//! - Rendered by the center router in place of Tracks/Albums while `show_queue` is on.
+//! - Rows are keyed by queue position (the same id may be queued twice).
+//! - Entries before the cursor are history (already played); the rest are up next.
+//! - Reordering uses per-row move buttons (iced has no built-in drag-and-drop for lists);
+//!   the `MoveInQueue` / `MoveToTop` / `MoveToBottom` messages don't depend on them.
+
+use iced::widget::{Column, button, column, container, row, scrollable, text};
+use iced::{Alignment, Length};
+
+use super::super::state::{Direction, Message, Sonora};
+use super::super::util::filename_stem;
+use super::constants::{ROW_TEXT, TRACK_LIST_SPACING};
+use super::widgets::fmt_duration;
+
+pub(crate) fn build_queue_panel(state: &Sonora) -> iced::widget::Container<'_, Message> {
+    let has_items = !state.queue.is_empty();
+
+    let play_btn = if has_items {
+        button(text("Play").size(12)).on_press(Message::PlayQueue)
+    } else {
+        button(text("Play").size(12))
+    };
+
+    let clear_btn = if has_items {
+        button(text("Clear").size(12)).on_press(Message::ClearQueue)
+    } else {
+        button(text("Clear").size(12))
+    };
+
+    let up_next = state
+        .queue
+        .len()
+        .saturating_sub(state.queue_pos + usize::from(state.now_playing.is_some()));
+
+    let header = row![
+        text(format!("Queue ({} up next)", up_next))
+            .size(18)
+            .width(Length::Fill),
+        play_btn,
+        clear_btn,
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    if !has_items {
+        return container(
+            column![
+                header,
+                text("Queue is empty. Add tracks from the inspector or album view.").size(12),
+            ]
+            .spacing(12),
+        )
+        .padding(12);
+    }
+
+    let mut list: Column<'_, Message> = column![].spacing(TRACK_LIST_SPACING);
+
+    let last = state.queue.len() - 1;
+    for (pos, &id) in state.queue.iter().enumerate() {
+        // ▶ marks the queue cursor; ✓ marks history (already played).
+        let marker = if pos == state.queue_pos {
+            "▶"
+        } else if pos < state.queue_pos {
+            "✓"
+        } else {
+            ""
+        };
+
+        let (title, artist, len) = match state.track_by_id(id) {
+            Some(t) => (
+                t.title.clone().unwrap_or_else(|| filename_stem(&t.path)),
+                t.artist.clone().unwrap_or_else(|| "Unknown".into()),
+                fmt_duration(t.duration_ms),
+            ),
+            None => (
+                "(missing — rescan?)".to_string(),
+                String::new(),
+                "-".to_string(),
+            ),
+        };
+
+        list = list.push(
+            row![
+                text(marker).size(ROW_TEXT).width(Length::Fixed(20.0)),
+                text(format!("{}.", pos + 1))
+                    .size(ROW_TEXT)
+                    .width(Length::Fixed(36.0)),
+                column![text(title).size(ROW_TEXT), text(artist).size(12)]
+                    .spacing(2)
+                    .width(Length::Fill),
+                text(len).size(12).width(Length::Fixed(44.0)),
+                move_btn("⤒", pos > 0, Message::MoveToTop(pos)),
+                move_btn("▲", pos > 0, Message::MoveInQueue(pos, Direction::Up)),
+                move_btn("▼", pos < last, Message::MoveInQueue(pos, Direction::Down)),
+                move_btn("⤓", pos < last, Message::MoveToBottom(pos)),
+                button(text("×").size(12)).on_press(Message::RemoveFromQueue(pos)),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    container(column![header, scrollable(list).height(Length::Fill)].spacing(12)).padding(12)
+}
+
+/// Reorder button; disabled at the end it would move past.
+fn move_btn(label: &str, enabled: bool, msg: Message) -> iced::widget::Button<'_, Message> {
+    let btn = button(text(label).size(12));
+    if enabled { btn.on_press(msg) } else { btn }
+}
@@ -0,0 +1,108 @@
+//! gui/view/recent.rs
+//! "Recently played" view (center panel): the play history, newest first.
+//!
+//! - Same row look as the track table (markers, now-playing tint).
+//! - A track played twice shows up twice, once per play.
+//! - Click selects, a click on the selected row plays it (like the track table).
+
+use iced::widget::{Column, column, container, mouse_area, row, scrollable, text};
+use iced::{Alignment, Color, Length, Theme};
+
+use super::super::state::{Message, RECENTLY_PLAYED_SHOWN, SelectMode, Sonora};
+use super::super::util::filename_stem;
+use super::constants::{
+    HEADER_TEXT, NOW_PLAYING_TINT, ROW_TEXT, TRACK_LIST_SPACING, TRACK_ROW_H, TRACK_ROW_HPAD,
+    TRACK_ROW_VPAD,
+};
+use super::widgets::fmt_duration;
+
+pub(crate) fn build_recently_played_center(state: &Sonora) -> Column<'_, Message> {
+    let title = text(format!("Recently played (last {RECENTLY_PLAYED_SHOWN})")).size(18);
+
+    if state.playback_history.is_empty() {
+        return column![
+            title,
+            text("Tracks you play to the end show up here.").size(12)
+        ]
+        .spacing(12);
+    }
+
+    let header = row![
+        text("").size(HEADER_TEXT).width(Length::Fixed(24.0)),
+        text("Played").size(HEADER_TEXT).width(Length::Fixed(130.0)),
+        text("Title").size(HEADER_TEXT).width(Length::Fixed(240.0)),
+        text("Artist").size(HEADER_TEXT).width(Length::Fixed(190.0)),
+        text("Album").size(HEADER_TEXT).width(Length::Fixed(240.0)),
+        text("Len").size(HEADER_TEXT).width(Length::Fixed(70.0)),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center);
+
+    let mut rows = column![].spacing(TRACK_LIST_SPACING);
+
+    for (id, played_at) in state.recently_played() {
+        let id = *id;
+        // Removed from the library since it was played.
+        let Some(t) = state.track_by_id(id) else {
+            continue;
+        };
+
+        let is_selected = state.selected_tracks.contains(&id);
+        let is_primary_selected = state.selected_track == Some(id);
+        let is_now_playing = state.now_playing == Some(id);
+
+        let marker = if is_now_playing && (is_selected || is_primary_selected) {
+            "▶"
+        } else if is_now_playing {
+            "♪"
+        } else if is_selected {
+            "●"
+        } else {
+            ""
+        };
+
+        let title = t.title.clone().unwrap_or_else(|| filename_stem(&t.path));
+        let artist = t.artist.clone().unwrap_or_else(|| "Unknown".into());
+        let album = t.album.clone().unwrap_or_else(|| "Unknown".into());
+
+        let msg = if is_primary_selected {
+            Message::PlayTrack(id)
+        } else {
+            Message::SelectTrackModified(id, SelectMode::Single)
+        };
+
+        let row_cells = row![
+            text(marker).size(ROW_TEXT).width(Length::Fixed(24.0)),
+            text(played_at.format("%Y-%m-%d %H:%M").to_string())
+                .size(ROW_TEXT)
+                .width(Length::Fixed(130.0)),
+            text(title).size(ROW_TEXT).width(Length::Fixed(240.0)),
+            text(artist).size(ROW_TEXT).width(Length::Fixed(190.0)),
+            text(album).size(ROW_TEXT).width(Length::Fixed(240.0)),
+            text(fmt_duration(t.duration_ms))
+                .size(ROW_TEXT)
+                .width(Length::Fixed(70.0)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let mut row_box = container(row_cells)
+            .padding([TRACK_ROW_VPAD, TRACK_ROW_HPAD])
+            .height(Length::Fixed(TRACK_ROW_H))
+            .width(Length::Fill);
+        if is_now_playing {
+            row_box = row_box.style(|theme: &Theme| {
+                container::background(Color {
+                    a: NOW_PLAYING_TINT,
+                    ..theme.palette().primary
+                })
+            });
+        }
+
+        rows = rows.push(mouse_area(row_box).on_press(msg));
+    }
+
+    let table = column![header, scrollable(rows).height(Length::Fill)].spacing(TRACK_LIST_SPACING);
+
+    column![title, table.height(Length::Fill)].spacing(12)
+}
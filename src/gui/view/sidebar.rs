@@ -1,37 +1,182 @@
 //! gui/view/sidebar.rs
 //! Left sidebar (scan, view toggles, roots list, playlists).
 
-use iced::Length;
-use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use iced::widget::{
+    ProgressBar, button, checkbox, column, container, pick_list, progress_bar, row, scrollable,
+    slider, text, text_input, tooltip,
+};
+use iced::{Alignment, Length};
 
-use super::super::state::{Message, Sonora, ViewMode};
+use super::super::state::{MAX_CROSSFADE_MS, Message, SYSTEM_DEFAULT_DEVICE, Sonora, ViewMode};
+use super::super::util::{is_busy, truncate_path};
+use super::constants::{PROGRESS_BAR_H, ROOT_PATH_MAX_CHARS, SCAN_ERRORS_H};
+use crate::core::playback::ReplayGainMode;
+use crate::core::tags::TagVersion;
 
 pub(crate) fn build_sidebar(state: &Sonora) -> iced::widget::Container<'_, Message> {
-    let busy = state.scanning || state.saving;
+    let busy = is_busy(state);
 
     let scan_btn = if state.scanning {
         button("Scanning...")
+    } else if busy {
+        button("Scan Library")
     } else {
         button("Scan Library").on_press(Message::ScanLibrary)
     };
 
+    // One bar per running job; both vanish when their job finishes.
+    let progress_bars = [state.scan_progress, state.save_progress]
+        .into_iter()
+        .flatten()
+        .fold(column![].spacing(4), |col, (done, total)| {
+            col.push(progress_fraction_bar(done, total))
+        });
+
     let albums_btn = if state.view_mode == ViewMode::Albums {
         button("✓ Album View")
+    } else if busy {
+        button("Album View")
     } else {
         button("Album View").on_press(Message::SetViewMode(ViewMode::Albums))
     };
 
     let tracks_btn = if state.view_mode == ViewMode::Tracks {
         button("✓ Track View")
+    } else if busy {
+        button("Track View")
     } else {
         button("Track View").on_press(Message::SetViewMode(ViewMode::Tracks))
     };
 
-    let view_toggle = row![albums_btn, tracks_btn].spacing(8);
+    let art_btn = if state.view_mode == ViewMode::ArtGrid {
+        button("✓ Art")
+    } else if busy {
+        button("Art")
+    } else {
+        button("Art").on_press(Message::SetViewMode(ViewMode::ArtGrid))
+    };
+
+    let view_toggle = row![albums_btn, tracks_btn, art_btn].spacing(8);
+
+    let duplicates_label = if state.view_mode == ViewMode::Duplicates {
+        "✓ Find duplicates"
+    } else {
+        "Find duplicates"
+    };
+    let duplicates_btn = if state.tracks.is_empty() {
+        button(duplicates_label)
+    } else {
+        button(duplicates_label).on_press(Message::FindDuplicates)
+    };
+
+    let compact_btn = if state.compact_mode {
+        button("✓ Compact rows").on_press(Message::ToggleCompactMode)
+    } else {
+        button("Compact rows").on_press(Message::ToggleCompactMode)
+    };
+
+    let replaygain_label = match state.replaygain_mode {
+        ReplayGainMode::Off => "ReplayGain: Off",
+        ReplayGainMode::Track => "ReplayGain: Track",
+        ReplayGainMode::Album => "ReplayGain: Album",
+    };
+    let replaygain_btn = button(replaygain_label).on_press(Message::CycleReplayGain);
+
+    // Two choices: the button flips to the other one.
+    let other_version = match state.write_options.tag_version {
+        TagVersion::V24 => TagVersion::V23,
+        TagVersion::V23 => TagVersion::V24,
+    };
+    let tag_version_btn = button(text(format!(
+        "Write tags as: {}",
+        state.write_options.tag_version.label()
+    )))
+    .on_press(Message::SetTagVersion(other_version));
+
+    let import_btn = button("Import playlist…").on_press(Message::ImportPlaylist);
+    let export_playlist_btn = button("Export playlist…").on_press(Message::ExportPlaylist);
+
+    let export_btn = if state.tracks.is_empty() {
+        button("Export CSV…")
+    } else {
+        button("Export CSV…").on_press(Message::ExportCsv)
+    };
+
+    let organize_label = if state.show_organize {
+        "✓ Organize files…"
+    } else {
+        "Organize files…"
+    };
+    let organize_btn = button(organize_label).on_press(Message::ToggleOrganizePanel);
+
+    let organize_panel = if state.show_organize {
+        let target = if state.selected_tracks.is_empty() {
+            format!("all {} tracks", state.tracks.len())
+        } else {
+            format!("{} selected", state.selected_tracks.len())
+        };
+        let run_btn = if busy || state.organize_dest.trim().is_empty() {
+            button(text(format!("Organize {target}")).size(12))
+        } else {
+            button(text(format!("Organize {target}")).size(12)).on_press(Message::OrganizeFiles)
+        };
+
+        column![
+            row![
+                text_input("Destination folder", &state.organize_dest)
+                    .on_input(Message::OrganizeDestChanged)
+                    .width(Length::Fill),
+                button("…").on_press(Message::PickOrganizeDest),
+            ]
+            .spacing(8),
+            text_input("{album_artist}/{album}/{title}", &state.organize_template)
+                .on_input(Message::OrganizeTemplateChanged)
+                .width(Length::Fill),
+            checkbox(state.organize_copy)
+                .label("Copy (keep originals)")
+                .on_toggle(Message::OrganizeCopyToggled),
+            run_btn,
+        ]
+        .spacing(6)
+    } else {
+        column![]
+    };
+
+    // Only shown once a scan has hit unreadable files.
+    let scan_errors_panel = if state.scan_errors.is_empty() {
+        column![]
+    } else {
+        let label = format!(
+            "{} Errors ({})",
+            if state.show_scan_errors { "▾" } else { "▸" },
+            state.scan_errors.len()
+        );
+        let mut panel = column![button(text(label)).on_press(Message::ToggleScanErrors)].spacing(6);
+
+        if state.show_scan_errors {
+            let mut log = column![].spacing(6);
+            for e in &state.scan_errors {
+                log = log.push(column![
+                    text(e.path.display().to_string()).size(12),
+                    text(&e.reason).size(11),
+                ]);
+            }
+            panel = panel
+                .push(button(text("Copy to clipboard").size(12)).on_press(Message::CopyScanErrors))
+                .push(scrollable(log).height(Length::Fixed(SCAN_ERRORS_H)));
+        }
+        panel
+    };
+
+    let find_replace_btn = if state.tracks.is_empty() || busy {
+        button("Find & replace…")
+    } else {
+        button("Find & replace…").on_press(Message::OpenFindReplace)
+    };
 
     let root_input = text_input("Add folder path", &state.root_input)
-        .on_input(Message::RootInputChanged)
-        .on_submit(Message::AddRootPressed)
+        .on_input_maybe((!busy).then_some(Message::RootInputChanged))
+        .on_submit_maybe((!busy).then_some(Message::AddRootPressed))
         .width(Length::Fill);
 
     let add_btn = if busy {
@@ -50,8 +195,16 @@ pub(crate) fn build_sidebar(state: &Sonora) -> iced::widget::Container<'_, Messa
             button("×").on_press(Message::RemoveRoot(i))
         };
 
-        // Keep long paths from exploding the layout.
-        let path_txt = text(p.display().to_string()).size(12).width(Length::Fill);
+        // Keep long paths from exploding the layout; the full path is on hover.
+        let path_txt = tooltip(
+            text(truncate_path(p, ROOT_PATH_MAX_CHARS))
+                .size(12)
+                .width(Length::Fill),
+            container(text(p.display().to_string()).size(12))
+                .padding(6)
+                .style(container::rounded_box),
+            tooltip::Position::Bottom,
+        );
 
         roots_list = roots_list.push(
             row![path_txt, remove_btn]
@@ -59,26 +212,253 @@ pub(crate) fn build_sidebar(state: &Sonora) -> iced::widget::Container<'_, Messa
                 .align_y(iced::Alignment::Center),
         );
     }
-    let roots_panel = scrollable(roots_list.spacing(6)).height(Length::Fixed(160.0));
+    // No inner scroll box: the sidebar itself scrolls, so the list just takes its height.
+    let roots_panel = roots_list.spacing(6);
+
+    let exclusion_input = text_input("Exclude (e.g. */podcasts/*)", &state.exclusion_input)
+        .on_input(Message::ExclusionInputChanged)
+        .on_submit(Message::AddExclusion(state.exclusion_input.clone()))
+        .width(Length::Fill);
+    let exclusion_add_row = row![
+        exclusion_input,
+        button("Add").on_press(Message::AddExclusion(state.exclusion_input.clone())),
+    ]
+    .spacing(8);
+
+    let mut exclusions_list = column![].spacing(6);
+    for (i, pattern) in state.exclusions.iter().enumerate() {
+        exclusions_list = exclusions_list.push(
+            row![
+                text(pattern).size(12).width(Length::Fill),
+                button("×").on_press(Message::RemoveExclusion(i)),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+
+    let missing_tags_btn = if state.missing_tag_report.is_some() {
+        button("✓ Missing tags").on_press(Message::CloseMissingTags)
+    } else if state.tracks.is_empty() {
+        button("Missing tags")
+    } else {
+        button("Missing tags").on_press(Message::FindMissingTags)
+    };
+
+    let check_albums_btn = if state.album_check_report.is_some() {
+        button("✓ Check albums").on_press(Message::CloseAlbumCheck)
+    } else if state.tracks.is_empty() {
+        button("Check albums")
+    } else {
+        button("Check albums").on_press(Message::CheckAlbums)
+    };
+
+    let check_files_btn = if state.tracks.is_empty() || busy {
+        button("Check files")
+    } else {
+        button("Check files").on_press(Message::CheckMissingFiles)
+    };
+    let remove_missing_btn = if state.missing_track_ids.is_empty() || busy {
+        button("Remove missing")
+    } else {
+        button(text(format!(
+            "Remove missing ({})",
+            state.missing_track_ids.len()
+        )))
+        .on_press(Message::RemoveMissingTracks)
+    };
+
+    let stats_btn = if state.show_stats {
+        button("✓ Statistics").on_press(Message::CloseStats)
+    } else {
+        button("Statistics").on_press(Message::ShowStats)
+    };
+
+    let favorites_btn = if state.view_mode == ViewMode::Favorites {
+        button("✓ Favorites")
+    } else if busy {
+        button("Favorites")
+    } else {
+        button("Favorites").on_press(Message::SetViewMode(ViewMode::Favorites))
+    };
+
+    let recent_btn = if state.view_mode == ViewMode::RecentlyPlayed {
+        button("✓ Recently played")
+    } else if busy {
+        button("Recently played")
+    } else {
+        button("Recently played").on_press(Message::SetViewMode(ViewMode::RecentlyPlayed))
+    };
 
     let playlists = column![
         text("Playlists").size(16),
         button("Library"),
-        button("Favorites (coming soon)"),
-        button("Recently added (coming soon)"),
+        missing_tags_btn,
+        check_albums_btn,
+        row![check_files_btn, remove_missing_btn].spacing(8),
+        stats_btn,
+        favorites_btn,
+        recent_btn,
     ]
     .spacing(6);
 
+    let settings_label = if state.show_settings {
+        "✓ Settings…"
+    } else {
+        "Settings…"
+    };
+    let settings_btn = button(settings_label).on_press(Message::ToggleSettingsPanel);
+
     let col = column![
         text(&state.status).size(12),
         scan_btn,
+        progress_bars,
+        scan_errors_panel,
         view_toggle,
+        compact_btn,
+        duplicates_btn,
+        replaygain_btn,
+        tag_version_btn,
+        row![import_btn, export_playlist_btn].spacing(8),
+        export_btn,
+        organize_btn,
+        organize_panel,
+        find_replace_btn,
         text("Library folders").size(16),
         add_row,
         roots_panel,
+        text("Excluded paths").size(16),
+        exclusion_add_row,
+        exclusions_list,
         playlists,
+        settings_btn,
+        settings_panel(state),
     ]
     .spacing(12);
 
     container(scrollable(col).height(Length::Fill)).padding(12)
 }
+
+/// `done / total` as a thin bar (empty until the total is known).
+fn progress_fraction_bar<'a>(done: usize, total: usize) -> ProgressBar<'a> {
+    let fraction = if total == 0 {
+        0.0
+    } else {
+        done as f32 / total as f32
+    };
+    progress_bar(0.0..=1.0, fraction).girth(Length::Fixed(PROGRESS_BAR_H))
+}
+
+/// Settings panel: Last.fm scrobbling account, AcoustID key, rating owner.
+fn settings_panel(state: &Sonora) -> iced::widget::Column<'_, Message> {
+    if !state.show_settings {
+        return column![];
+    }
+
+    let cfg = &state.scrobbler;
+    let connected = cfg.is_connected();
+
+    let status_line = if connected {
+        "Connected: finished tracks are scrobbled."
+    } else if state.lastfm_auth_token.is_some() {
+        "Waiting for approval in the browser…"
+    } else {
+        "Not connected."
+    };
+
+    let connect_btn = if connected {
+        button(text("Disconnect").size(12)).on_press(Message::DisconnectLastFm)
+    } else if cfg.has_api_credentials() {
+        button(text("Connect Last.fm").size(12)).on_press(Message::ConnectLastFm)
+    } else {
+        button(text("Connect Last.fm").size(12))
+    };
+
+    let finish_btn = if state.lastfm_auth_token.is_some() && !connected {
+        button(text("Finish connecting").size(12)).on_press(Message::FinishLastFmAuth)
+    } else {
+        button(text("Finish connecting").size(12))
+    };
+
+    let crossfade_label = match state.crossfade_ms / 1000 {
+        0 => "Crossfade: off (gapless)".to_string(),
+        secs => format!("Crossfade: {secs} s"),
+    };
+
+    // A saved device that isn't plugged in stays listed (and selected) so it's visible.
+    let selected_device = state
+        .output_device
+        .clone()
+        .unwrap_or_else(|| SYSTEM_DEFAULT_DEVICE.to_string());
+    let device_options: Vec<String> = std::iter::once(SYSTEM_DEFAULT_DEVICE.to_string())
+        .chain(state.output_device.clone())
+        .chain(state.audio_devices.iter().cloned())
+        .fold(Vec::new(), |mut out, name| {
+            if !out.contains(&name) {
+                out.push(name);
+            }
+            out
+        });
+
+    column![
+        text("Last.fm").size(14),
+        text_input("API key", &cfg.api_key)
+            .on_input(Message::LastFmApiKeyChanged)
+            .width(Length::Fill),
+        text_input("API secret", &cfg.api_secret)
+            .on_input(Message::LastFmApiSecretChanged)
+            .secure(true)
+            .width(Length::Fill),
+        row![connect_btn, finish_btn].spacing(8),
+        text(status_line).size(12),
+        text("AcoustID").size(14),
+        text_input(
+            "API key (for \"Identify by audio\")",
+            &state.acoustid_api_key
+        )
+        .on_input(Message::AcoustIdApiKeyChanged)
+        .width(Length::Fill),
+        text("External tag editor").size(14),
+        text_input("Command (e.g. open -a Mp3tag)", &state.external_tag_editor)
+            .on_input(Message::ExternalEditorChanged)
+            .width(Length::Fill),
+        text("Playback").size(14),
+        text(crossfade_label).size(12),
+        slider(
+            0.0..=(MAX_CROSSFADE_MS / 1000) as f32,
+            (state.crossfade_ms / 1000) as f32,
+            |secs| Message::SetCrossfade(secs as u64 * 1000)
+        )
+        .step(1.0)
+        .on_release(Message::SaveConfig),
+        text("Output device").size(12),
+        row![
+            pick_list(
+                device_options,
+                Some(selected_device),
+                Message::SetOutputDevice
+            )
+            .text_size(12)
+            .width(Length::Fill),
+            button(text("Refresh").size(12)).on_press(Message::LoadAudioDevices),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        text("Tag writes").size(14),
+        checkbox(state.write_options.backup)
+            .label("Back up files (.bak) before writing")
+            .on_toggle(Message::SetBackupOnWrite),
+        checkbox(state.validate_before_write)
+            .label("Check MP3 frames before saving (skip corrupt files)")
+            .on_toggle(Message::SetValidateBeforeWrite),
+        text("Albums").size(14),
+        checkbox(state.album_group_by_year)
+            .label("Split same-named albums by year (editions, reissues)")
+            .on_toggle(Message::SetAlbumGroupByYear),
+        text("Ratings").size(14),
+        text_input("POPM email (default: Last.fm user)", &state.rating_email)
+            .on_input(Message::RatingEmailChanged)
+            .width(Length::Fill),
+    ]
+    .spacing(6)
+}
@@ -0,0 +1,104 @@
+//! gui/view/stats.rs
+//! "Statistics" overview (center panel): totals plus genre/decade bar charts.
+//!
+//! - Bars are plain containers sized with `FillPortion` (count vs. the largest count).
+//! - Numbers come from `state.library_stats` (computed in update, never here).
+
+use iced::widget::{Column, button, column, container, row, scrollable, space, text};
+use iced::{Alignment, Element, Length, Theme};
+
+use super::super::state::Message;
+use super::super::util::format_total_duration_ms;
+use crate::core::stats::LibraryStats;
+
+/// Width of the label column in the bar charts.
+const BAR_LABEL_W: f32 = 140.0;
+const BAR_H: f32 = 14.0;
+
+pub(crate) fn build_stats_center(stats: &LibraryStats) -> Column<'_, Message> {
+    let header = row![
+        text("Library statistics").size(18).width(Length::Fill),
+        button(text("Close").size(12)).on_press(Message::CloseStats),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let avg_bpm = match stats.average_bpm {
+        Some(b) => format!("{b:.0}"),
+        None => "-".to_string(),
+    };
+
+    let totals = column![
+        text(format!("Tracks: {}", stats.total_tracks)).size(14),
+        text(format!(
+            "Total length: {}",
+            format_total_duration_ms(stats.total_duration_ms)
+        ))
+        .size(14),
+        text(format!("Average BPM: {avg_bpm}")).size(14),
+        text(format!("Missing artwork: {}", stats.missing_artwork)).size(14),
+    ]
+    .spacing(4);
+
+    let genres = bar_chart(
+        "Top genres",
+        stats
+            .top_genres
+            .iter()
+            .map(|(g, n)| (g.clone(), *n))
+            .collect(),
+    );
+    let decades = bar_chart(
+        "Decades",
+        stats
+            .decades
+            .iter()
+            .map(|(d, n)| (format!("{d}s"), *n))
+            .collect(),
+    );
+
+    let body = column![totals, genres, decades].spacing(20);
+
+    column![header, scrollable(body).height(Length::Fill)].spacing(12)
+}
+
+/// Titled list of (label, count) rows with a bar proportional to the largest count.
+fn bar_chart<'a>(title: &'a str, rows: Vec<(String, usize)>) -> Column<'a, Message> {
+    let mut col: Column<'a, Message> = column![text(title).size(16)].spacing(4);
+
+    if rows.is_empty() {
+        return col.push(text("No data (tags missing).").size(12));
+    }
+
+    let max = rows.iter().map(|(_, n)| *n).max().unwrap_or(1).max(1);
+    for (label, n) in rows {
+        col = col.push(
+            row![
+                text(label).size(12).width(Length::Fixed(BAR_LABEL_W)),
+                bar(n, max),
+                text(n.to_string()).size(12).width(Length::Fixed(48.0)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+    col
+}
+
+fn bar<'a>(n: usize, max: usize) -> Element<'a, Message> {
+    // FillPortion needs integers: scale to per-mille of the largest count.
+    let filled = ((n * 1000) / max).max(1) as u16;
+    let rest = 1000u16.saturating_sub(filled);
+
+    let mut r = row![
+        container(space())
+            .width(Length::FillPortion(filled))
+            .height(Length::Fixed(BAR_H))
+            .style(|theme: &Theme| container::background(theme.palette().primary)),
+    ]
+    .width(Length::Fill);
+    if rest > 0 {
+        r = r.push(space().width(Length::FillPortion(rest)));
+    }
+    r.into()
+}
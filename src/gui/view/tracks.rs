@@ -3,45 +3,173 @@
 //!
 //! - Row identity is `TrackId`, not `Vec` index.
 //! - We still iterate `state.tracks` in display order, but clicks emit messages by id.
+//! - The now-playing row gets its own marker and a tinted background.
+//! - Double-clicking a title edits it in place (Enter saves, Escape cancels).
+//! - The header sits above the scrolled rows (Up/Down scroll the selection into view).
+//! - The ♥/♡ column toggles a track's favorite flag; Favorites view lists only loved ones.
+//! - Right-click plays a row and queues everything below it ("Play from here").
+//! - Columns follow `state.visible_columns` (order) and `column_widths`; ⚙ next to the
+//!   title opens the panel that edits them.
 
-use iced::widget::{Column, column, container, mouse_area, row, scrollable, text};
-use iced::{Alignment, Length};
+use iced::widget::{
+    Column, button, checkbox, column, container, mouse_area, row, scrollable, space, text,
+    text_input,
+};
+use iced::{Alignment, Color, Element, Length, Theme};
 
-use super::super::state::{Message, Sonora};
+use super::super::state::{
+    Direction, INLINE_EDIT_ID, InspectorField, Message, SelectMode, Sonora, TRACK_LIST_ID,
+    TrackColumn, ViewMode,
+};
 use super::super::util::filename_stem;
 use super::constants::{
-    HEADER_TEXT, ROW_TEXT, TRACK_LIST_SPACING, TRACK_ROW_H, TRACK_ROW_HPAD, TRACK_ROW_VPAD,
+    COLUMN_WIDTH_STEP, HEADER_TEXT, NOW_PLAYING_TINT, ROW_TEXT, ROW_TEXT_COMPACT,
+    TRACK_LIST_SPACING, TRACK_LIST_SPACING_COMPACT, TRACK_ROW_H, TRACK_ROW_H_COMPACT,
+    TRACK_ROW_HPAD, TRACK_ROW_VPAD, TRACK_ROW_VPAD_COMPACT,
 };
 use super::widgets::fmt_duration;
+use crate::core::types::{TrackId, TrackRow};
 
 pub(crate) fn build_tracks_center(state: &Sonora) -> Column<'_, Message> {
-    column![
-        text("Tracks").size(18),
-        build_tracks_table(state).height(Length::Fill),
-    ]
-    .spacing(12)
+    if state.view_mode == ViewMode::Favorites {
+        let count = state.tracks.iter().filter(|t| t.is_favorite).count();
+        if count == 0 {
+            return column![
+                text("Favorites").size(18),
+                text("Click ♡ next to a track to add it here.").size(12),
+            ]
+            .spacing(12);
+        }
+        return with_title(state, format!("Favorites ({count})"));
+    }
+
+    with_title(state, "Tracks".to_string())
 }
 
-fn build_tracks_table(state: &Sonora) -> iced::widget::Scrollable<'_, Message> {
-    let header = row![
-        text("").size(HEADER_TEXT).width(Length::Fixed(24.0)),
-        text("#").size(HEADER_TEXT).width(Length::Fixed(44.0)),
-        text("Title").size(HEADER_TEXT).width(Length::Fixed(240.0)),
-        text("Artist").size(HEADER_TEXT).width(Length::Fixed(190.0)),
-        text("Album").size(HEADER_TEXT).width(Length::Fixed(240.0)),
-        text("Album Artist")
+/// Title row (with the ⚙ column settings toggle), the optional settings panel, the table.
+fn with_title(state: &Sonora, title: String) -> Column<'_, Message> {
+    let gear = button(text("⚙").size(14))
+        .style(button::text)
+        .padding([0, 6])
+        .on_press(Message::ToggleColumnConfig);
+    let title_row = row![text(title).size(18), gear]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+    let mut out = column![title_row].spacing(12);
+    if state.show_column_config {
+        out = out.push(build_column_config(state));
+    }
+    out.push(build_tracks_table(state).height(Length::Fill))
+}
+
+/// Column settings: visible ones first (in table order, with ◀/▶ and −/+ width),
+/// then the hidden ones in default order.
+fn build_column_config(state: &Sonora) -> Element<'_, Message> {
+    let small = |label: &'static str, msg: Option<Message>| {
+        button(text(label).size(12))
+            .padding([0, 6])
+            .on_press_maybe(msg)
+    };
+
+    let mut list = column![].spacing(4);
+    let last = state.visible_columns.len().saturating_sub(1);
+    for (i, &c) in state.visible_columns.iter().enumerate() {
+        list = list.push(
+            row![
+                checkbox(true)
+                    .label(c.settings_label())
+                    .size(14)
+                    .text_size(12)
+                    .on_toggle(move |on| Message::SetColumnVisible(c, on))
+                    .width(Length::Fixed(130.0)),
+                small(
+                    "◀",
+                    (i > 0).then_some(Message::MoveColumn(c, Direction::Up))
+                ),
+                small(
+                    "▶",
+                    (i < last).then_some(Message::MoveColumn(c, Direction::Down))
+                ),
+                space().width(Length::Fixed(12.0)),
+                small("−", Some(Message::ResizeColumn(c, -COLUMN_WIDTH_STEP))),
+                text(format!("{:.0} px", state.column_width(c)))
+                    .size(12)
+                    .width(Length::Fixed(52.0)),
+                small("+", Some(Message::ResizeColumn(c, COLUMN_WIDTH_STEP))),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+    }
+    for c in TrackColumn::ALL {
+        if state.visible_columns.contains(&c) {
+            continue;
+        }
+        list = list.push(
+            checkbox(false)
+                .label(c.settings_label())
+                .size(14)
+                .text_size(12)
+                .on_toggle(move |on| Message::SetColumnVisible(c, on)),
+        );
+    }
+
+    let footer = button(text("Reset columns").size(12)).on_press(Message::ResetColumns);
+
+    container(column![list, footer].spacing(8))
+        .padding(8)
+        .style(container::rounded_box)
+        .into()
+}
+
+/// Clickable header cell; the active sort column gets a ▲/▼ suffix.
+fn header_cell<'a>(state: &Sonora, column: TrackColumn) -> iced::widget::MouseArea<'a, Message> {
+    let sort = column.sort_column();
+    let label = if state.sort_column == sort {
+        let arrow = if state.sort_ascending { "▲" } else { "▼" };
+        format!("{} {arrow}", column.label())
+    } else {
+        column.label().to_string()
+    };
+
+    mouse_area(
+        text(label)
             .size(HEADER_TEXT)
-            .width(Length::Fixed(170.0)),
-        text("Year").size(HEADER_TEXT).width(Length::Fixed(70.0)),
-        text("Genre").size(HEADER_TEXT).width(Length::Fixed(140.0)),
-        text("Len").size(HEADER_TEXT).width(Length::Fixed(70.0)),
-    ]
-    .spacing(10)
-    .align_y(Alignment::Center);
+            .width(Length::Fixed(state.column_width(column))),
+    )
+    .on_press(Message::SetSort(sort))
+}
+
+fn build_tracks_table(state: &Sonora) -> Column<'_, Message> {
+    // The marker column is fixed; the rest follow the column settings.
+    let header = state
+        .visible_columns
+        .iter()
+        .fold(
+            row![text("").size(HEADER_TEXT).width(Length::Fixed(24.0))],
+            |r, &c| r.push(header_cell(state, c)),
+        )
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+    // Compact mode: shorter rows, smaller text, no gaps (`scroll_track_into_view` agrees).
+    let (row_h, row_vpad, row_text, spacing) = if state.compact_mode {
+        (
+            TRACK_ROW_H_COMPACT,
+            TRACK_ROW_VPAD_COMPACT,
+            ROW_TEXT_COMPACT,
+            TRACK_LIST_SPACING_COMPACT,
+        )
+    } else {
+        (TRACK_ROW_H, TRACK_ROW_VPAD, ROW_TEXT, TRACK_LIST_SPACING)
+    };
 
-    let mut col = column![header].spacing(TRACK_LIST_SPACING);
+    let mut col = column![].spacing(spacing);
 
-    for t in state.tracks.iter() {
+    // Filter + sort only change what is rendered; `state.tracks` is untouched.
+    for i in state.visible_track_indices() {
+        let t = &state.tracks[i];
         let Some(id) = t.id else {
             continue;
         };
@@ -53,61 +181,140 @@ fn build_tracks_table(state: &Sonora) -> iced::widget::Scrollable<'_, Message> {
         // Playback
         let is_now_playing = state.now_playing == Some(id);
 
-        // ▶ means "now playing". ● means "selected".
-        let marker = if is_now_playing {
+        // ♪ = now playing, ▶ = now playing + selected, ● = selected.
+        let marker = if is_now_playing && (is_selected || is_primary_selected) {
             "▶"
+        } else if is_now_playing {
+            "♪"
         } else if is_selected {
             "●"
         } else {
             ""
         };
 
-        let track_no = t.track_no.map(|n| n.to_string()).unwrap_or_default();
-        let title = t.title.clone().unwrap_or_else(|| filename_stem(&t.path));
-        let artist = t.artist.clone().unwrap_or_else(|| "Unknown".into());
-        let album = t.album.clone().unwrap_or_else(|| "Unknown".into());
-        let album_artist = t
-            .album_artist
-            .clone()
-            .or_else(|| t.artist.clone())
-            .unwrap_or_else(|| "Unknown".into());
-        let year = t.year.map(|y| y.to_string()).unwrap_or_default();
-        let genre = t.genre.clone().unwrap_or_default();
-        let len = fmt_duration(t.duration_ms);
-
-        let row_cells = row![
-            text(marker).size(ROW_TEXT).width(Length::Fixed(24.0)),
-            text(track_no).size(ROW_TEXT).width(Length::Fixed(44.0)),
-            text(title).size(ROW_TEXT).width(Length::Fixed(240.0)),
-            text(artist).size(ROW_TEXT).width(Length::Fixed(190.0)),
-            text(album).size(ROW_TEXT).width(Length::Fixed(240.0)),
-            text(album_artist)
-                .size(ROW_TEXT)
-                .width(Length::Fixed(170.0)),
-            text(year).size(ROW_TEXT).width(Length::Fixed(70.0)),
-            text(genre).size(ROW_TEXT).width(Length::Fixed(140.0)),
-            text(len).size(ROW_TEXT).width(Length::Fixed(70.0)),
-        ]
-        .spacing(10)
-        .align_y(Alignment::Center);
-
-        // First click selects; clicking the already-selected row plays it.
-        let msg = if is_primary_selected {
+        // Ctrl toggles, Shift extends; a plain click on the primary row plays it.
+        let msg = if state.modifiers.command() {
+            Message::SelectTrackModified(id, SelectMode::AddToggle)
+        } else if state.modifiers.shift() {
+            Message::SelectTrackModified(id, SelectMode::RangeExtend)
+        } else if is_primary_selected {
             Message::PlayTrack(id)
         } else {
-            Message::SelectTrack(id)
+            Message::SelectTrackModified(id, SelectMode::Single)
         };
 
-        let row_widget = mouse_area(
-            container(row_cells)
-                .padding([TRACK_ROW_VPAD, TRACK_ROW_HPAD])
-                .height(Length::Fixed(TRACK_ROW_H))
-                .width(Length::Fill),
-        )
-        .on_press(msg);
+        let mut row_cells = row![text(marker).size(row_text).width(Length::Fixed(24.0))];
+        for &c in &state.visible_columns {
+            let width = Length::Fixed(state.column_width(c));
+            let cell: Element<'_, Message> = match c {
+                TrackColumn::Favorite => mouse_area(
+                    text(if t.is_favorite { "♥" } else { "♡" })
+                        .size(row_text)
+                        .width(width),
+                )
+                .on_press(Message::ToggleFavorite(id))
+                .into(),
+                TrackColumn::Title => title_cell(state, t, id, &msg, width, row_text),
+                _ => text(cell_text(t, c)).size(row_text).width(width).into(),
+            };
+            row_cells = row_cells.push(cell);
+        }
+        let row_cells = row_cells.spacing(10).align_y(Alignment::Center);
+
+        let mut row_box = container(row_cells)
+            .padding([row_vpad, TRACK_ROW_HPAD])
+            .height(Length::Fixed(row_h))
+            .width(Length::Fill);
+        if is_now_playing {
+            row_box = row_box.style(|theme: &Theme| {
+                container::background(Color {
+                    a: NOW_PLAYING_TINT,
+                    ..theme.palette().primary
+                })
+            });
+        }
+
+        let row_widget = mouse_area(row_box)
+            .on_press(msg)
+            .on_right_press(Message::PlayFromHere(id));
 
         col = col.push(row_widget);
     }
 
-    scrollable(col).height(Length::Fill)
+    // The header stays put; only rows scroll, so row `n` sits at `n * (row + spacing)`.
+    column![
+        header,
+        scrollable(col)
+            .id(TRACK_LIST_ID)
+            .on_scroll(Message::TrackListScrolled)
+            .height(Length::Fill),
+    ]
+    .spacing(spacing)
+}
+
+/// Title cell: double-click edits in place, otherwise plain text.
+fn title_cell<'a>(
+    state: &'a Sonora,
+    t: &TrackRow,
+    id: TrackId,
+    msg: &Message,
+    width: Length,
+    text_size: f32,
+) -> Element<'a, Message> {
+    if state.inline_edit == Some((id, InspectorField::Title)) {
+        return text_input("Title", &state.inline_edit_value)
+            .id(INLINE_EDIT_ID)
+            .on_input(Message::InlineEditChanged)
+            .on_submit(Message::CommitInlineEdit(
+                id,
+                InspectorField::Title,
+                state.inline_edit_value.clone(),
+            ))
+            .size(text_size)
+            .padding(0)
+            .width(width)
+            .into();
+    }
+
+    let title = t.title.clone().unwrap_or_else(|| filename_stem(&t.path));
+    // "!" = the file was gone at the last "Check files".
+    let title = if state.missing_track_ids.contains(&id) {
+        format!("! {title}")
+    } else {
+        title
+    };
+
+    // The title cell captures the row's presses, so it forwards every one (select,
+    // or play the primary row); a double-click also opens the inline editor.
+    mouse_area(text(title).size(text_size).width(width))
+        .on_press(msg.clone())
+        .on_double_click(Message::BeginInlineEdit(id, InspectorField::Title))
+        .into()
+}
+
+/// Text of a plain (non-interactive) cell.
+fn cell_text(t: &TrackRow, column: TrackColumn) -> String {
+    match column {
+        TrackColumn::TrackNo => t.track_no.map(|n| n.to_string()).unwrap_or_default(),
+        TrackColumn::Artist => t.artist.clone().unwrap_or_else(|| "Unknown".into()),
+        TrackColumn::Album => t.album.clone().unwrap_or_else(|| "Unknown".into()),
+        TrackColumn::AlbumArtist => t
+            .album_artist
+            .clone()
+            .or_else(|| t.artist.clone())
+            .unwrap_or_else(|| "Unknown".into()),
+        TrackColumn::Year => t.year.map(|y| y.to_string()).unwrap_or_default(),
+        TrackColumn::Genre => t.genre.clone().unwrap_or_default(),
+        TrackColumn::Duration => fmt_duration(t.duration_ms),
+        // RFC 3339 timestamp; the table only shows the date part.
+        TrackColumn::DateAdded => t
+            .date_added
+            .as_deref()
+            .unwrap_or("")
+            .chars()
+            .take(10)
+            .collect(),
+        // Interactive cells are built by the caller.
+        TrackColumn::Favorite | TrackColumn::Title => String::new(),
+    }
 }
@@ -2,11 +2,16 @@
 //! Reusable helpers used across view modules.
 #![allow(dead_code)]
 
-use iced::widget::{button, column, container, image, row, slider, text, text_input};
-use iced::{Alignment, Element, Length};
+use iced::widget::{
+    Row, button, column, container, image, pick_list, row, slider, space, stack, text, text_input,
+};
+use iced::{Alignment, Color, Element, Font, Length, Theme, font};
 
-use super::super::state::{Message, Sonora};
-use super::constants::LABEL_W;
+use super::super::state::{Message, QueueFill, Sonora};
+use super::constants::{LABEL_W, WAVEFORM_BAR_SPACING, WAVEFORM_H};
+use crate::core::playback::RepeatMode;
+use crate::core::types::SyncedLyric;
+use crate::core::types::TagFormat;
 
 pub(crate) fn fmt_duration(ms: Option<u32>) -> String {
     let Some(ms) = ms else { return "-".into() };
@@ -16,6 +21,32 @@ pub(crate) fn fmt_duration(ms: Option<u32>) -> String {
     format!("{m}:{s:02}")
 }
 
+/// Channel count as a layout name: 1 -> "Mono", 2 -> "Stereo", 6 -> "5.1".
+pub(crate) fn fmt_channels(channels: Option<u16>) -> String {
+    match channels {
+        None => "-".into(),
+        Some(1) => "Mono".into(),
+        Some(2) => "Stereo".into(),
+        Some(6) => "5.1".into(),
+        Some(8) => "7.1".into(),
+        Some(n) => format!("{n} ch"),
+    }
+}
+
+/// Inspector "Tags:" value.
+pub(crate) fn fmt_tag_format(format: Option<TagFormat>) -> &'static str {
+    match format {
+        None => "-",
+        Some(TagFormat::Id3v2_2) => "ID3v2.2",
+        Some(TagFormat::Id3v2_3) => "ID3v2.3",
+        Some(TagFormat::Id3v2_4) => "ID3v2.4",
+        Some(TagFormat::Id3v1) => "ID3v1",
+        Some(TagFormat::VorbisComment) => "Vorbis comment",
+        Some(TagFormat::FlacVorbis) => "FLAC (Vorbis comment)",
+        Some(TagFormat::None) => "None (no tags found)",
+    }
+}
+
 fn fmt_duration_u64(ms: u64) -> String {
     let s = ms / 1000;
     let m = s / 60;
@@ -23,6 +54,42 @@ fn fmt_duration_u64(ms: u64) -> String {
     format!("{m}:{s:02}")
 }
 
+/// Lines shown before / after the current one in the synced lyrics panel.
+const SYNCED_LYRICS_BEFORE: usize = 2;
+const SYNCED_LYRICS_AFTER: usize = 4;
+
+/// Karaoke-style window of synced lyrics around the playhead.
+/// - The current line (latest one whose timestamp has passed) is larger and bold
+/// - Redrawn by the playback repaint ticks, so no extra messages are needed
+pub(crate) fn synced_lyrics(
+    lines: &[SyncedLyric],
+    position_ms: u64,
+) -> iced::widget::Column<'_, Message> {
+    let current = lines
+        .partition_point(|l| u64::from(l.timestamp_ms) <= position_ms)
+        .checked_sub(1);
+
+    let first = current.map_or(0, |c| c.saturating_sub(SYNCED_LYRICS_BEFORE));
+    let last = current
+        .map_or(SYNCED_LYRICS_AFTER, |c| c + SYNCED_LYRICS_AFTER)
+        .min(lines.len() - 1);
+
+    let bold = Font {
+        weight: font::Weight::Bold,
+        ..Font::DEFAULT
+    };
+
+    let mut col = column![].spacing(2).width(Length::Fill);
+    for (i, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+        col = col.push(if Some(i) == current {
+            text(&line.text).size(16).font(bold)
+        } else {
+            text(&line.text).size(13)
+        });
+    }
+    col
+}
+
 pub(crate) fn cover_placeholder(size: f32) -> iced::widget::Container<'static, Message> {
     container(
         column![text("♪").size(28), text("cover").size(12)]
@@ -85,6 +152,42 @@ pub(crate) fn num_pair_row<'a>(
     .align_y(Alignment::Center)
 }
 
+/// Collapsible equalizer: one gain slider (±12 dB) per preset band.
+fn eq_panel(state: &Sonora) -> Element<'_, Message> {
+    let mut bands = row![].spacing(16).align_y(Alignment::Center);
+
+    for (i, band) in state.eq_bands.iter().enumerate() {
+        let label = if band.freq_hz >= 1000.0 {
+            format!("{}k", band.freq_hz / 1000.0)
+        } else {
+            format!("{}", band.freq_hz)
+        };
+
+        bands = bands.push(
+            column![
+                text(format!("{label}Hz")).size(12),
+                slider(-12.0..=12.0, band.gain_db, move |g| Message::SetEqGain(
+                    i, g
+                ))
+                .step(0.5)
+                .width(Length::Fixed(110.0)),
+                text(format!("{:+.1} dB", band.gain_db)).size(12),
+            ]
+            .spacing(4)
+            .align_x(Alignment::Center),
+        );
+    }
+
+    row![
+        text("EQ").size(14),
+        bands,
+        button(text("Flat").size(12)).on_press(Message::ResetEq),
+    ]
+    .spacing(16)
+    .align_y(Alignment::Center)
+    .into()
+}
+
 /// Bottom playback bar.
 pub(crate) fn playback_bar(state: &Sonora) -> iced::widget::Container<'_, Message> {
     let engine_ready = state.playback.is_some();
@@ -108,8 +211,55 @@ pub(crate) fn playback_bar(state: &Sonora) -> iced::widget::Container<'_, Messag
         button("⏭")
     };
 
+    let shuffle_label = if state.shuffle {
+        "Shuffle: On"
+    } else {
+        "Shuffle: Off"
+    };
+    let shuffle_btn = if engine_ready {
+        button(text(shuffle_label).size(12)).on_press(Message::ToggleShuffle)
+    } else {
+        button(text(shuffle_label).size(12))
+    };
+
+    let repeat_label = match state.repeat_mode {
+        RepeatMode::Off => "Repeat: Off",
+        RepeatMode::All => "Repeat: All",
+        RepeatMode::One => "Repeat: One",
+    };
+    let repeat_btn = if engine_ready {
+        button(text(repeat_label).size(12)).on_press(Message::CycleRepeat)
+    } else {
+        button(text(repeat_label).size(12))
+    };
+
+    let fill_picker = pick_list(
+        QueueFill::ALL,
+        Some(state.queue_fill),
+        Message::SetQueueFill,
+    )
+    .text_size(12);
+
+    let queue_label = if state.show_queue {
+        format!("✓ Queue ({})", state.queue.len())
+    } else {
+        format!("Queue ({})", state.queue.len())
+    };
+    let queue_btn = button(text(queue_label).size(12)).on_press(Message::ToggleQueuePanel);
+
+    let eq_label = if state.show_eq { "✓ EQ" } else { "EQ" };
+    let eq_btn = button(text(eq_label).size(12)).on_press(Message::ToggleEqPanel);
+
+    let lyrics_label = if state.show_lyrics {
+        "✓ Lyrics"
+    } else {
+        "Lyrics"
+    };
+    let lyrics_btn = button(text(lyrics_label).size(12)).on_press(Message::ToggleLyricsPanel);
+
     // --- seek slider ---
-    let pos = state.position_ms;
+    // Interpolated between engine ticks so the slider moves continuously.
+    let pos = state.estimated_position_ms();
     let dur = state.duration_ms.unwrap_or(0);
 
     let seek_enabled = engine_ready && dur > 0;
@@ -146,6 +296,7 @@ pub(crate) fn playback_bar(state: &Sonora) -> iced::widget::Container<'_, Messag
     let vol_slider = if engine_ready {
         slider(0.0..=1.0, vol, Message::SetVolume)
             .step(0.01) // <-- fixes “mute/unmute” behavior
+            .on_release(Message::SaveConfig)
             .width(Length::Fixed(140.0))
     } else {
         slider(0.0..=1.0, vol, |_| Message::Noop)
@@ -163,18 +314,37 @@ pub(crate) fn playback_bar(state: &Sonora) -> iced::widget::Container<'_, Messag
         None => "Nothing playing".into(),
     };
 
-    let bar = row![
-        row![prev_btn, play_btn, next_btn]
-            .spacing(8)
+    let waveform = state
+        .now_playing
+        .and_then(|id| state.waveform_cache.get(&id))
+        .filter(|peaks| !peaks.is_empty())
+        .map(|peaks| waveform_strip(peaks, shown_ratio));
+
+    let mut info = column![text(now_playing).size(14)].spacing(6);
+    if let Some(w) = waveform {
+        info = info.push(w);
+    }
+    info = info.push(
+        row![seek, text(time_text).size(12)]
+            .spacing(10)
             .align_y(Alignment::Center),
-        column![
-            text(now_playing).size(14),
-            row![seek, text(time_text).size(12)]
-                .spacing(10)
-                .align_y(Alignment::Center),
+    );
+
+    let bar = row![
+        row![
+            prev_btn,
+            play_btn,
+            next_btn,
+            shuffle_btn,
+            repeat_btn,
+            fill_picker,
+            queue_btn,
+            eq_btn,
+            lyrics_btn
         ]
-        .spacing(6)
-        .width(Length::Fill),
+        .spacing(8)
+        .align_y(Alignment::Center),
+        info.width(Length::Fill),
         row![text("Vol").size(12), vol_slider]
             .spacing(8)
             .align_y(Alignment::Center),
@@ -182,5 +352,53 @@ pub(crate) fn playback_bar(state: &Sonora) -> iced::widget::Container<'_, Messag
     .spacing(16)
     .align_y(Alignment::Center);
 
-    container(bar).padding(12)
+    if state.show_eq {
+        container(column![eq_panel(state), bar].spacing(12)).padding(12)
+    } else {
+        container(bar).padding(12)
+    }
+}
+
+/// Waveform thumbnail: one thin bar per bucket (played part in the accent color)
+/// with a position line stacked on top. Plain containers, no canvas.
+fn waveform_strip(peaks: &[f32], ratio: f32) -> Element<'_, Message> {
+    let played = (ratio.clamp(0.0, 1.0) * peaks.len() as f32) as usize;
+
+    let bars = peaks
+        .iter()
+        .enumerate()
+        .fold(Row::new(), |r, (i, &v)| {
+            let h = (v.clamp(0.0, 1.0) * WAVEFORM_H).max(1.0);
+            let is_played = i < played;
+            r.push(
+                container(space())
+                    .width(Length::Fill)
+                    .height(Length::Fixed(h))
+                    .style(move |theme: &Theme| {
+                        let accent = theme.palette().primary;
+                        let color = if is_played {
+                            accent
+                        } else {
+                            Color { a: 0.35, ..accent }
+                        };
+                        container::background(color)
+                    }),
+            )
+        })
+        .spacing(WAVEFORM_BAR_SPACING)
+        .height(Length::Fixed(WAVEFORM_H))
+        .align_y(Alignment::Center);
+
+    // FillPortion needs integers: split the width in per-mille.
+    let before = (ratio.clamp(0.0, 1.0) * 1000.0) as u16;
+    let indicator = row![
+        space().width(Length::FillPortion(before.max(1))),
+        container(space())
+            .width(Length::Fixed(2.0))
+            .height(Length::Fixed(WAVEFORM_H))
+            .style(|theme: &Theme| container::background(theme.palette().text)),
+        space().width(Length::FillPortion((1000 - before).max(1))),
+    ];
+
+    stack![bars, indicator].into()
 }
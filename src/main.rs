@@ -2,16 +2,20 @@
 //!
 //! Current behavior
 //! - User adds one or more library root folders.
-//! - "Scan Library" walks roots for `.mp3` files and reads ID3 tags into `TrackRow`.
+//! - "Scan Library" walks roots for `.mp3`/`.ogg`/`.flac` files and reads their tags into `TrackRow`.
 //! - Library can be viewed as:
 //!   - Track View: flat list
 //!   - Album View: grouped by (album artist, album) with expandable album rows
 //! - Selecting a track loads an Inspector (draft fields).
 //! - "Save edits" writes the edited ID3 tags back to that single file, then re-reads it.
 //! - Audio playback
+//! - Library roots + preferences persist in `config.toml`; tag reads are cached in SQLite
+//!   so rescans only re-read new/changed files.
+//!
+//! - `sonora --scan <path>` prints the folder's tracks as JSON and exits (no GUI),
+//!   e.g. `sonora --scan /music | jq '.[].artist' | sort | uniq`.
 //!
 //! Future behavior
-//! - Persistent cache / DB
 //! - Multi-file batch editing
 
 #![forbid(unsafe_code)]
@@ -19,20 +23,56 @@
 mod core;
 mod gui;
 
+use std::path::PathBuf;
+
 use iced::{Size, window};
 
 use crate::gui::view::constants::{WINDOW_H, WINDOW_W};
-use crate::gui::{Sonora, subscription, update, view};
+use crate::gui::{Sonora, subscription, title, update, view};
 
 fn main() -> iced::Result {
-    iced::application(Sonora::default, update, view)
-        .title("Sonora")
+    // One flag doesn't need an argument parser.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(i) = args.iter().position(|a| a == "--scan") {
+        let Some(root) = args.get(i + 1) else {
+            eprintln!("usage: sonora --scan <path>");
+            std::process::exit(2);
+        };
+        std::process::exit(scan_to_stdout(PathBuf::from(root)));
+    }
+
+    iced::application(Sonora::boot, update, view)
+        .title(title)
         .subscription(subscription)
         .window(window::Settings {
             size: Size::new(WINDOW_W, WINDOW_H),
             min_size: Some(Size::new(720.0, 540.0)),
             resizable: true,
+            exit_on_close_request: false,
             ..Default::default()
         })
         .run()
 }
+
+/// CLI mode: read every track under `root` (uncached) and print them as JSON.
+/// Returns the process exit code.
+fn scan_to_stdout(root: PathBuf) -> i32 {
+    let json = core::scan_and_read_roots(&[root], None).and_then(|(rows, errors)| {
+        // Unreadable files are still listed (empty tags); say why on stderr.
+        for e in &errors {
+            eprintln!("sonora: {}: {}", e.path.display(), e.reason);
+        }
+        core::export::tracks_json(&rows)
+    });
+
+    match json {
+        Ok(json) => {
+            println!("{json}");
+            0
+        }
+        Err(e) => {
+            eprintln!("sonora: {e}");
+            1
+        }
+    }
+}